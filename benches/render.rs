@@ -0,0 +1,43 @@
+//! Parsing and rendering a full 16-game page, the largest regular season
+//! day. Run with `cargo bench`; a full page should render in well under
+//! a millisecond.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nhl_235::api_types::APIResponse;
+use nhl_235::render::{AnsiRenderer, PlainRenderer, Renderer};
+use nhl_235::{parse_games, RenderOptions};
+
+const SIXTEEN_GAMES: &str = include_str!("../tests/fixtures/sixteen_games.json");
+
+fn options() -> RenderOptions {
+    RenderOptions::default()
+        .highlights(vec![String::from("Stützle"), String::from("Aho")])
+        .show_highlights(true)
+        .show_stats(true)
+        .deterministic(true)
+}
+
+fn parsing(c: &mut Criterion) {
+    c.bench_function("parse_games", |b| {
+        b.iter(|| {
+            let scores: APIResponse = serde_json::from_str(black_box(SIXTEEN_GAMES)).unwrap();
+            parse_games(scores)
+        })
+    });
+}
+
+fn rendering(c: &mut Criterion) {
+    let scores: APIResponse = serde_json::from_str(SIXTEEN_GAMES).unwrap();
+    let games = parse_games(scores);
+    let options = options();
+
+    c.bench_function("render plain page", |b| {
+        b.iter(|| PlainRenderer.render_games(black_box(&games), &options))
+    });
+    c.bench_function("render ansi page", |b| {
+        b.iter(|| AnsiRenderer.render_games(black_box(&games), &options))
+    });
+}
+
+criterion_group!(benches, parsing, rendering);
+criterion_main!(benches);
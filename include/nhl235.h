@@ -0,0 +1,25 @@
+/*
+ * C interface to the NHL-235 renderer.
+ * Build the library with `cargo build --release --features ffi`.
+ */
+
+#ifndef NHL235_H
+#define NHL235_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Renders nhl-score-api JSON into the plain text 235 page.
+ * Returns NULL for invalid input. Free the result with nhl235_free_string.
+ */
+char *nhl235_render_json(const char *json);
+
+void nhl235_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
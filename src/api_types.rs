@@ -24,6 +24,24 @@ pub struct GameResponse {
     pub teams: TeamsResponse,
     pub pre_game_stats: PreGameStatsResponse,
     pub current_stats: CurrentStatsResponse,
+    pub venue: Option<VenueResponse>,
+    pub broadcasts: Option<Vec<BroadcastResponse>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VenueResponse {
+    pub name: String,
+    pub city: Option<String>,
+    pub neutral_site: Option<bool>,
+    pub special_event: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BroadcastResponse {
+    pub network: String,
+    pub market: Option<String>,
+    pub country: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,6 +56,15 @@ pub struct ProgressResponse {
     pub current_period: u64,
     pub current_period_ordinal: String,
     pub current_period_time_remaining: CurrentPeriodTimeRemaining,
+    pub power_play: Option<PowerPlayResponse>,
+}
+
+/// Man advantage of a live game, only sent while a power play is on
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerPlayResponse {
+    pub team: String,
+    pub time_remaining: CurrentPeriodTimeRemaining,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -105,3 +132,83 @@ pub struct TeamResponse {
     pub short_name: String,
     pub team_name: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StandingsResponse {
+    pub standings: Vec<TeamStandingResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStandingResponse {
+    pub team_abbrev: LocalizedResponse,
+    pub team_name: LocalizedResponse,
+    pub conference_name: Option<String>,
+    pub division_name: Option<String>,
+    pub division_abbrev: Option<String>,
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub ot_losses: u64,
+    pub points: u64,
+    pub division_sequence: Option<u64>,
+    pub conference_sequence: Option<u64>,
+    pub league_sequence: Option<u64>,
+    pub wildcard_sequence: Option<u64>,
+}
+
+/// Playoff bracket of a season from NHL's web API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayoffBracketResponse {
+    #[serde(default)]
+    pub series: Vec<PlayoffSeriesResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayoffSeriesResponse {
+    /// A-H in the first round, I-L in the second, M-N in the conference
+    /// finals and O in the final
+    pub series_letter: String,
+    pub playoff_round: u64,
+    /// Missing until the team is known
+    pub top_seed_team: Option<BracketTeamResponse>,
+    pub bottom_seed_team: Option<BracketTeamResponse>,
+    #[serde(default)]
+    pub top_seed_wins: u64,
+    #[serde(default)]
+    pub bottom_seed_wins: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BracketTeamResponse {
+    pub abbrev: String,
+}
+
+/// Current roster of a team from NHL's web API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RosterResponse {
+    #[serde(default)]
+    pub forwards: Vec<RosterPlayerResponse>,
+    #[serde(default)]
+    pub defensemen: Vec<RosterPlayerResponse>,
+    #[serde(default)]
+    pub goalies: Vec<RosterPlayerResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterPlayerResponse {
+    pub first_name: LocalizedResponse,
+    pub last_name: LocalizedResponse,
+    pub sweater_number: Option<u64>,
+    pub position_code: Option<String>,
+    /// Three letter country code like "FIN"
+    pub birth_country: Option<String>,
+}
+
+/// NHL's web API wraps names into objects with translations
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocalizedResponse {
+    pub default: String,
+}
@@ -0,0 +1,112 @@
+//! Player names without diacritics, Teräväinen → Teravainen, for
+//! terminals, fonts and systems downstream that can't show them.
+
+use crate::game::{Game, Player};
+use crate::roster::RosterPlayer;
+
+/// Replaces letters with diacritics by their base letters. Characters
+/// outside the table that aren't ASCII become "?" so the result is
+/// always plain ASCII.
+pub fn transliterate(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            c if c.is_ascii() => c.to_string(),
+            'ä' | 'å' | 'á' | 'à' | 'â' | 'ã' | 'ą' | 'ā' => String::from("a"),
+            'Ä' | 'Å' | 'Á' | 'À' | 'Â' | 'Ã' | 'Ą' | 'Ā' => String::from("A"),
+            'ö' | 'ø' | 'ó' | 'ò' | 'ô' | 'õ' | 'ő' => String::from("o"),
+            'Ö' | 'Ø' | 'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ő' => String::from("O"),
+            'ü' | 'ú' | 'ù' | 'û' | 'ů' | 'ű' | 'ū' => String::from("u"),
+            'Ü' | 'Ú' | 'Ù' | 'Û' | 'Ů' | 'Ű' | 'Ū' => String::from("U"),
+            'é' | 'è' | 'ê' | 'ë' | 'ě' | 'ę' | 'ē' => String::from("e"),
+            'É' | 'È' | 'Ê' | 'Ë' | 'Ě' | 'Ę' | 'Ē' => String::from("E"),
+            'í' | 'ì' | 'î' | 'ï' | 'ī' => String::from("i"),
+            'Í' | 'Ì' | 'Î' | 'Ï' | 'Ī' => String::from("I"),
+            'ý' | 'ÿ' => String::from("y"),
+            'Ý' => String::from("Y"),
+            'č' | 'ç' | 'ć' => String::from("c"),
+            'Č' | 'Ç' | 'Ć' => String::from("C"),
+            'š' | 'ś' => String::from("s"),
+            'Š' | 'Ś' => String::from("S"),
+            'ž' | 'ź' | 'ż' => String::from("z"),
+            'Ž' | 'Ź' | 'Ż' => String::from("Z"),
+            'ř' => String::from("r"),
+            'Ř' => String::from("R"),
+            'ň' | 'ñ' | 'ń' => String::from("n"),
+            'Ň' | 'Ñ' | 'Ń' => String::from("N"),
+            'ď' => String::from("d"),
+            'Ď' => String::from("D"),
+            'ť' => String::from("t"),
+            'Ť' => String::from("T"),
+            'ł' | 'ľ' | 'ĺ' => String::from("l"),
+            'Ł' | 'Ľ' | 'Ĺ' => String::from("L"),
+            'æ' => String::from("ae"),
+            'Æ' => String::from("AE"),
+            'ß' => String::from("ss"),
+            _ => String::from("?"),
+        })
+        .collect()
+}
+
+fn ascii_player(player: &mut Player) {
+    player.first_name = transliterate(&player.first_name);
+    player.last_name = transliterate(&player.last_name);
+}
+
+/// Transliterates the scorers and assisting players of every goal
+pub fn ascii_names(game: &mut Game) {
+    for goal in &mut game.goals {
+        ascii_player(&mut goal.scorer);
+        goal.assists.iter_mut().for_each(ascii_player);
+    }
+}
+
+/// Transliterates the names on a roster
+pub fn ascii_roster_names(players: &mut [RosterPlayer]) {
+    for player in players {
+        player.first_name = transliterate(&player.first_name);
+        player.last_name = transliterate(&player.last_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_transliterates_player_names() {
+        assert_eq!(transliterate("Teräväinen"), "Teravainen");
+        assert_eq!(transliterate("Pastrňák"), "Pastrnak");
+        assert_eq!(transliterate("Ørjan Åström"), "Orjan Astrom");
+        assert_eq!(transliterate("Crosby"), "Crosby");
+        assert_eq!(transliterate("Ovechkin 8"), "Ovechkin 8");
+        assert_eq!(transliterate("Овечкин"), "???????");
+    }
+
+    #[test]
+    fn it_transliterates_scorers_and_assists() -> serde_json::Result<()> {
+        let test_game: crate::api_types::GameResponse = serde_json::from_str(
+            r#"{
+                "status":{"state":"FINAL"},
+                "startTime":"2024-10-16T23:00:00Z",
+                "goals":[
+                    {"team":"CAR","period":"1","scorer":{"player":"Teuvo Teräväinen"},"assists":[{"player":"Sebastian Aho","seasonTotal":3}],"min":4,"sec":10}
+                ],
+                "scores":{"CAR":1,"CHI":0},
+                "teams":{
+                    "away":{"abbreviation":"CAR","id":12,"locationName":"Carolina","shortName":"Carolina","teamName":"Hurricanes"},
+                    "home":{"abbreviation":"CHI","id":16,"locationName":"Chicago","shortName":"Chicago","teamName":"Blackhawks"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }"#,
+        )?;
+        let mut game = crate::game::parse_game(&test_game).unwrap();
+
+        ascii_names(&mut game);
+        let goal = &game.goals[0];
+        assert_eq!(goal.scorer.first_name, "Teuvo");
+        assert_eq!(goal.scorer.last_name, "Teravainen");
+        assert_eq!(goal.assists[0].last_name, "Aho");
+        Ok(())
+    }
+}
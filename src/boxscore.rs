@@ -0,0 +1,244 @@
+//! `235 game`, a single game with everything the page leaves out: goals
+//! by period, every assist and strength, the shootout and the records of
+//! the teams.
+
+use crate::game::{Game, Goal, Player, Special};
+use crate::options::RenderOptions;
+use crate::render::{game_team_names, goal_role, score_text};
+use crate::style::{Role, StyledLine};
+
+const REGULATION_PERIODS: u64 = 3;
+
+/// Goals of the home and away teams in each period played, overtimes
+/// included, labelled "1" to "3", "OT", "2OT" and so on. Shootout goals
+/// aren't counted.
+pub fn period_scores(game: &Game) -> Vec<(String, u64, u64)> {
+    let live_period = game
+        .progress
+        .as_ref()
+        .filter(|progress| !progress.shootout)
+        .map_or(0, |progress| progress.period);
+    let overtime = match game.special {
+        Special::Regulation => REGULATION_PERIODS,
+        Special::Overtime { periods } => REGULATION_PERIODS + periods,
+        Special::Shootout => REGULATION_PERIODS + 1,
+    };
+    let last = game
+        .goals
+        .iter()
+        .filter_map(|goal| period_number(&goal.period))
+        .chain(vec![live_period, overtime])
+        .max()
+        .unwrap_or(REGULATION_PERIODS);
+
+    (1..=last)
+        .map(|period| {
+            let goals = |team: &str| {
+                game.goals
+                    .iter()
+                    .filter(|goal| goal.team == team && period_number(&goal.period) == Some(period))
+                    .count() as u64
+            };
+            (period_label(period), goals(&game.home), goals(&game.away))
+        })
+        .collect()
+}
+
+/// Period of a goal as a number, the first overtime is 4. None for the
+/// shootout.
+fn period_number(period: &str) -> Option<u64> {
+    match period {
+        "OT" => Some(REGULATION_PERIODS + 1),
+        _ => period.parse().ok(),
+    }
+}
+
+fn period_label(period: u64) -> String {
+    match period.checked_sub(REGULATION_PERIODS) {
+        None | Some(0) => period.to_string(),
+        Some(1) => String::from("OT"),
+        Some(overtime) => format!("{}OT", overtime),
+    }
+}
+
+/// The box score: teams and score, records, a table of goals by period
+/// and every goal with its assists and strength, shootout goals last
+pub fn boxscore_lines(game: &Game, options: &RenderOptions) -> Vec<StyledLine> {
+    let (home, away) = game_team_names(game, options);
+    let mut title = StyledLine::styled(format!("{} - {}", home, away), Role::Header);
+    if let Some(score) = score_text(game, options) {
+        title.push(format!(" {}", score.trim()), Role::FinalScore);
+    }
+    let mut lines = vec![title];
+
+    if let Some((home_record, away_record)) = game.records {
+        lines.push(StyledLine::styled(
+            format!(
+                "{} {}, {} {}",
+                game.home, home_record, game.away, away_record
+            ),
+            Role::Info,
+        ));
+    }
+
+    if game.status == "PREVIEW" {
+        return lines;
+    }
+
+    let periods = period_scores(game);
+    let shootout: Vec<&Goal> = game
+        .goals
+        .iter()
+        .filter(|goal| goal.period == "SO")
+        .collect();
+    let shootout_goals = |team: &str| shootout.iter().filter(|goal| goal.team == team).count();
+
+    let mut columns: Vec<String> = periods
+        .iter()
+        .map(|(label, _, _)| label.to_string())
+        .collect();
+    let mut home_row: Vec<String> = periods
+        .iter()
+        .map(|(_, home, _)| home.to_string())
+        .collect();
+    let mut away_row: Vec<String> = periods
+        .iter()
+        .map(|(_, _, away)| away.to_string())
+        .collect();
+    if !shootout.is_empty() {
+        columns.push(String::from("SO"));
+        home_row.push(shootout_goals(&game.home).to_string());
+        away_row.push(shootout_goals(&game.away).to_string());
+    }
+    columns.push(String::from("T"));
+    home_row.push(game.home_score.to_string());
+    away_row.push(game.away_score.to_string());
+
+    let row = |team: &str, cells: &[String]| {
+        let cells: Vec<String> = cells.iter().map(|cell| format!("{:>3}", cell)).collect();
+        format!("{:<4}{}", team, cells.join(" "))
+    };
+    lines.push(StyledLine::default());
+    lines.push(StyledLine::styled(row("", &columns), Role::Title));
+    lines.push(StyledLine::styled(row(&game.home, &home_row), Role::Info));
+    lines.push(StyledLine::styled(row(&game.away, &away_row), Role::Info));
+
+    let goals: Vec<&Goal> = game
+        .goals
+        .iter()
+        .filter(|goal| goal.period != "SO")
+        .collect();
+    if !goals.is_empty() || !shootout.is_empty() {
+        lines.push(StyledLine::default());
+    }
+    for goal in goals {
+        let minute = format!("{:>3}", goal.minute);
+        lines.push(goal_line(&minute, goal, options));
+    }
+    for goal in shootout {
+        lines.push(goal_line(" SO", goal, options));
+    }
+
+    lines
+}
+
+/// "32 PIT Sidney Crosby (Evgeni Malkin, Erik Karlsson) PPG"
+fn goal_line(minute: &str, goal: &Goal, options: &RenderOptions) -> StyledLine {
+    let mut text = format!("{} {} {}", minute, goal.team, full_name(&goal.scorer));
+    if !goal.assists.is_empty() {
+        let assists: Vec<String> = goal.assists.iter().map(full_name).collect();
+        text.push_str(&format!(" ({})", assists.join(", ")));
+    }
+    if let Some(strength) = goal
+        .strength
+        .as_deref()
+        .filter(|strength| *strength != "EVEN")
+    {
+        text.push_str(&format!(" {}", strength));
+    }
+    StyledLine::styled(text, goal_role(goal, options))
+}
+
+fn full_name(player: &Player) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::APIResponse;
+    use crate::game::parse_games;
+
+    fn fixture_games(json: &str) -> Vec<Game> {
+        let scores: APIResponse = serde_json::from_str(json).unwrap();
+        parse_games(scores).into_iter().flatten().collect()
+    }
+
+    #[test]
+    fn it_counts_goals_by_period() {
+        let games = fixture_games(include_str!("../tests/fixtures/overtime.json"));
+        let periods = period_scores(&games[0]);
+        assert_eq!(periods.len(), 4);
+        assert_eq!(periods[3].0, "OT");
+        let (home, away) = periods
+            .iter()
+            .fold((0, 0), |(home, away), (_, h, a)| (home + h, away + a));
+        assert_eq!((home, away), (games[0].home_score, games[0].away_score));
+    }
+
+    #[test]
+    fn it_lists_every_goal_with_its_assists() {
+        let games = fixture_games(include_str!("../tests/fixtures/live.json"));
+        let options = RenderOptions::default().deterministic(true);
+        let lines: Vec<String> = boxscore_lines(&games[0], &options)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "Pittsburgh - Toronto 2-1",
+                "PIT 5-2-1, TOR 4-3-0",
+                "",
+                "      1   2   3   T",
+                "PIT   0   1   1   2",
+                "TOR   1   0   0   1",
+                "",
+                "  4 TOR Mitch Marner (Auston Matthews)",
+                " 32 PIT Sidney Crosby (Evgeni Malkin, Erik Karlsson) PPG",
+                " 42 PIT Evgeni Malkin (Sidney Crosby)",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_shows_the_shootout() {
+        let games = fixture_games(include_str!("../tests/fixtures/shootout.json"));
+        let options = RenderOptions::default().deterministic(true);
+        let lines: Vec<String> = boxscore_lines(&games[0], &options)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+
+        assert_eq!(lines[3], "      1   2   3  OT  SO   T");
+        assert_eq!(lines[4], "NYR   0   1   0   0   2   2");
+        assert_eq!(
+            &lines[lines.len() - 3..],
+            [
+                " SO NYR Artemi Panarin",
+                " SO NJD Jesper Bratt",
+                " SO NYR Mika Zibanejad"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_labels_overtimes() {
+        assert_eq!(period_label(3), "3");
+        assert_eq!(period_label(4), "OT");
+        assert_eq!(period_label(6), "3OT");
+    }
+}
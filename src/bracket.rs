@@ -0,0 +1,165 @@
+//! `235 playoffs`, the whole playoff bracket drawn as a tree with a
+//! column per round. Each series is two lines, the teams with their wins,
+//! and the series of the next round sits between the two it follows.
+
+use crate::api_types::{PlayoffBracketResponse, PlayoffSeriesResponse};
+use crate::style::{Role, StyledLine};
+use serde::Serialize;
+
+/// Wins it takes to win a series
+pub const SERIES_WINS: u64 = 4;
+/// First round, second round, conference finals and the final
+pub const ROUNDS: u64 = 4;
+
+/// A series of the bracket. Teams are None until they are known.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Series {
+    /// 1 for the first round up to 4 for the final
+    pub round: u64,
+    pub top: Option<String>,
+    pub bottom: Option<String>,
+    pub top_wins: u64,
+    pub bottom_wins: u64,
+}
+
+/// The series in bracket order, by round and top to bottom within the
+/// round
+pub fn parse_bracket(bracket: &PlayoffBracketResponse) -> Vec<Series> {
+    let mut series: Vec<&PlayoffSeriesResponse> = bracket.series.iter().collect();
+    series.sort_by(|a, b| {
+        (a.playoff_round, &a.series_letter).cmp(&(b.playoff_round, &b.series_letter))
+    });
+    series
+        .into_iter()
+        .map(|series| Series {
+            round: series.playoff_round,
+            top: series
+                .top_seed_team
+                .as_ref()
+                .map(|team| team.abbrev.to_string()),
+            bottom: series
+                .bottom_seed_team
+                .as_ref()
+                .map(|team| team.abbrev.to_string()),
+            top_wins: series.top_seed_wins,
+            bottom_wins: series.bottom_seed_wins,
+        })
+        .collect()
+}
+
+/// Width of a series cell: a team, its wins and the connector
+const CELL_WIDTH: usize = 7;
+/// Space between the rounds
+const GAP: &str = "  ";
+
+/// The bracket as a tree, rounds from left to right. The first round has
+/// a series every four lines and every later round is centred between
+/// the two series it follows.
+pub fn bracket_lines(series: &[Series]) -> Vec<StyledLine> {
+    let rows = (1usize << (ROUNDS + 1)) - 2;
+    let mut grid: Vec<Vec<Option<(String, Role)>>> = vec![vec![None; ROUNDS as usize]; rows];
+
+    for round in 1..=ROUNDS {
+        let in_round = series.iter().filter(|series| series.round == round);
+        for (index, series) in in_round.enumerate() {
+            let top_row = (1usize << (round + 1)) * index + (1usize << round) - 2;
+            let column = round as usize - 1;
+            if top_row + 1 >= rows {
+                break;
+            }
+            grid[top_row][column] = Some(cell(&series.top, series.top_wins, '┐'));
+            grid[top_row + 1][column] = Some(cell(&series.bottom, series.bottom_wins, '┘'));
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| {
+            let last = row.iter().rposition(Option::is_some);
+            let mut line = StyledLine::default();
+            let cells = row.into_iter().take(last.map_or(0, |last| last + 1));
+            for (column, cell) in cells.enumerate() {
+                if column > 0 {
+                    line.push(GAP, Role::Text);
+                }
+                match cell {
+                    Some((text, role)) => line.push(text, role),
+                    None => line.push(" ".repeat(CELL_WIDTH), Role::Text),
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// A team of a series with its wins, the team that won the series in
+/// its own role
+fn cell(team: &Option<String>, wins: u64, connector: char) -> (String, Role) {
+    let text = format!(
+        "{:<3} {} {}",
+        team.as_deref().unwrap_or("TBD"),
+        wins,
+        connector
+    );
+    let role = if wins >= SERIES_WINS {
+        Role::FinalScore
+    } else {
+        Role::Score
+    };
+    (text, role)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(round: u64, top: &str, bottom: &str, wins: (u64, u64)) -> Series {
+        Series {
+            round,
+            top: Some(String::from(top)),
+            bottom: Some(String::from(bottom)),
+            top_wins: wins.0,
+            bottom_wins: wins.1,
+        }
+    }
+
+    #[test]
+    fn it_sorts_the_series_in_bracket_order() -> serde_json::Result<()> {
+        let bracket: PlayoffBracketResponse = serde_json::from_str(
+            r#"{"series":[
+                {"seriesLetter":"I","playoffRound":2,"topSeedTeam":{"abbrev":"FLA"},"topSeedWins":1,"bottomSeedWins":0},
+                {"seriesLetter":"B","playoffRound":1,"topSeedTeam":{"abbrev":"BOS"},"bottomSeedTeam":{"abbrev":"TOR"},"topSeedWins":4,"bottomSeedWins":3},
+                {"seriesLetter":"A","playoffRound":1,"topSeedTeam":{"abbrev":"FLA"},"bottomSeedTeam":{"abbrev":"TBL"},"topSeedWins":4,"bottomSeedWins":1}
+            ]}"#,
+        )?;
+
+        let bracket = parse_bracket(&bracket);
+        assert_eq!(bracket[0], series(1, "FLA", "TBL", (4, 1)));
+        assert_eq!(bracket[1].top.as_deref(), Some("BOS"));
+        assert_eq!(bracket[2].round, 2);
+        assert_eq!(bracket[2].bottom, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_draws_the_next_round_between_its_series() {
+        let bracket = [
+            series(1, "FLA", "TBL", (4, 1)),
+            series(1, "BOS", "TOR", (4, 3)),
+            series(2, "FLA", "BOS", (2, 1)),
+        ];
+        let lines: Vec<String> = bracket_lines(&bracket)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+
+        assert_eq!(lines[0], "FLA 4 ┐");
+        assert_eq!(lines[1], "TBL 1 ┘");
+        assert_eq!(lines[2], "         FLA 2 ┐");
+        assert_eq!(lines[3], "         BOS 1 ┘");
+        assert_eq!(lines[4], "BOS 4 ┐");
+        assert_eq!(lines[5], "TOR 3 ┘");
+        assert_eq!(lines.len(), 30);
+        assert_eq!(bracket_lines(&bracket)[0].spans[0].role, Role::FinalScore);
+    }
+}
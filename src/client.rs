@@ -0,0 +1,318 @@
+use crate::api_types::{APIResponse, PlayoffBracketResponse, RosterResponse, StandingsResponse};
+use crate::game::{parse_games, Game};
+use chrono::NaiveDate;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const SCORE_API_URL: &str = "https://nhl-score-api.herokuapp.com/api";
+pub const NHL_API_URL: &str = "https://api-web.nhle.com/v1";
+/// nhl-score-api is run by a volunteer, keep well below what it could take
+pub const DEFAULT_MAX_RPS: f64 = 1.0;
+
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Base URL of nhl-score-api used for scores
+    pub score_api_url: String,
+    /// Base URL of NHL's own web API used for standings
+    pub nhl_api_url: String,
+    pub timeout: Option<Duration>,
+    /// Requests per second shared by everything using the client,
+    /// None for no limit
+    pub max_rps: Option<f64>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            score_api_url: String::from(SCORE_API_URL),
+            nhl_api_url: String::from(NHL_API_URL),
+            timeout: None,
+            max_rps: Some(DEFAULT_MAX_RPS),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// Could not connect to the API at all
+    Connect(reqwest::Error),
+    /// API did not respond in time
+    Timeout(reqwest::Error),
+    /// API responded with an error status, like 429 Too Many Requests
+    Status(reqwest::Error),
+    /// API responded with data that doesn't match the expected format
+    Decode(reqwest::Error),
+    Other(reqwest::Error),
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_connect() {
+            ClientError::Connect(e)
+        } else if e.is_timeout() {
+            ClientError::Timeout(e)
+        } else if e.is_status() {
+            ClientError::Status(e)
+        } else if e.is_decode() {
+            ClientError::Decode(e)
+        } else {
+            ClientError::Other(e)
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Connect(e) => write!(f, "can't connect to the API: {}", e),
+            ClientError::Timeout(e) => write!(f, "API timed out: {}", e),
+            ClientError::Status(e) => write!(f, "API returned an error: {}", e),
+            ClientError::Decode(e) => write!(f, "API returned malformed data: {}", e),
+            ClientError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Token bucket allowing short bursts of up to `rate` requests and
+/// `rate` requests per second after that
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    /// Goes below zero when requests are queued up waiting for tokens
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        let capacity = rate.max(1.0);
+        TokenBucket {
+            rate,
+            capacity,
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    /// Takes a token and returns how long to wait before using it
+    fn take(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity) - 1.0;
+        self.updated = now;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Typed client for the score and standings APIs.
+///
+/// Requests are rate limited by `ClientConfig::max_rps`. Clones of a client
+/// share the same limit.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), nhl_235::ClientError> {
+/// use nhl_235::{ClientConfig, ScoreClient};
+///
+/// let client = ScoreClient::new(ClientConfig::default());
+/// let scores = client.latest().await?;
+/// println!("{} games", scores.games.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ScoreClient {
+    config: ClientConfig,
+    http: Client,
+    bucket: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl ScoreClient {
+    pub fn new(config: ClientConfig) -> Self {
+        let mut builder = Client::builder();
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let http = builder.build().unwrap_or_default();
+        let bucket = config
+            .max_rps
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| Arc::new(Mutex::new(TokenBucket::new(rate, Instant::now()))));
+
+        ScoreClient {
+            config,
+            http,
+            bucket,
+        }
+    }
+
+    /// Waits until the rate limit allows the next request
+    async fn throttle(&self) {
+        let wait = match &self.bucket {
+            Some(bucket) => match bucket.lock() {
+                Ok(mut bucket) => bucket.take(Instant::now()),
+                Err(_) => Duration::ZERO,
+            },
+            None => Duration::ZERO,
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// GETs `url` within the rate limit. Error statuses like 429 are errors
+    /// instead of bodies that fail to decode.
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, ClientError> {
+        self.throttle().await;
+        let body = self
+            .http
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(body)
+    }
+
+    /// Fetches the latest scores: live games or the previous night's results
+    pub async fn latest(&self) -> Result<APIResponse, ClientError> {
+        let url = format!("{}/scores/latest", self.config.score_api_url);
+        self.get_json(&url).await
+    }
+
+    /// Fetches the scores of a single day
+    pub async fn for_date(&self, date: NaiveDate) -> Result<APIResponse, ClientError> {
+        let url = self.date_range_url(date, date);
+        let days: Vec<APIResponse> = self.get_json(&url).await?;
+
+        Ok(days.into_iter().next().unwrap_or(APIResponse {
+            date: None,
+            games: Vec::new(),
+            errors: None,
+        }))
+    }
+
+    /// Fetches the scores of every day from `start` to `end`, both included
+    pub async fn date_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<APIResponse>, ClientError> {
+        let url = self.date_range_url(start, end);
+        self.get_json(&url).await
+    }
+
+    /// The latest scores as the API sent them, for debugging the parsing
+    pub async fn latest_json(&self) -> Result<serde_json::Value, ClientError> {
+        let url = format!("{}/scores/latest", self.config.score_api_url);
+        self.get_json(&url).await
+    }
+
+    /// The scores of every day from `start` to `end` as the API sent them
+    pub async fn date_range_json(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<serde_json::Value, ClientError> {
+        let url = self.date_range_url(start, end);
+        self.get_json(&url).await
+    }
+
+    /// Fetches the current league standings
+    pub async fn standings(&self) -> Result<StandingsResponse, ClientError> {
+        let url = format!("{}/standings/now", self.config.nhl_api_url);
+        self.get_json(&url).await
+    }
+
+    /// Fetches the playoff bracket of the season ending in the spring of
+    /// `year`, 2024 for 2023-24
+    pub async fn playoff_bracket(&self, year: i32) -> Result<PlayoffBracketResponse, ClientError> {
+        let url = format!("{}/playoff-bracket/{}", self.config.nhl_api_url, year);
+        self.get_json(&url).await
+    }
+
+    /// Fetches the current roster of a team by its abbreviation, like "PIT"
+    pub async fn roster(&self, team: &str) -> Result<RosterResponse, ClientError> {
+        let url = format!("{}/roster/{}/current", self.config.nhl_api_url, team);
+        self.get_json(&url).await
+    }
+
+    fn date_range_url(&self, start: NaiveDate, end: NaiveDate) -> String {
+        format!(
+            "{}/scores?startDate={}&endDate={}",
+            self.config.score_api_url,
+            start.format("%Y-%m-%d"),
+            end.format("%Y-%m-%d")
+        )
+    }
+}
+
+/// Fetches and parses the latest games, or the games of `dates` one day
+/// after another when given. This is all a bot or a widget needs to show
+/// the page:
+///
+/// ```no_run
+/// use nhl_235::{fetch_games, render_games, ClientConfig, RenderOptions, ScoreClient};
+///
+/// # async fn page() -> Result<String, nhl_235::ClientError> {
+/// let client = ScoreClient::new(ClientConfig::default());
+/// let games = fetch_games(&client, &[]).await?;
+/// Ok(render_games(&games, &RenderOptions::default()))
+/// # }
+/// ```
+pub async fn fetch_games(
+    client: &ScoreClient,
+    dates: &[NaiveDate],
+) -> Result<Vec<Option<Game>>, ClientError> {
+    if dates.is_empty() {
+        return Ok(parse_games(client.latest().await?));
+    }
+    let mut games = Vec::new();
+    for date in dates {
+        games.extend(parse_games(client.for_date(*date).await?));
+    }
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_date_range_urls() {
+        let client = ScoreClient::new(ClientConfig::default());
+        let date = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+
+        assert_eq!(
+            client.date_range_url(date, date),
+            "https://nhl-score-api.herokuapp.com/api/scores?startDate=2024-03-09&endDate=2024-03-09"
+        );
+    }
+
+    #[test]
+    fn it_spaces_requests_out_after_a_burst() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, start);
+        assert_eq!(bucket.take(start), Duration::ZERO);
+        assert_eq!(bucket.take(start), Duration::ZERO);
+        assert_eq!(bucket.take(start), Duration::from_millis(500));
+        assert_eq!(bucket.take(start), Duration::from_millis(1000));
+
+        let later = start + Duration::from_secs(10);
+        assert_eq!(bucket.take(later), Duration::ZERO);
+
+        let mut slow = TokenBucket::new(0.5, start);
+        assert_eq!(slow.take(start), Duration::ZERO);
+        assert_eq!(slow.take(start), Duration::from_secs(2));
+    }
+}
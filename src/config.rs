@@ -0,0 +1,634 @@
+//! Settings read from `$HOME/.235.toml`.
+//!
+//! Every setting is optional and command line flags take precedence over them.
+//! Highlighted players are still read from `$HOME/.235.config`.
+//!
+//! Both files can be moved between machines as one bundle with
+//! `235 config export` and `235 config import`.
+
+use dirs::home_dir;
+use nhl_235::events::QuietHours;
+use nhl_235::options::MAX_SPACING;
+use nhl_235::teams::edit_distance;
+use nhl_235::{Layout, StatsFormat, StatsStyle};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Blank lines after each game, 0-2
+    #[serde(deserialize_with = "spacing_in_range")]
+    pub spacing: Option<usize>,
+    /// Named groups of teams for `--team`, like `mine = ["PIT", "CAR"]`
+    pub teams: HashMap<String, Vec<String>>,
+    /// Teams whose results `--outcome-colors` colours, names or groups
+    /// like with `--team`
+    pub favorites: Vec<String>,
+    /// Names to print for teams, like `tampa = "Tampa"`. Keys are team
+    /// names or abbreviations like with `--team`
+    pub team_names: HashMap<String, String>,
+    pub stats: StatsConfig,
+    pub layout: LayoutConfig,
+    pub history: HistoryConfig,
+    pub tui: TuiConfig,
+    pub notify: NotifyConfig,
+    pub timeouts: Timeouts,
+}
+
+/// Rejects spacing the page can't have, so that the error points to its line
+fn spacing_in_range<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<usize>, D::Error> {
+    let spacing = usize::deserialize(deserializer)?;
+    if spacing > MAX_SPACING {
+        return Err(serde::de::Error::custom(format!(
+            "invalid spacing {}, use 0-{}",
+            spacing, MAX_SPACING
+        )));
+    }
+    Ok(Some(spacing))
+}
+
+/// `[stats]` section for the look of the `--stats` footer
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatsConfig {
+    /// "plus" for 1+1, "points" for 2 pts or "labeled" for G:1 A:1
+    pub format: Option<StatsFormat>,
+    /// Wrap the footer in parentheses, on by default
+    pub parentheses: Option<bool>,
+}
+
+impl StatsConfig {
+    pub fn style(&self) -> StatsStyle {
+        let default = StatsStyle::default();
+        StatsStyle {
+            format: self.format.unwrap_or(default.format),
+            parentheses: self.parentheses.unwrap_or(default.parentheses),
+        }
+    }
+}
+
+/// `[layout]` section, a preset and the widths of single columns on top
+/// of it, like `preset = "wide"` and `name_width = 18`
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutConfig {
+    /// "classic", "wide" or "narrow", classic by default
+    pub preset: Option<String>,
+    pub name_width: Option<usize>,
+    pub minute_width: Option<usize>,
+    pub score_width: Option<usize>,
+}
+
+impl LayoutConfig {
+    /// The column widths, checked so that the columns stay aligned
+    pub fn layout(&self) -> Result<Layout, String> {
+        let preset: Layout = match &self.preset {
+            Some(preset) => preset.parse()?,
+            None => Layout::default(),
+        };
+        Layout {
+            name_width: self.name_width.unwrap_or(preset.name_width),
+            minute_width: self.minute_width.unwrap_or(preset.minute_width),
+            score_width: self.score_width.unwrap_or(preset.score_width),
+        }
+        .validate()
+    }
+}
+
+/// `[history]` section for the archive `235 history` reads
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    /// Keep the finished games of every fetch in the archive, off by default
+    pub archive: Option<bool>,
+    /// Archive file instead of `$HOME/.235.results.jsonl`
+    pub file: Option<PathBuf>,
+}
+
+impl HistoryConfig {
+    pub fn archive(&self) -> bool {
+        self.archive.unwrap_or(false)
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        self.file.clone().or_else(|| {
+            let mut path = home_dir()?;
+            path.push(".235.results.jsonl");
+            Some(path)
+        })
+    }
+}
+
+/// `[tui]` section for `235 tui`
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiConfig {
+    /// Keys for the actions of the TUI under `[tui.keys]`, like
+    /// `refresh = "u"`, see `tui::Keymap`
+    pub keys: HashMap<String, String>,
+    /// Clicks on games and links, on by default. Off leaves the mouse to
+    /// the terminal for selecting text.
+    pub mouse: Option<bool>,
+}
+
+impl TuiConfig {
+    pub fn mouse(&self) -> bool {
+        self.mouse.unwrap_or(true)
+    }
+}
+
+/// `[timeouts]` section, seconds each kind of network operation may take
+/// before it's given up so that one hung server can't stall the whole run
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timeouts {
+    pub api: Option<u64>,
+    pub webhook: Option<u64>,
+    pub email: Option<u64>,
+}
+
+impl Timeouts {
+    pub fn api(&self) -> Duration {
+        Duration::from_secs(self.api.unwrap_or(10))
+    }
+
+    pub fn webhook(&self) -> Duration {
+        Duration::from_secs(self.webhook.unwrap_or(10))
+    }
+
+    pub fn email(&self) -> Duration {
+        Duration::from_secs(self.email.unwrap_or(30))
+    }
+}
+
+/// Warns on stderr in verbose mode when an operation used more than half
+/// of its timeout, a sign that it may soon start failing
+pub fn warn_if_slow(verbose: bool, operation: &str, elapsed: Duration, timeout: Duration) {
+    if verbose && elapsed > timeout / 2 {
+        eprintln!(
+            "WARNING: {} took {:.1}s, the timeout is {}s",
+            operation,
+            elapsed.as_secs_f64(),
+            timeout.as_secs()
+        );
+    }
+}
+
+/// `[notify]` section used by `235 notify-once`
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotifyConfig {
+    /// URL that new events are POSTed to as JSON
+    pub webhook: Option<String>,
+    /// Name of a keyring secret holding the webhook URL, used instead of `webhook`
+    pub webhook_secret: Option<String>,
+    /// Events during these hours are held back and sent together afterwards
+    pub quiet_hours: Option<QuietHours>,
+    /// Pinged after every run, with `/fail` appended when the run failed,
+    /// like a healthchecks.io check URL
+    pub healthcheck: Option<String>,
+    pub email: Option<EmailConfig>,
+}
+
+/// `[notify.email]` section for the morning digest. The SMTP password
+/// is read from the keyring if `password_secret` is set, otherwise from
+/// the NHL235_SMTP_PASSWORD environment variable.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    pub smtp_port: Option<u16>,
+    /// Use STARTTLS (usually port 587) instead of TLS from the start (port 465)
+    #[serde(default)]
+    pub starttls: bool,
+    pub username: Option<String>,
+    /// Name of a keyring secret holding the SMTP password
+    pub password_secret: Option<String>,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, std::io::Error),
+    /// `line` is 1-based, when the error can be pinned to a line
+    Parse {
+        path: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
+    Write(PathBuf, std::io::Error),
+    Bundle(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read(path, e) => write!(f, "Can't read {}: {}", path.display(), e),
+            ConfigError::Parse {
+                path,
+                line: Some(line),
+                message,
+            } => write!(f, "{}:{}: {}", path.display(), line, message),
+            ConfigError::Parse {
+                path,
+                line: None,
+                message,
+            } => write!(f, "Invalid config in {}: {}", path.display(), message),
+            ConfigError::Write(path, e) => write!(f, "Can't write {}: {}", path.display(), e),
+            ConfigError::Bundle(e) => write!(f, "Invalid settings bundle: {}", e),
+        }
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    let mut path = home_dir()?;
+    path.push(".235.toml");
+    Some(path)
+}
+
+/// Highlighted players, one per line
+pub fn highlights_path() -> Option<PathBuf> {
+    let mut path = home_dir()?;
+    path.push(".235.config");
+    Some(path)
+}
+
+/// Tells when the config or the highlights file has changed, so that
+/// watch mode and the TUI can pick up new settings without a restart
+pub struct ConfigWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl ConfigWatcher {
+    pub fn new() -> ConfigWatcher {
+        ConfigWatcher::of(config_path().into_iter().chain(highlights_path()).collect())
+    }
+
+    fn of(paths: Vec<PathBuf>) -> ConfigWatcher {
+        ConfigWatcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+        }
+    }
+
+    /// True when a file was written, created or removed since the
+    /// previous check
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+        for (path, seen) in self.files.iter_mut() {
+            let modified = modified(path);
+            if modified != *seen {
+                *seen = modified;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Reads the config file. A missing file is the same as an empty one.
+pub fn read_config() -> Result<Config, ConfigError> {
+    let path = match config_path() {
+        Some(path) => path,
+        None => return Ok(Config::default()),
+    };
+    match fs::read_to_string(&path) {
+        Ok(contents) => parse_config(&contents).map_err(|e| {
+            let (line, message) = describe_error(&contents, &e);
+            ConfigError::Parse {
+                path,
+                line,
+                message,
+            }
+        }),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(ConfigError::Read(path, e)),
+    }
+}
+
+fn parse_config(contents: &str) -> Result<Config, toml::de::Error> {
+    toml::from_str(contents)
+}
+
+/// Explains a parse error by the line, key and section it's on, suggesting
+/// the closest known key for a misspelled one
+fn describe_error(contents: &str, error: &toml::de::Error) -> (Option<usize>, String) {
+    let message = error.message().trim();
+    let start = match error.span() {
+        Some(span) => span.start.min(contents.len()),
+        None => return (None, message.to_string()),
+    };
+    let before = &contents[..start];
+    let line = before.matches('\n').count() + 1;
+    let section = before
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with('[') && line.ends_with(']'));
+    let text = contents.lines().nth(line - 1).unwrap_or("");
+    let key = text.split('=').next().unwrap_or("").trim();
+    let locate = |key: &str| match section {
+        Some(section) => format!("`{}` in {}", key, section),
+        None => format!("`{}`", key),
+    };
+
+    let message = if let Some(unknown) = message.strip_prefix("unknown field ") {
+        // "unknown field `colour`, expected one of `spacing`, `teams`"
+        let mut fields = unknown.split('`').skip(1).step_by(2);
+        let field = fields.next().unwrap_or(key);
+        let closest = fields
+            .map(|known| (edit_distance(field, known), known))
+            .min_by_key(|(distance, _)| *distance)
+            .filter(|(distance, _)| *distance <= (field.chars().count() / 4).max(1));
+        match closest {
+            Some((_, known)) => format!("unknown key {}, did you mean `{}`?", locate(field), known),
+            None => format!("unknown key {}", locate(field)),
+        }
+    } else if message.is_empty() || key.is_empty() {
+        String::from("invalid TOML")
+    } else {
+        format!("invalid {}: {}", locate(key), message)
+    };
+    (Some(line), message)
+}
+
+/// Settings that stay on the machine they were set on, as (section, key).
+/// The SMTP password is never in the config to begin with.
+const SECRETS: [(&str, &str); 2] = [("notify", "webhook"), ("notify", "healthcheck")];
+
+/// All settings in one portable TOML file
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+struct Bundle {
+    highlights: Vec<String>,
+    config: toml::Table,
+}
+
+/// Bundles the contents of `$HOME/.235.toml` and the highlighted players
+/// into one TOML file, leaving secrets out
+pub fn export_bundle(config: &str, highlights: &[String]) -> Result<String, ConfigError> {
+    let mut config: toml::Table =
+        toml::from_str(config).map_err(|e| ConfigError::Bundle(e.to_string()))?;
+    for (section, key) in SECRETS.iter() {
+        if let Some(toml::Value::Table(values)) = config.get_mut(*section) {
+            values.remove(*key);
+            if values.is_empty() {
+                config.remove(*section);
+            }
+        }
+    }
+    let bundle = Bundle {
+        highlights: highlights.to_vec(),
+        config,
+    };
+    let contents = toml::to_string(&bundle).map_err(|e| ConfigError::Bundle(e.to_string()))?;
+
+    Ok(format!(
+        "# nhl-235 settings, apply with `235 config import <file>`\n{}",
+        contents
+    ))
+}
+
+/// Reads a bundle made by `export_bundle` into new contents for
+/// `$HOME/.235.toml` and the highlighted players. Secrets of the `current`
+/// config are kept.
+pub fn import_bundle(bundle: &str, current: &str) -> Result<(String, Vec<String>), ConfigError> {
+    let bundle: Bundle = toml::from_str(bundle).map_err(|e| ConfigError::Bundle(e.to_string()))?;
+    let current: toml::Table =
+        toml::from_str(current).map_err(|e| ConfigError::Bundle(e.to_string()))?;
+
+    let mut config = bundle.config;
+    for (section, key) in SECRETS.iter() {
+        let secret = current
+            .get(*section)
+            .and_then(|section| section.get(*key))
+            .cloned();
+        if let Some(secret) = secret {
+            let section = config
+                .entry(section.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(section) = section {
+                section.insert(key.to_string(), secret);
+            }
+        }
+    }
+
+    let contents = toml::to_string(&config).map_err(|e| ConfigError::Bundle(e.to_string()))?;
+    parse_config(&contents).map_err(|e| ConfigError::Bundle(e.to_string()))?;
+
+    Ok((contents, bundle.highlights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_config() {
+        assert_eq!(parse_config("").unwrap(), Config::default());
+        assert_eq!(parse_config("spacing = 0").unwrap().spacing, Some(0));
+        assert!(parse_config("spacing = \"wide\"").is_err());
+        assert!(parse_config("colour = true").is_err());
+
+        let config = parse_config("[teams]\nmine = [\"PIT\", \"canes\"]").unwrap();
+        assert_eq!(config.teams["mine"], ["PIT", "canes"]);
+        assert_eq!(
+            parse_config("favorites = [\"PIT\"]").unwrap().favorites,
+            ["PIT"]
+        );
+        let config = parse_config("[team_names]\nTBL = \"Tampa\"").unwrap();
+        assert_eq!(config.team_names["TBL"], "Tampa");
+        let config = parse_config("[tui.keys]\nrefresh = \"u\"").unwrap();
+        assert_eq!(config.tui.keys["refresh"], "u");
+        assert!(config.tui.mouse());
+        assert!(!parse_config("[tui]\nmouse = false").unwrap().tui.mouse());
+
+        let config = parse_config("[notify]\nwebhook = \"https://example.com/hook\"").unwrap();
+        assert_eq!(
+            config.notify.webhook.as_deref(),
+            Some("https://example.com/hook")
+        );
+        assert!(parse_config("[notify]\nquiet_hours = \"00:30-07:30\"")
+            .unwrap()
+            .notify
+            .quiet_hours
+            .is_some());
+        assert!(parse_config("[notify]\nquiet_hours = \"night\"").is_err());
+
+        let email = parse_config(
+            "[notify.email]\nsmtp_host = \"smtp.example.com\"\nfrom = \"235 <235@example.com>\"\nto = \"me@example.com\"",
+        )
+        .unwrap()
+        .notify
+        .email
+        .unwrap();
+        assert_eq!(email.smtp_host, "smtp.example.com");
+        assert!(!email.starttls);
+        assert_eq!(email.password_secret, None);
+
+        let notify = parse_config("[notify]\nwebhook_secret = \"slack\"")
+            .unwrap()
+            .notify;
+        assert_eq!(notify.webhook_secret.as_deref(), Some("slack"));
+
+        let notify = parse_config("[notify]\nhealthcheck = \"https://hc-ping.com/abc\"")
+            .unwrap()
+            .notify;
+        assert_eq!(
+            notify.healthcheck.as_deref(),
+            Some("https://hc-ping.com/abc")
+        );
+
+        let timeouts = parse_config("[timeouts]\nwebhook = 3").unwrap().timeouts;
+        assert_eq!(timeouts.webhook(), Duration::from_secs(3));
+        assert_eq!(timeouts.api(), Duration::from_secs(10));
+        assert!(parse_config("[timeouts]\nsmtp = 3").is_err());
+
+        let stats = parse_config("[stats]\nformat = \"points\"\nparentheses = false")
+            .unwrap()
+            .stats
+            .style();
+        assert_eq!(stats.format, StatsFormat::Points);
+        assert!(!stats.parentheses);
+        assert_eq!(Config::default().stats.style(), StatsStyle::default());
+        assert!(parse_config("[stats]\nformat = \"goals\"").is_err());
+    }
+
+    #[test]
+    fn it_reads_the_history_settings() {
+        let history = Config::default().history;
+        assert!(!history.archive());
+
+        let config =
+            parse_config("[history]\narchive = true\nfile = \"/tmp/results.jsonl\"").unwrap();
+        assert!(config.history.archive());
+        assert_eq!(
+            config.history.path(),
+            Some(PathBuf::from("/tmp/results.jsonl"))
+        );
+    }
+
+    #[test]
+    fn it_builds_the_layout_from_a_preset_and_widths() {
+        assert_eq!(Config::default().layout.layout(), Ok(Layout::CLASSIC));
+
+        let layout = parse_config("[layout]\npreset = \"wide\"\nname_width = 18")
+            .unwrap()
+            .layout
+            .layout()
+            .unwrap();
+        assert_eq!(layout.name_width, 18);
+        assert_eq!(layout.minute_width, Layout::WIDE.minute_width);
+
+        let config = parse_config("[layout]\nscore_width = 4").unwrap();
+        assert_eq!(
+            config.layout.layout(),
+            Err(String::from(
+                "invalid layout, score width 4 is not within 6-10"
+            ))
+        );
+        let config = parse_config("[layout]\npreset = \"huge\"").unwrap();
+        assert!(config.layout.layout().is_err());
+        assert!(parse_config("[layout]\nwidth = 20").is_err());
+    }
+
+    #[test]
+    fn it_moves_settings_between_machines_without_secrets() {
+        let laptop = "spacing = 0\n[teams]\nmine = [\"PIT\"]\n[notify]\nwebhook = \"https://example.com/laptop\"\nhealthcheck = \"https://hc-ping.com/laptop\"\nquiet_hours = \"00:30-07:30\"";
+        let bundle = export_bundle(laptop, &[String::from("Crosby")]).unwrap();
+        assert!(bundle.contains("Crosby"));
+        assert!(!bundle.contains("example.com"));
+        assert!(!bundle.contains("hc-ping.com"));
+
+        let server = "[notify]\nwebhook = \"https://example.com/server\"\nhealthcheck = \"https://hc-ping.com/server\"";
+        let (contents, highlights) = import_bundle(&bundle, server).unwrap();
+        assert_eq!(highlights, ["Crosby"]);
+        let config = parse_config(&contents).unwrap();
+        assert_eq!(config.spacing, Some(0));
+        assert_eq!(config.teams["mine"], ["PIT"]);
+        assert!(config.notify.quiet_hours.is_some());
+        assert_eq!(
+            config.notify.webhook.as_deref(),
+            Some("https://example.com/server")
+        );
+        assert_eq!(
+            config.notify.healthcheck.as_deref(),
+            Some("https://hc-ping.com/server")
+        );
+
+        assert!(import_bundle("[config]\nspacing = \"wide\"", "").is_err());
+        assert!(import_bundle("colour = true", "").is_err());
+    }
+
+    #[test]
+    fn it_points_to_the_line_of_an_error() {
+        let describe = |contents: &str| {
+            let error = parse_config(contents).unwrap_err();
+            describe_error(contents, &error)
+        };
+
+        assert_eq!(
+            describe("spacing = 1\n\n[stats]\nformat = \"plus\"\nparenthesis = false"),
+            (
+                Some(5),
+                String::from("unknown key `parenthesis` in [stats], did you mean `parentheses`?")
+            )
+        );
+        assert_eq!(
+            describe("colour = true"),
+            (Some(1), String::from("unknown key `colour`"))
+        );
+        assert_eq!(
+            describe("[notify]\nquiet_hours = \"night\""),
+            (
+                Some(2),
+                String::from(
+                    "invalid `quiet_hours` in [notify]: invalid quiet hours 'night', use HH:MM-HH:MM"
+                )
+            )
+        );
+        assert_eq!(
+            describe("# roomier\nspacing = 7"),
+            (
+                Some(2),
+                String::from("invalid `spacing`: invalid spacing 7, use 0-2")
+            )
+        );
+        assert_eq!(
+            describe("spacing = "),
+            (Some(1), String::from("invalid TOML"))
+        );
+    }
+
+    #[test]
+    fn it_notices_changed_config_files() {
+        let path = std::env::temp_dir().join(format!("nhl-235-watch-{}.toml", std::process::id()));
+        fs::write(&path, "spacing = 1").unwrap();
+        let mut watcher = ConfigWatcher::of(vec![path.clone()]);
+        assert!(!watcher.changed());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::write(&path, "spacing = 2").unwrap();
+        assert!(watcher.changed());
+        fs::remove_file(&path).unwrap();
+    }
+}
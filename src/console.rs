@@ -0,0 +1,26 @@
+//! Windows consoles default to a legacy code page like 850 or 1252, which
+//! turns names like Teräväinen into mojibake when the output goes through a
+//! pipe or an older terminal. Switching the console to UTF-8 (65001) at
+//! startup makes every renderer's output come out as written.
+
+#[cfg(windows)]
+const CP_UTF8: u32 = 65001;
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn SetConsoleOutputCP(code_page: u32) -> i32;
+}
+
+/// Sets the console output code page to UTF-8. Does nothing outside
+/// Windows, where terminals already expect UTF-8.
+#[cfg(windows)]
+pub fn use_utf8_output() {
+    // Failing just means there is no console, like when writing to a file
+    unsafe {
+        SetConsoleOutputCP(CP_UTF8);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn use_utf8_output() {}
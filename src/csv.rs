@@ -0,0 +1,87 @@
+//! Every goal of the shown days as CSV, one row per goal, for spreadsheets.
+//! Shootout attempts aren't goals and are left out.
+
+use crate::game::{Goal, Player};
+use crate::render::Section;
+
+pub const CSV_HEADER: &str = "date,home,away,scorer,assists,minute,period,strength";
+
+/// The goals of all games in `sections` under a header row. Assists are
+/// listed in one field, separated by semicolons.
+pub fn goals_csv(sections: &[Section]) -> String {
+    let mut output = String::from(CSV_HEADER);
+    output.push('\n');
+    for section in sections {
+        for game in section.games.iter().flatten() {
+            for goal in game.goals.iter().filter(|goal| goal.period != "SO") {
+                let row = [
+                    section.title.to_string(),
+                    game.home.to_string(),
+                    game.away.to_string(),
+                    full_name(&goal.scorer),
+                    assists(goal),
+                    goal.minute.to_string(),
+                    goal.period.to_string(),
+                    goal.strength.clone().unwrap_or_default(),
+                ];
+                let fields: Vec<String> = row.iter().map(|field| escape(field)).collect();
+                output.push_str(&fields.join(","));
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
+fn full_name(player: &Player) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+}
+
+fn assists(goal: &Goal) -> String {
+    goal.assists
+        .iter()
+        .map(full_name)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Quotes a field that has a comma, a quote or a line break in it
+pub(crate) fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::APIResponse;
+    use crate::game::parse_games;
+
+    #[test]
+    fn it_writes_a_row_per_goal() {
+        let json = include_str!("../tests/fixtures/live.json");
+        let scores: APIResponse = serde_json::from_str(json).unwrap();
+        let sections = [Section {
+            title: String::from("2024-10-15"),
+            games: parse_games(scores),
+        }];
+
+        let csv = goals_csv(&sections);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], CSV_HEADER);
+        assert_eq!(
+            rows[2],
+            "2024-10-15,PIT,TOR,Sidney Crosby,Evgeni Malkin; Erik Karlsson,32,2,PPG"
+        );
+    }
+
+    #[test]
+    fn it_quotes_fields_with_commas_and_quotes() {
+        assert_eq!(escape("Crosby"), "Crosby");
+        assert_eq!(escape("Crosby, Sidney"), "\"Crosby, Sidney\"");
+        assert_eq!(escape("Sid \"The Kid\""), "\"Sid \"\"The Kid\"\"\"");
+    }
+}
@@ -0,0 +1,953 @@
+use crate::game::{estimate_goal_time, power_play, Game, Special, SHOOTOUT_MINUTE};
+use crate::style::{Role, StyledLine};
+use crate::teams::translate_team_name;
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration as StdDuration;
+
+/// Something that happened in a game since the previous check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    GameStarted {
+        /// `Game::id` of the game, `#[serde(default)]` keeps held back
+        /// events saved by older versions readable
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+    },
+    Goal {
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+        team: String,
+        scorer: String,
+        minute: u64,
+        /// Score after the goal as "home-away"
+        score: String,
+    },
+    PowerPlay {
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+        /// Team with the man advantage
+        team: String,
+        /// Time left of the power play, "1:23"
+        time_remaining: String,
+    },
+    PeriodStarted {
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+        period: u64,
+        /// Period as the API names it, "2nd" or "OT"
+        ordinal: String,
+        score: String,
+    },
+    /// A game went to overtime or a shootout
+    OvertimeStarted {
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+        period: u64,
+        /// "OT", "2OT" or "SO"
+        ordinal: String,
+        score: String,
+    },
+    PeriodEnded {
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+        period: u64,
+        ordinal: String,
+        score: String,
+    },
+    GameEnded {
+        #[serde(default)]
+        game_id: String,
+        home: String,
+        away: String,
+        score: String,
+        special: String,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::GameStarted { home, away, .. } => write!(f, "{} started", teams(home, away)),
+            Event::Goal {
+                home,
+                away,
+                scorer,
+                minute,
+                score,
+                ..
+            } => write!(f, "{} {} {} {}", teams(home, away), score, scorer, minute),
+            Event::PowerPlay {
+                home,
+                away,
+                team,
+                time_remaining,
+                ..
+            } => write!(
+                f,
+                "{} PP {} {}",
+                teams(home, away),
+                translate_team_name(team),
+                time_remaining
+            ),
+            Event::PeriodStarted {
+                home,
+                away,
+                ordinal,
+                score,
+                ..
+            } => write!(
+                f,
+                "{} {} starting {}",
+                teams(home, away),
+                period_name(ordinal),
+                score
+            ),
+            Event::OvertimeStarted {
+                home,
+                away,
+                ordinal,
+                score,
+                ..
+            } => write!(f, "{} to {} {}", teams(home, away), ordinal, score),
+            Event::PeriodEnded {
+                home,
+                away,
+                ordinal,
+                score,
+                ..
+            } => write!(
+                f,
+                "{} {} ended {}",
+                teams(home, away),
+                period_name(ordinal),
+                score
+            ),
+            Event::GameEnded {
+                home,
+                away,
+                score,
+                special,
+                ..
+            } if special.is_empty() => write!(f, "{} {} final", teams(home, away), score),
+            Event::GameEnded {
+                home,
+                away,
+                score,
+                special,
+                ..
+            } => write!(f, "{} {} {} final", teams(home, away), special, score),
+        }
+    }
+}
+
+impl Event {
+    /// Identifies the event across runs so that it's never delivered twice,
+    /// like "2024-10-16T23:00:00Z-TOR-PIT/goal/1-0/Crosby"
+    pub fn id(&self) -> String {
+        match self {
+            Event::GameStarted { game_id, .. } => format!("{}/start", game_id),
+            Event::Goal {
+                game_id,
+                score,
+                scorer,
+                ..
+            } => format!("{}/goal/{}/{}", game_id, score, scorer),
+            Event::PowerPlay {
+                game_id,
+                team,
+                time_remaining,
+                ..
+            } => format!("{}/pp/{}/{}", game_id, team, time_remaining),
+            Event::PeriodStarted {
+                game_id, period, ..
+            } => format!("{}/period/{}/start", game_id, period),
+            Event::PeriodEnded {
+                game_id, period, ..
+            } => format!("{}/period/{}/end", game_id, period),
+            Event::OvertimeStarted {
+                game_id, period, ..
+            } => format!("{}/overtime/{}", game_id, period),
+            Event::GameEnded { game_id, .. } => format!("{}/end", game_id),
+        }
+    }
+
+    /// The `type` the event is written with in JSON, like "goal"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::GameStarted { .. } => "game_started",
+            Event::Goal { .. } => "goal",
+            Event::PowerPlay { .. } => "power_play",
+            Event::PeriodStarted { .. } => "period_started",
+            Event::OvertimeStarted { .. } => "overtime_started",
+            Event::PeriodEnded { .. } => "period_ended",
+            Event::GameEnded { .. } => "game_ended",
+        }
+    }
+}
+
+/// A delivered event in the event log, one JSON object per line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedEvent {
+    pub id: String,
+    /// When the event was delivered, RFC 3339 in UTC
+    pub time: String,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+impl LoggedEvent {
+    pub fn new(event: &Event, time: DateTime<Utc>) -> Self {
+        LoggedEvent {
+            id: event.id(),
+            time: time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            event: event.clone(),
+        }
+    }
+
+    /// Events with an unreadable time are treated as old
+    pub fn is_since(&self, since: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.time).is_ok_and(|time| time >= since)
+    }
+}
+
+/// Reads an event log written one `LoggedEvent` per line
+pub fn parse_event_log(contents: &str) -> Result<Vec<LoggedEvent>, serde_json::Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect()
+}
+
+/// "2nd period" for regular periods, "OT" and "SO" as they are
+fn period_name(ordinal: &str) -> String {
+    if ordinal.ends_with(|c: char| c.is_ascii_lowercase()) {
+        format!("{} period", ordinal)
+    } else {
+        ordinal.to_string()
+    }
+}
+
+fn teams(home: &str, away: &str) -> String {
+    format!(
+        "{} - {}",
+        translate_team_name(home),
+        translate_team_name(away)
+    )
+}
+
+/// What has already been reported of a single game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameState {
+    pub status: String,
+    pub goals: usize,
+    /// Team on a power play at the previous check
+    #[serde(default)]
+    pub power_play: Option<String>,
+    /// Period at the previous check, 0 before the game
+    #[serde(default)]
+    pub period: u64,
+    /// The period had ended at the previous check
+    #[serde(default)]
+    pub intermission: bool,
+}
+
+const REGULATION_PERIODS: u64 = 3;
+
+/// Which events to report besides starts, goals, power plays and results
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Abbreviations of teams whose period starts and ends are reported
+    pub followed_teams: Vec<String>,
+    /// Report every game of the league going to overtime or a shootout
+    pub alert_overtime: bool,
+}
+
+impl WatchOptions {
+    fn follows(&self, game: &Game) -> bool {
+        self.followed_teams.contains(&game.home) || self.followed_teams.contains(&game.away)
+    }
+}
+
+/// What has already been reported, persisted between runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyState {
+    pub games: HashMap<String, GameState>,
+    /// Events held back during quiet hours
+    #[serde(default)]
+    pub pending: Vec<Event>,
+    /// Game day of the latest digest that was sent
+    #[serde(default)]
+    pub digest_date: Option<String>,
+}
+
+/// True once every game of the day is over and the results
+/// won't change anymore
+pub fn all_games_over(games: &[Option<Game>]) -> bool {
+    let mut games = games.iter().flatten().peekable();
+    games.peek().is_some() && games.all(|game| game.status == "FINAL" || game.status == "POSTPONED")
+}
+
+/// Wait between refreshes while a followed game is live
+pub const LIVE_REFRESH: StdDuration = StdDuration::from_secs(30);
+/// Wait between refreshes while the followed games are yet to start
+pub const IDLE_REFRESH: StdDuration = StdDuration::from_secs(10 * 60);
+/// Refreshes stop for the rest of the night once every game is over after
+/// midnight, and start again at this hour when the next games are out
+const RESUME_HOUR: u32 = 12;
+
+/// How long watch mode and the TUI wait at `now` before refreshing the
+/// `games`. Games of `followed_teams`, or all games when no team is
+/// followed, are refreshed every 30 seconds while one of them is live and
+/// every 10 minutes otherwise, sooner if one starts before that. When
+/// every one of them is over between midnight and midday, refreshing
+/// waits until midday.
+pub fn refresh_delay<'a, Tz: TimeZone>(
+    games: impl IntoIterator<Item = &'a Game>,
+    followed_teams: &[String],
+    now: &DateTime<Tz>,
+) -> StdDuration {
+    let followed: Vec<&Game> = games
+        .into_iter()
+        .filter(|game| {
+            followed_teams.is_empty()
+                || followed_teams.contains(&game.home)
+                || followed_teams.contains(&game.away)
+        })
+        .collect();
+    if followed.iter().any(|game| game.status == "LIVE") {
+        return LIVE_REFRESH;
+    }
+    let over = !followed.is_empty()
+        && followed
+            .iter()
+            .all(|game| game.status == "FINAL" || game.status == "POSTPONED");
+    if over && now.naive_local().hour() < RESUME_HOUR {
+        return until_resume(now);
+    }
+    let upcoming: Vec<&Game> = followed
+        .into_iter()
+        .filter(|game| game.status == "PREVIEW")
+        .collect();
+    let next_start = upcoming
+        .iter()
+        .filter_map(|game| DateTime::parse_from_rfc3339(&game.start_time).ok())
+        .min();
+    match next_start {
+        // A game past its start time that isn't live yet is checked often
+        Some(start) => (start.with_timezone(&Utc) - now.with_timezone(&Utc))
+            .to_std()
+            .unwrap_or(LIVE_REFRESH)
+            .clamp(LIVE_REFRESH, IDLE_REFRESH),
+        None => IDLE_REFRESH,
+    }
+}
+
+/// Time from `now` to the next `RESUME_HOUR` on the local clock
+fn until_resume<Tz: TimeZone>(now: &DateTime<Tz>) -> StdDuration {
+    let now = now.naive_local();
+    let mut resume = now
+        .date()
+        .and_hms_opt(RESUME_HOUR, 0, 0)
+        .expect("resume hour is a valid time");
+    if resume <= now {
+        resume += Duration::days(1);
+    }
+    (resume - now).to_std().unwrap_or(IDLE_REFRESH)
+}
+
+/// Time of day when notifications are held back, like "00:30-07:30".
+/// The range can wrap over midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid quiet hours '{}', use HH:MM-HH:MM", s);
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M");
+        Ok(QuietHours {
+            start: parse(start).map_err(|_| invalid())?,
+            end: parse(end).map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl TryFrom<String> for QuietHours {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Identifies a game in the state file. Kept apart from `Game::id` so
+/// that state files written by older versions still match.
+fn game_key(game: &Game) -> String {
+    format!("{} {}-{}", game.start_time, game.away, game.home)
+}
+
+/// Compares the games to what has been reported before and returns the new
+/// events in the order they happened with the updated state.
+/// Games that are no longer in the response are dropped from the state.
+pub fn diff(
+    previous: &NotifyState,
+    games: &[Option<Game>],
+    watch: &WatchOptions,
+) -> (Vec<Event>, NotifyState) {
+    let mut events = Vec::new();
+    let mut state = NotifyState::default();
+
+    for game in games.iter().flatten() {
+        let key = game_key(game);
+        let seen = previous.games.get(&key).cloned().unwrap_or_default();
+        let started = game.status == "LIVE" || game.status == "FINAL";
+        let was_started = seen.status == "LIVE" || seen.status == "FINAL";
+
+        if started && !was_started {
+            events.push(Event::GameStarted {
+                game_id: game.id.to_string(),
+                home: game.home.to_string(),
+                away: game.away.to_string(),
+            });
+        }
+
+        // Shootout attempts aren't goals, the result is told when the game ends
+        let goals: Vec<_> = game
+            .goals
+            .iter()
+            .filter(|goal| !(game.special == Special::Shootout && goal.minute == SHOOTOUT_MINUTE))
+            .collect();
+        let (mut home_score, mut away_score) = (0, 0);
+        for (index, goal) in goals.iter().enumerate() {
+            if goal.team == game.home {
+                home_score += 1;
+            } else {
+                away_score += 1;
+            }
+            if index >= seen.goals {
+                events.push(Event::Goal {
+                    game_id: game.id.to_string(),
+                    home: game.home.to_string(),
+                    away: game.away.to_string(),
+                    team: goal.team.to_string(),
+                    scorer: goal.scorer.last_name.to_string(),
+                    minute: goal.minute,
+                    score: format!("{}-{}", home_score, away_score),
+                });
+            }
+        }
+
+        // A power play is reported when it starts or the advantage changes
+        // hands, not again on every check while it lasts
+        let power_play = power_play(game);
+        if let Some(pp) = power_play {
+            if seen.power_play.as_deref() != Some(pp.team.as_str()) {
+                events.push(Event::PowerPlay {
+                    game_id: game.id.to_string(),
+                    home: game.home.to_string(),
+                    away: game.away.to_string(),
+                    team: pp.team.to_string(),
+                    time_remaining: pp.time_remaining.to_string(),
+                });
+            }
+        }
+
+        // The first period starts with the game, later ones are the cue
+        // to get back to the stream
+        let progress = game.progress.as_ref().filter(|_| game.status == "LIVE");
+        let score = format!("{}-{}", game.home_score, game.away_score);
+        let period_started = progress.filter(|progress| {
+            progress.period > seen.period && progress.period > 1 && !progress.intermission
+        });
+
+        // Overtime is told to everyone who asked, which covers its start
+        // for followed games too
+        let overtime = period_started
+            .filter(|progress| watch.alert_overtime && progress.period > REGULATION_PERIODS);
+        if let Some(progress) = overtime {
+            events.push(Event::OvertimeStarted {
+                game_id: game.id.to_string(),
+                home: game.home.to_string(),
+                away: game.away.to_string(),
+                period: progress.period,
+                ordinal: progress.ordinal.to_string(),
+                score: score.to_string(),
+            });
+        }
+
+        if let Some(progress) = progress.filter(|_| watch.follows(game)) {
+            if period_started.is_some() && overtime.is_none() {
+                events.push(Event::PeriodStarted {
+                    game_id: game.id.to_string(),
+                    home: game.home.to_string(),
+                    away: game.away.to_string(),
+                    period: progress.period,
+                    ordinal: progress.ordinal.to_string(),
+                    score: score.to_string(),
+                });
+            }
+            if progress.intermission && !(seen.intermission && seen.period == progress.period) {
+                events.push(Event::PeriodEnded {
+                    game_id: game.id.to_string(),
+                    home: game.home.to_string(),
+                    away: game.away.to_string(),
+                    period: progress.period,
+                    ordinal: progress.ordinal.to_string(),
+                    score: score.to_string(),
+                });
+            }
+        }
+
+        if game.status == "FINAL" && seen.status != "FINAL" {
+            events.push(Event::GameEnded {
+                game_id: game.id.to_string(),
+                home: game.home.to_string(),
+                away: game.away.to_string(),
+                score: game.score.to_string(),
+                special: game.special.to_string(),
+            });
+        }
+
+        state.games.insert(
+            key,
+            GameState {
+                status: game.status.to_string(),
+                goals: goals.len(),
+                power_play: power_play.map(|pp| pp.team.to_string()),
+                period: progress.map_or(seen.period, |progress| progress.period),
+                intermission: progress.is_some_and(|progress| progress.intermission),
+            },
+        );
+    }
+
+    (events, state)
+}
+
+/// What happened between two snapshots of the same games, the events
+/// `diff` reports when it has already seen `before` and checks `after`
+pub fn changes(
+    before: &[Option<Game>],
+    after: &[Option<Game>],
+    watch: &WatchOptions,
+) -> Vec<Event> {
+    let (_, seen) = diff(&NotifyState::default(), before, watch);
+    diff(&seen, after, watch).0
+}
+
+/// Follows the goals from one watch mode refresh to the next and keeps
+/// the latest one for the banner over the page
+#[derive(Debug, Clone, Default)]
+pub struct GoalTracker {
+    seen: NotifyState,
+    latest: Option<Event>,
+}
+
+impl GoalTracker {
+    /// Takes the games of a refresh, one slice per day, and returns the
+    /// latest goal so far. A goal that shows up in a refresh is newer than
+    /// every goal of the refreshes before it. Among the goals of a single
+    /// refresh, like the first one, the one estimated to have been scored
+    /// last on the clock wins.
+    pub fn update(&mut self, days: &[&[Option<Game>]]) -> Option<&Event> {
+        let mut seen = NotifyState::default();
+        let mut latest = None;
+        for games in days {
+            let (events, state) = diff(&self.seen, games, &WatchOptions::default());
+            seen.games.extend(state.games);
+            for event in events {
+                if let Event::Goal {
+                    game_id, minute, ..
+                } = &event
+                {
+                    let time = games
+                        .iter()
+                        .flatten()
+                        .find(|game| &game.id == game_id)
+                        .and_then(|game| {
+                            let goal = game.goals.iter().find(|goal| goal.minute == *minute)?;
+                            estimate_goal_time(game, goal)
+                        });
+                    if latest
+                        .as_ref()
+                        .is_none_or(|(latest_time, _)| time >= *latest_time)
+                    {
+                        latest = Some((time, event));
+                    }
+                }
+            }
+        }
+        self.seen = seen;
+        if let Some((_, event)) = latest {
+            self.latest = Some(event);
+        }
+        self.latest.as_ref()
+    }
+}
+
+/// A line per event, goals and results in the colours of the page
+pub fn event_lines(events: &[Event]) -> Vec<StyledLine> {
+    if events.is_empty() {
+        return vec![StyledLine::styled("No changes.", Role::Text)];
+    }
+    events
+        .iter()
+        .map(|event| {
+            let role = match event {
+                Event::Goal { .. } => Role::Goal,
+                Event::GameEnded { .. } => Role::FinalScore,
+                Event::PowerPlay { .. } => Role::Notice,
+                _ => Role::Info,
+            };
+            StyledLine::styled(event.to_string(), role)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{test_game_json, test_games};
+    use chrono::TimeZone;
+
+    fn games(state: &str, goals: &str) -> Vec<Option<Game>> {
+        games_with_status(&format!(r#"{{"state":"{}"}}"#, state), goals)
+    }
+
+    fn games_with_status(status: &str, goals: &str) -> Vec<Option<Game>> {
+        test_games(&[test_game_json(
+            status,
+            "2021-01-23T19:00:00Z",
+            ("TOR", 1),
+            ("PIT", 1),
+            goals,
+        )])
+    }
+
+    const MARNER: &str =
+        r#"{"team":"TOR","period":"1","scorer":{"player":"Mitch Marner"},"min":4,"sec":10}"#;
+    const CROSBY: &str =
+        r#"{"team":"PIT","period":"2","scorer":{"player":"Sidney Crosby"},"min":4,"sec":27}"#;
+
+    #[test]
+    fn it_knows_quiet_hours() {
+        let night: QuietHours = "00:30-07:30".parse().unwrap();
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert!(night.contains(time(4, 0)));
+        assert!(!night.contains(time(7, 30)));
+        assert!(!night.contains(time(23, 0)));
+
+        let over_midnight: QuietHours = "23:00-06:00".parse().unwrap();
+        assert!(over_midnight.contains(time(23, 30)));
+        assert!(over_midnight.contains(time(5, 0)));
+        assert!(!over_midnight.contains(time(12, 0)));
+
+        assert!("7-8".parse::<QuietHours>().is_err());
+    }
+
+    #[test]
+    fn it_refreshes_faster_while_games_are_live() {
+        // The game starts at 19:00 UTC
+        let at = |h, m| Utc.with_ymd_and_hms(2021, 1, 23, h, m, 0).unwrap();
+        let delay = |state: &str, now| {
+            let games = games(state, "");
+            refresh_delay(games.iter().flatten(), &[], &now)
+        };
+        assert_eq!(delay("LIVE", at(20, 0)), LIVE_REFRESH);
+        assert_eq!(delay("PREVIEW", at(12, 0)), IDLE_REFRESH);
+        assert_eq!(delay("PREVIEW", at(18, 55)), StdDuration::from_secs(5 * 60));
+        assert_eq!(delay("PREVIEW", at(19, 5)), LIVE_REFRESH);
+        // A finished game is still checked now and then before midnight,
+        // and after it until midday
+        assert_eq!(delay("FINAL", at(23, 0)), IDLE_REFRESH);
+        assert_eq!(
+            delay("FINAL", at(4, 0)),
+            StdDuration::from_secs(8 * 60 * 60)
+        );
+        assert_eq!(delay("FINAL", at(11, 30)), StdDuration::from_secs(30 * 60));
+        assert_eq!(delay("FINAL", at(13, 0)), IDLE_REFRESH);
+
+        // Only followed games count, and a team without a game isn't over
+        let games = games("LIVE", "");
+        let followed = [String::from("CAR")];
+        assert_eq!(
+            refresh_delay(games.iter().flatten(), &followed, &at(20, 0)),
+            IDLE_REFRESH
+        );
+        assert_eq!(
+            refresh_delay(games.iter().flatten(), &followed, &at(4, 0)),
+            IDLE_REFRESH
+        );
+    }
+
+    #[test]
+    fn it_tags_events_with_the_game_id() {
+        let (events, _) = diff(
+            &NotifyState::default(),
+            &games("LIVE", MARNER),
+            &WatchOptions::default(),
+        );
+        let json = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(
+            json["game_id"],
+            games("LIVE", MARNER)[0].as_ref().unwrap().id
+        );
+
+        // Held back events saved before ids were added
+        let old: Event =
+            serde_json::from_str(r#"{"type":"game_started","home":"TOR","away":"PIT"}"#).unwrap();
+        assert_eq!(old.to_string(), "Toronto - Pittsburgh started");
+    }
+
+    #[test]
+    fn it_logs_events_by_id() {
+        let (events, _) = diff(
+            &NotifyState::default(),
+            &games("LIVE", MARNER),
+            &WatchOptions::default(),
+        );
+        let delivered = Utc.with_ymd_and_hms(2021, 1, 23, 19, 5, 0).unwrap();
+        let log: String = events
+            .iter()
+            .map(|event| serde_json::to_string(&LoggedEvent::new(event, delivered)).unwrap() + "\n")
+            .collect();
+
+        let logged = parse_event_log(&log).unwrap();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[1].id, "2021-01-23T19:00:00Z-PIT-TOR/goal/1-0/Marner");
+        assert_eq!(logged[1].time, "2021-01-23T19:05:00Z");
+        assert_eq!(logged[1].event, events[1]);
+        assert!(logged[0].is_since(delivered));
+        assert!(!logged[0].is_since(delivered + chrono::Duration::seconds(1)));
+    }
+
+    #[test]
+    fn it_knows_when_the_game_day_is_over() {
+        assert!(!all_games_over(&[]));
+        assert!(!all_games_over(&games("LIVE", MARNER)));
+        assert!(all_games_over(&games("FINAL", MARNER)));
+    }
+
+    #[test]
+    fn it_reports_only_new_events() {
+        let (events, state) = diff(
+            &NotifyState::default(),
+            &games("LIVE", MARNER),
+            &WatchOptions::default(),
+        );
+        let messages: Vec<String> = events.iter().map(Event::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "Toronto - Pittsburgh started",
+                "Toronto - Pittsburgh 1-0 Marner 4"
+            ]
+        );
+
+        let (events, state) = diff(&state, &games("LIVE", MARNER), &WatchOptions::default());
+        assert!(events.is_empty());
+
+        let both = format!("{},{}", MARNER, CROSBY);
+        let (events, _) = diff(&state, &games("FINAL", &both), &WatchOptions::default());
+        let messages: Vec<String> = events.iter().map(Event::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "Toronto - Pittsburgh 1-1 Crosby 24",
+                "Toronto - Pittsburgh 1-1 final"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_tells_what_changed_between_snapshots() {
+        let both = format!("{},{}", MARNER, CROSBY);
+        let events = changes(
+            &games("LIVE", MARNER),
+            &games("FINAL", &both),
+            &WatchOptions::default(),
+        );
+        let lines: Vec<String> = event_lines(&events).iter().map(StyledLine::text).collect();
+        assert_eq!(
+            lines,
+            [
+                "Toronto - Pittsburgh 1-1 Crosby 24",
+                "Toronto - Pittsburgh 1-1 final"
+            ]
+        );
+        assert_eq!(event_lines(&events)[0].spans[0].role, Role::Goal);
+        assert_eq!(events[1].kind(), "game_ended");
+
+        let same = changes(
+            &games("LIVE", MARNER),
+            &games("LIVE", MARNER),
+            &WatchOptions::default(),
+        );
+        assert_eq!(event_lines(&same)[0].text(), "No changes.");
+    }
+
+    #[test]
+    fn it_keeps_the_latest_goal_between_refreshes() {
+        let scorer = |event: Option<&Event>| match event {
+            Some(Event::Goal { scorer, .. }) => scorer.to_string(),
+            _ => String::new(),
+        };
+        let mut tracker = GoalTracker::default();
+        assert_eq!(tracker.update(&[&games("PREVIEW", "")]), None);
+
+        let both = format!("{},{}", MARNER, CROSBY);
+        assert_eq!(scorer(tracker.update(&[&games("LIVE", &both)])), "Crosby");
+        assert_eq!(scorer(tracker.update(&[&games("LIVE", &both)])), "Crosby");
+
+        let tracker = &mut GoalTracker::default();
+        tracker.update(&[&games("LIVE", CROSBY)]);
+        let later = format!("{},{}", CROSBY, MARNER);
+        assert_eq!(scorer(tracker.update(&[&games("LIVE", &later)])), "Marner");
+    }
+
+    #[test]
+    fn it_reports_a_power_play_once() {
+        let status = |team: &str| {
+            format!(
+                r#"{{"state":"LIVE","progress":{{"currentPeriod":2,"currentPeriodOrdinal":"2nd","currentPeriodTimeRemaining":{{"pretty":"08:12","min":8,"sec":12}},"powerPlay":{{"team":"{}","timeRemaining":{{"pretty":"1:58","min":1,"sec":58}}}}}}}}"#,
+                team
+            )
+        };
+        let (_, state) = diff(
+            &NotifyState::default(),
+            &games("LIVE", MARNER),
+            &WatchOptions::default(),
+        );
+
+        let (events, state) = diff(
+            &state,
+            &games_with_status(&status("PIT"), MARNER),
+            &WatchOptions::default(),
+        );
+        let messages: Vec<String> = events.iter().map(Event::to_string).collect();
+        assert_eq!(messages, ["Toronto - Pittsburgh PP Pittsburgh 1:58"]);
+
+        let (events, state) = diff(
+            &state,
+            &games_with_status(&status("PIT"), MARNER),
+            &WatchOptions::default(),
+        );
+        assert!(events.is_empty());
+
+        let (events, _) = diff(
+            &state,
+            &games_with_status(&status("TOR"), MARNER),
+            &WatchOptions::default(),
+        );
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn it_reports_periods_of_followed_games() {
+        let status = |period: u64, ordinal: &str, min: u64| {
+            format!(
+                r#"{{"state":"LIVE","progress":{{"currentPeriod":{},"currentPeriodOrdinal":"{}","currentPeriodTimeRemaining":{{"pretty":"{}:00","min":{},"sec":0}}}}}}"#,
+                period, ordinal, min, min
+            )
+        };
+        let watch = WatchOptions {
+            followed_teams: vec![String::from("PIT")],
+            ..WatchOptions::default()
+        };
+        let (_, state) = diff(&NotifyState::default(), &games("LIVE", MARNER), &watch);
+
+        let first_ended = games_with_status(&status(1, "1st", 0), MARNER);
+        let (events, state) = diff(&state, &first_ended, &watch);
+        let messages: Vec<String> = events.iter().map(Event::to_string).collect();
+        assert_eq!(messages, ["Toronto - Pittsburgh 1st period ended 1-1"]);
+        assert!(diff(&state, &first_ended, &watch).0.is_empty());
+
+        let second = games_with_status(&status(2, "2nd", 20), MARNER);
+        let (events, state) = diff(&state, &second, &watch);
+        assert_eq!(
+            events[0].to_string(),
+            "Toronto - Pittsburgh 2nd period starting 1-1"
+        );
+        assert!(diff(&state, &second, &watch).0.is_empty());
+
+        let overtime = games_with_status(&status(4, "OT", 5), MARNER);
+        let (events, _) = diff(&state, &overtime, &watch);
+        assert_eq!(
+            events[0].to_string(),
+            "Toronto - Pittsburgh OT starting 1-1"
+        );
+
+        // Nobody follows this game
+        let (events, _) = diff(&state, &overtime, &WatchOptions::default());
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn it_alerts_about_overtime_in_any_game() {
+        let status = |period: u64, ordinal: &str| {
+            format!(
+                r#"{{"state":"LIVE","progress":{{"currentPeriod":{},"currentPeriodOrdinal":"{}","currentPeriodTimeRemaining":{{"pretty":"05:00","min":5,"sec":0}}}}}}"#,
+                period, ordinal
+            )
+        };
+        let watch = WatchOptions {
+            alert_overtime: true,
+            ..WatchOptions::default()
+        };
+        let third = games_with_status(&status(3, "3rd"), MARNER);
+        let (events, state) = diff(&NotifyState::default(), &third, &watch);
+        assert_eq!(events.len(), 2, "start and goal only");
+
+        let overtime = games_with_status(&status(4, "OT"), MARNER);
+        let (events, state) = diff(&state, &overtime, &watch);
+        let messages: Vec<String> = events.iter().map(Event::to_string).collect();
+        assert_eq!(messages, ["Toronto - Pittsburgh to OT 1-1"]);
+        assert!(diff(&state, &overtime, &watch).0.is_empty());
+
+        let shootout = games_with_status(&status(5, "SO"), MARNER);
+        let (events, _) = diff(&state, &shootout, &watch);
+        assert_eq!(events[0].to_string(), "Toronto - Pittsburgh to SO 1-1");
+
+        // Followed games get the overtime alert instead of a period start
+        let both = WatchOptions {
+            followed_teams: vec![String::from("PIT")],
+            alert_overtime: true,
+        };
+        let (events, _) = diff(&state, &shootout, &both);
+        assert_eq!(events.len(), 1);
+    }
+}
@@ -0,0 +1,145 @@
+//! Common ways to run 235, for `235 examples` and the end of `235 --help`.
+//! The commands are checked against the argument parser in the tests of
+//! main.rs so that they keep working as the flags change.
+
+/// A command and what it's good for
+pub struct Example {
+    pub command: &'static str,
+    pub description: &'static str,
+}
+
+/// Examples under a topic like watching games or a status bar
+pub struct Topic {
+    pub title: &'static str,
+    pub examples: &'static [Example],
+}
+
+const fn example(command: &'static str, description: &'static str) -> Example {
+    Example {
+        command,
+        description,
+    }
+}
+
+pub const TOPICS: [Topic; 6] = [
+    Topic {
+        title: "Scores",
+        examples: &[
+            example("235", "Latest results and live games"),
+            example("235 --team PIT --team leafs", "Only the games of some teams"),
+            example("235 --days 3 --duration", "The last three days with game lengths"),
+            example("235 game PIT-TOR", "Box score of one game"),
+        ],
+    },
+    Topic {
+        title: "Watching live games",
+        examples: &[
+            example(
+                "235 --watch --interval 30",
+                "Refresh the page every 30 seconds with the latest goal on top",
+            ),
+            example(
+                "235 --watch --team FLA --close-games",
+                "Follow one team and mark close games",
+            ),
+        ],
+    },
+    Topic {
+        title: "Highlighted players",
+        examples: &[
+            example(
+                "235 --highlight --stats",
+                "Players from $HOME/.235.config in colour with their points",
+            ),
+            example(
+                "235 --stats --players Aho,Barkov",
+                "Points of players without a config file",
+            ),
+            example("235 player Crosby --days 7", "Goals and assists over a week"),
+        ],
+    },
+    Topic {
+        title: "Status bars and narrow panes",
+        examples: &[
+            example(
+                "235 --abbr --collapse-others --tight",
+                "Compact page with one line per game",
+            ),
+            example(
+                "235 --layout narrow --initials",
+                "Narrow columns for a split terminal",
+            ),
+            example(
+                "235 --format tsv --team PIT",
+                "A line per game and goal to cut into a status bar",
+            ),
+        ],
+    },
+    Topic {
+        title: "Cron digests and notifications",
+        examples: &[
+            example(
+                "235 notify-once",
+                "New goals and final scores to the webhook in $HOME/.235.toml, every few minutes from cron",
+            ),
+            example(
+                "235 --days 1 --format markdown",
+                "Yesterday's games as Markdown for a daily email",
+            ),
+            example("235 --days 1 --format html", "The same as a web page"),
+        ],
+    },
+    Topic {
+        title: "Standings and teams",
+        examples: &[
+            example("235 standings --division MET", "One division"),
+            example("235 h2h PIT TOR", "This season's games between two teams"),
+            example("235 teams", "Every team and the names --team accepts"),
+            example(
+                "235 history --team FLA --last 10",
+                "Latest games of a team from the local archive, offline",
+            ),
+        ],
+    },
+];
+
+/// Every example under its topic, the commands lined up
+pub fn examples_text() -> String {
+    let width = TOPICS
+        .iter()
+        .flat_map(|topic| topic.examples.iter())
+        .map(|example| example.command.len())
+        .max()
+        .unwrap_or(0);
+    let mut output = String::new();
+    for (i, topic) in TOPICS.iter().enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+        output.push_str(&format!("{}:\n", topic.title));
+        for example in topic.examples {
+            output.push_str(&format!(
+                "    {:<width$}  {}\n",
+                example.command,
+                example.description,
+                width = width
+            ));
+        }
+    }
+    output
+}
+
+/// The first example of each topic for the end of `--help`
+pub fn help_text() -> String {
+    let mut output = String::from("EXAMPLES:\n");
+    for topic in &TOPICS {
+        if let Some(example) = topic.examples.first() {
+            output.push_str(&format!(
+                "    {}\n            {}\n",
+                example.command, example.description
+            ));
+        }
+    }
+    output.push_str("\nSee `235 examples` for more.");
+    output
+}
@@ -0,0 +1,223 @@
+//! Archive of completed games as JSON Lines, one game per line, for loading
+//! into tools like DuckDB or pandas.
+//!
+//! The fields of `GameRecord` are a stable schema: fields are only ever
+//! added, and `schema` is bumped if an existing field has to change.
+
+use crate::game::{Game, Goal, Player, Special, SHOOTOUT_MINUTE};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// File formats `235 export` can write
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Newline-delimited JSON
+    #[default]
+    Jsonl,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            _ => Err(format!(
+                "unsupported export format '{}', use one of: jsonl",
+                s
+            )),
+        }
+    }
+}
+
+/// A completed game
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub schema: u32,
+    /// `Game::id`, stable across exports for joining and deduplicating
+    pub id: String,
+    /// Game day as YYYY-MM-DD
+    pub date: String,
+    /// Start time in UTC as given by the API
+    pub start_time: String,
+    pub home: String,
+    pub away: String,
+    pub home_score: u64,
+    pub away_score: u64,
+    /// "regulation", "overtime" or "shootout"
+    pub decision: String,
+    /// Overtime periods played, 0 when decided in regulation
+    pub overtime_periods: u64,
+    pub goals: Vec<GoalRecord>,
+}
+
+/// A goal of a completed game. Shootout goals aren't included.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct GoalRecord {
+    pub team: String,
+    pub scorer: String,
+    pub assists: Vec<String>,
+    /// Game minute, 1-60 in regulation and over 60 in overtime
+    pub minute: u64,
+}
+
+impl GameRecord {
+    /// How the game was decided, from `decision` and `overtime_periods`
+    pub fn special(&self) -> Special {
+        match self.decision.as_str() {
+            "overtime" => Special::Overtime {
+                periods: self.overtime_periods,
+            },
+            "shootout" => Special::Shootout,
+            _ => Special::Regulation,
+        }
+    }
+}
+
+/// First and last day of the season starting in the autumn of `season`,
+/// wide enough to cover both the regular season and the playoffs
+pub fn season_dates(season: i32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_ymd_opt(season, 9, 1)?,
+        NaiveDate::from_ymd_opt(season + 1, 6, 30)?,
+    ))
+}
+
+/// Starting year of the season `date` belongs to, the season of the
+/// spring is still the one that started the autumn before
+pub fn season_of(date: NaiveDate) -> i32 {
+    if date.month() >= 9 {
+        date.year()
+    } else {
+        date.year() - 1
+    }
+}
+
+/// Records of the completed games of a day, games that haven't ended
+/// are left out
+pub fn game_records(date: &str, games: &[Option<Game>]) -> Vec<GameRecord> {
+    games
+        .iter()
+        .flatten()
+        .filter(|game| game.status == "FINAL")
+        .map(|game| game_record(date, game))
+        .collect()
+}
+
+fn game_record(date: &str, game: &Game) -> GameRecord {
+    let (decision, overtime_periods) = match game.special {
+        Special::Regulation => ("regulation", 0),
+        Special::Overtime { periods } => ("overtime", periods),
+        Special::Shootout => ("shootout", 1),
+    };
+    GameRecord {
+        schema: SCHEMA_VERSION,
+        id: game.id.to_string(),
+        date: date.to_string(),
+        start_time: game.start_time.to_string(),
+        home: game.home.to_string(),
+        away: game.away.to_string(),
+        home_score: game.home_score,
+        away_score: game.away_score,
+        decision: decision.to_string(),
+        overtime_periods,
+        goals: game
+            .goals
+            .iter()
+            .filter(|goal| !(game.special == Special::Shootout && goal.minute == SHOOTOUT_MINUTE))
+            .map(goal_record)
+            .collect(),
+    }
+}
+
+fn goal_record(goal: &Goal) -> GoalRecord {
+    GoalRecord {
+        team: goal.team.to_string(),
+        scorer: full_name(&goal.scorer),
+        assists: goal.assists.iter().map(full_name).collect(),
+        minute: goal.minute,
+    }
+}
+
+fn full_name(player: &Player) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+}
+
+/// Writes the records as JSON Lines
+pub fn to_jsonl(records: &[GameRecord]) -> Result<String, serde_json::Error> {
+    let mut output = String::new();
+    for record in records {
+        output.push_str(&serde_json::to_string(record)?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_api_response;
+
+    #[test]
+    fn it_exports_completed_games_as_json_lines() -> serde_json::Result<()> {
+        let games = parse_api_response(
+            r#"{"date":null,"errors":null,"games":[{
+                "status":{"state":"FINAL"},
+                "startTime":"2023-10-10T23:00:00Z",
+                "goals":[
+                    {"team":"PIT","period":"1","scorer":{"player":"Sidney Crosby"},"assists":[{"player":"Kris Letang","seasonTotal":1}],"min":4,"sec":10},
+                    {"team":"CHI","period":"OT","scorer":{"player":"Connor Bedard"},"min":2,"sec":1}
+                ],
+                "scores":{"PIT":1,"CHI":2,"overtime":true},
+                "teams":{
+                    "away":{"abbreviation":"CHI","id":16,"locationName":"Chicago","shortName":"Chicago","teamName":"Blackhawks"},
+                    "home":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            },{
+                "status":{"state":"PREVIEW"},
+                "startTime":"2023-10-11T23:00:00Z",
+                "scores":{"TOR":0,"MTL":0},
+                "teams":{
+                    "away":{"abbreviation":"MTL","id":8,"locationName":"Montréal","shortName":"Montréal","teamName":"Canadiens"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }]}"#,
+        )?;
+
+        let records = game_records("2023-10-10", &games);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].decision, "overtime");
+        assert_eq!(records[0].overtime_periods, 1);
+
+        let jsonl = to_jsonl(&records)?;
+        assert_eq!(jsonl.lines().count(), 1);
+        let line: serde_json::Value = serde_json::from_str(jsonl.trim_end())?;
+        assert_eq!(line["schema"], SCHEMA_VERSION);
+        assert_eq!(line["id"], "2023-10-10T23:00:00Z-CHI-PIT");
+        assert_eq!(line["home"], "PIT");
+        assert_eq!(line["goals"][0]["scorer"], "Sidney Crosby");
+        assert_eq!(line["goals"][0]["assists"][0], "Kris Letang");
+        assert_eq!(line["goals"][1]["minute"], 62);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_knows_the_season_dates() {
+        let (start, end) = season_dates(2023).unwrap();
+        assert_eq!(start.to_string(), "2023-09-01");
+        assert_eq!(end.to_string(), "2024-06-30");
+        let date = |text: &str| NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap();
+        assert_eq!(season_of(date("2024-03-09")), 2023);
+        assert_eq!(season_of(date("2024-10-16")), 2024);
+        assert_eq!("JSONL".parse(), Ok(ExportFormat::Jsonl));
+        assert!("csv".parse::<ExportFormat>().is_err());
+    }
+}
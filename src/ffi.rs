@@ -0,0 +1,72 @@
+use crate::game::parse_api_response;
+use crate::options::RenderOptions;
+use crate::render::render_games;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+/// Renders nhl-score-api JSON (a response of `/api/scores/latest`) into the
+/// plain text 235 page.
+///
+/// Returns NULL if the input is NULL, not UTF-8 or not a valid API response.
+/// The returned string is owned by the caller and must be released with
+/// `nhl235_free_string`.
+///
+/// # Safety
+///
+/// `json` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn nhl235_render_json(json: *const c_char) -> *mut c_char {
+    if json.is_null() {
+        return ptr::null_mut();
+    }
+    let json = match CStr::from_ptr(json).to_str() {
+        Ok(json) => json,
+        Err(_) => return ptr::null_mut(),
+    };
+    let games = match parse_api_response(json) {
+        Ok(games) => games,
+        Err(_) => return ptr::null_mut(),
+    };
+
+    let page = render_games(&games, &RenderOptions::default().colors(false));
+    match CString::new(page) {
+        Ok(page) => page.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Releases a string returned by `nhl235_render_json`.
+///
+/// # Safety
+///
+/// `s` must be NULL or a pointer returned by `nhl235_render_json` that
+/// hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nhl235_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_json_through_the_c_abi() {
+        let json = CString::new(r#"{"date":null,"games":[],"errors":null}"#).unwrap();
+        unsafe {
+            let page = nhl235_render_json(json.as_ptr());
+            assert!(!page.is_null());
+            assert_eq!(CStr::from_ptr(page).to_str().unwrap(), "No games today.\n");
+            nhl235_free_string(page);
+        }
+
+        let broken = CString::new("{").unwrap();
+        unsafe {
+            assert!(nhl235_render_json(broken.as_ptr()).is_null());
+            assert!(nhl235_render_json(ptr::null()).is_null());
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Dates and times written the way the selected language writes them.
+//! Every renderer formats through these so that a language only has to
+//! be taught once.
+
+use crate::i18n::Lang;
+use chrono::{NaiveDate, NaiveTime};
+
+/// Day and month, "Mar 23" in English, "23.3." in Finnish and "23/3" in Swedish
+pub fn format_date(date: NaiveDate, lang: Lang) -> String {
+    match lang {
+        Lang::En => date.format("%b %-d").to_string(),
+        Lang::Fi => date.format("%-d.%-m.").to_string(),
+        Lang::Sv => date.format("%-d/%-m").to_string(),
+    }
+}
+
+/// Time of day on the 24-hour clock, "19:30" in English and "19.30" in
+/// Finnish and Swedish
+pub fn format_time(time: NaiveTime, lang: Lang) -> String {
+    match lang {
+        Lang::En => time.format("%H:%M").to_string(),
+        Lang::Fi | Lang::Sv => time.format("%H.%M").to_string(),
+    }
+}
+
+/// Formats a YYYY-MM-DD date for `lang`. Anything else is returned as is.
+pub fn format_iso_date(date: &str, lang: Lang) -> String {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => format_date(date, lang),
+        Err(_) => date.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_formats_dates_and_times_by_language() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 23).unwrap();
+        assert_eq!(format_date(date, Lang::En), "Mar 23");
+        assert_eq!(format_date(date, Lang::Fi), "23.3.");
+        assert_eq!(format_date(date, Lang::Sv), "23/3");
+
+        let time = NaiveTime::from_hms_opt(19, 5, 0).unwrap();
+        assert_eq!(format_time(time, Lang::En), "19:05");
+        assert_eq!(format_time(time, Lang::Fi), "19.05");
+
+        assert_eq!(format_iso_date("2024-03-09", Lang::Fi), "9.3.");
+        assert_eq!(format_iso_date("", Lang::En), "");
+    }
+}
@@ -0,0 +1,1052 @@
+use crate::api_types::{
+    APIResponse, BroadcastResponse, GameResponse, GoalResponse, PowerPlayResponse,
+    ProgressResponse, TeamResponse, VenueResponse,
+};
+use chrono::{DateTime, Duration, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+pub const SHOOTOUT_MINUTE: u64 = 65;
+const INTERMISSION_MINUTES: u64 = 18;
+const SHOOTOUT_DURATION_MINUTES: u64 = 10;
+
+#[derive(Clone, Serialize)]
+pub struct Goal {
+    pub scorer: Player,
+    pub assists: Vec<Player>,
+    pub minute: u64,
+    pub special: bool,
+    pub team: String,
+    /// Period as given by the API: "1" to "3", "OT" or "4" and up in
+    /// overtime, and "SO" for the shootout
+    pub period: String,
+    /// "EVEN", "PPG" for power play or "SHG" for shorthanded, when known
+    pub strength: Option<String>,
+}
+
+#[derive(Clone, Eq, Hash, PartialEq, Serialize)]
+pub struct Player {
+    pub first_name: String,
+    pub last_name: String,
+    pub team: String,
+}
+
+#[derive(Serialize)]
+pub struct Game {
+    /// Stable identifier of the game, the same on every fetch and in
+    /// every output, see `game_id`
+    pub id: String,
+    pub home: String,
+    pub away: String,
+    /// City and nickname of the home team, like "Pittsburgh Penguins"
+    pub home_name: String,
+    pub away_name: String,
+    pub score: String,
+    pub home_score: u64,
+    pub away_score: u64,
+    pub goals: Vec<Goal>,
+    pub status: String,
+    pub special: Special,
+    pub playoff_series: Option<HashMap<String, serde_json::Value>>,
+    pub start_time: String,
+    pub length: Option<GameLength>,
+    pub venue: Option<Venue>,
+    pub broadcasts: Vec<String>,
+    pub progress: Option<Progress>,
+    /// League ranks of the home and away teams going into the game
+    pub ranks: Option<(u64, u64)>,
+    /// Records of the home and away teams, with this game once it's over
+    pub records: Option<(Record, Record)>,
+}
+
+/// How the game was decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Special {
+    #[default]
+    Regulation,
+    /// Number of overtime periods played, more than one only happens in playoffs
+    Overtime {
+        periods: u64,
+    },
+    Shootout,
+}
+
+/// Short marker printed before the final score: "", "ot", "2ot" or "so"
+impl fmt::Display for Special {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Special::Regulation => Ok(()),
+            Special::Overtime { periods: 1 } => write!(f, "ot"),
+            Special::Overtime { periods } => write!(f, "{}ot", periods),
+            Special::Shootout => write!(f, "so"),
+        }
+    }
+}
+
+/// Where a live game is going
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Progress {
+    pub period: u64,
+    /// Period as the API names it: "1st", "2nd", "3rd", "OT", "SO", ...
+    pub ordinal: String,
+    /// Time left in the period in seconds
+    pub seconds_remaining: u64,
+    /// Time left in the period as shown by the API, "12:21" or "END"
+    pub time_remaining: String,
+    /// The period has ended and the next one hasn't started yet
+    pub intermission: bool,
+    pub shootout: bool,
+    pub power_play: Option<PowerPlay>,
+}
+
+/// Team on a power play and how long the man advantage still lasts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PowerPlay {
+    pub team: String,
+    pub seconds_remaining: u64,
+    /// Time left as shown by the API, "1:23"
+    pub time_remaining: String,
+}
+
+/// Wins, losses and overtime losses of a team
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub wins: u64,
+    pub losses: u64,
+    #[serde(default)]
+    pub ot: u64,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.losses, self.ot)
+    }
+}
+
+#[derive(Serialize)]
+pub struct Venue {
+    pub name: String,
+    pub city: Option<String>,
+    pub neutral_site: bool,
+    pub special_event: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GameLength {
+    pub seconds: u64,
+    pub intermissions: u64,
+    pub shootout: bool,
+}
+
+/// Parses a raw nhl-score-api response into games. Malformed JSON is an
+/// error, games that are missing data needed for the page are `None`.
+pub fn parse_api_response(json: &str) -> Result<Vec<Option<Game>>, serde_json::Error> {
+    let scores: APIResponse = serde_json::from_str(json)?;
+    Ok(parse_games(scores))
+}
+
+/// Transforms a JSON structure of multiple games into
+/// a vector of Option<Game> so they can be processed by
+/// other parts of the application
+pub fn parse_games(scores: APIResponse) -> Vec<Option<Game>> {
+    let games = scores.games;
+
+    games.iter().map(parse_game).collect::<Vec<Option<Game>>>()
+}
+
+/// The API doesn't return game ids, but no team plays twice at the same
+/// start time so "2024-10-16T23:00:00Z-TOR-PIT" (away team first) is unique
+pub fn game_id(start_time: &str, away: &str, home: &str) -> String {
+    format!("{}-{}-{}", start_time, away, home)
+}
+
+/// Transforms a combination of min (between 0 and 19) and
+/// period ("OT", "SO" or number > 0 in number form)
+/// into a numeric minute given 20 minute periods.
+/// Unknown periods are treated as overtime like in `parse_game`.
+fn format_minute(min: u64, period: &str) -> u64 {
+    match period.parse::<u64>() {
+        Ok(period) => period
+            .saturating_sub(1)
+            .saturating_mul(20)
+            .saturating_add(min),
+        Err(_) => min.saturating_add(60),
+    }
+}
+
+/// Returns true if the goal scored was done in
+/// overtime or in a shootout
+fn is_special(goal: &GoalResponse) -> bool {
+    match goal.period.parse::<u64>() {
+        Ok(period) => period >= 4,
+        Err(_) => true,
+    }
+}
+
+/// Transforms a JSON structure of an individual game into a Game
+pub fn parse_game(game_json: &GameResponse) -> Option<Game> {
+    let home_team = &game_json.teams.home.abbreviation;
+    let away_team = &game_json.teams.away.abbreviation;
+
+    let home_score = game_json.scores.get(home_team)?;
+    let away_score = game_json.scores.get(away_team)?;
+
+    let empty_vec: &Vec<GoalResponse> = &Vec::<GoalResponse>::new();
+
+    let all_goals = match &game_json.goals {
+        Some(goals) => goals,
+        None => empty_vec,
+    };
+
+    let special = match all_goals.last() {
+        None => Special::Regulation,
+        Some(last_goal) => {
+            let period = &last_goal.period;
+            match period.as_str() {
+                "OT" => Special::Overtime { periods: 1 },
+                "SO" => Special::Shootout,
+                _ => match period.parse::<u64>() {
+                    Ok(period) if period <= 3 => Special::Regulation,
+                    // Playoff overtimes are numbered as periods 4, 5, ...
+                    Ok(period) => Special::Overtime {
+                        periods: period - 3,
+                    },
+                    // The only periods with chars should be OT and SO.
+                    // If other periods occur, new arms should be added
+                    Err(_) => Special::Overtime { periods: 1 },
+                },
+            }
+        }
+    };
+
+    let goals: &Vec<GoalResponse> = all_goals;
+
+    let goals = goals
+        .iter()
+        .map(|goal| {
+            let minute = match goal.period.as_str() {
+                "SO" => SHOOTOUT_MINUTE,
+                _ => format_minute(goal.min.unwrap_or(0), &goal.period),
+            };
+
+            let scorer = extract_player(&goal.scorer.player, &goal.team);
+            let assists = &goal
+                .assists
+                .as_ref()
+                .unwrap_or(&Vec::new())
+                .iter()
+                .map(|assist| extract_player(&assist.player, &goal.team))
+                .collect::<Vec<Player>>();
+
+            Goal {
+                scorer,
+                assists: assists.to_vec(),
+                minute,
+                team: goal.team.replace("\"", ""),
+                special: is_special(goal),
+                period: goal.period.to_string(),
+                strength: goal.strength.clone(),
+            }
+        })
+        .collect::<Vec<Goal>>();
+
+    let length = match game_json.status.state.as_str() {
+        "FINAL" => Some(game_length(all_goals, special)),
+        _ => None,
+    };
+
+    let score = format!("{}-{}", home_score, away_score);
+    let game = Game {
+        id: game_id(&game_json.start_time, away_team, home_team),
+        home: String::from(home_team),
+        away: String::from(away_team),
+        home_name: full_team_name(&game_json.teams.home),
+        away_name: full_team_name(&game_json.teams.away),
+        score: score.to_owned(),
+        home_score: home_score.as_u64().unwrap_or(0),
+        away_score: away_score.as_u64().unwrap_or(0),
+        goals,
+        status: String::from(&game_json.status.state),
+        special,
+        playoff_series: game_json.current_stats.playoff_series.clone(),
+        start_time: String::from(&game_json.start_time),
+        length,
+        venue: game_json.venue.as_ref().map(parse_venue),
+        broadcasts: national_broadcasts(game_json.broadcasts.as_deref().unwrap_or_default()),
+        progress: game_json.status.progress.as_ref().map(parse_progress),
+        ranks: parse_ranks(game_json, home_team, away_team),
+        records: parse_records(game_json, home_team, away_team),
+    };
+
+    Some(game)
+}
+
+fn full_team_name(team: &TeamResponse) -> String {
+    format!("{} {}", team.location_name, team.team_name)
+}
+
+fn parse_progress(progress: &ProgressResponse) -> Progress {
+    let remaining = &progress.current_period_time_remaining;
+    let shootout = progress.current_period_ordinal == "SO";
+    Progress {
+        period: progress.current_period,
+        ordinal: progress.current_period_ordinal.to_string(),
+        seconds_remaining: remaining
+            .min
+            .saturating_mul(60)
+            .saturating_add(remaining.sec),
+        time_remaining: remaining.pretty.to_string(),
+        // The API shows "END" between periods. Progress is only sent for
+        // games that are going on so a period with no time left hasn't
+        // been followed by the next one yet.
+        intermission: !shootout
+            && (remaining.pretty == "END" || (remaining.min == 0 && remaining.sec == 0)),
+        shootout,
+        power_play: progress.power_play.as_ref().map(parse_power_play),
+    }
+}
+
+fn parse_power_play(power_play: &PowerPlayResponse) -> PowerPlay {
+    let remaining = &power_play.time_remaining;
+    PowerPlay {
+        team: power_play.team.to_string(),
+        seconds_remaining: remaining
+            .min
+            .saturating_mul(60)
+            .saturating_add(remaining.sec),
+        time_remaining: remaining.pretty.to_string(),
+    }
+}
+
+/// Power play of a live game, None when teams are at even strength or
+/// the API doesn't tell
+pub fn power_play(game: &Game) -> Option<&PowerPlay> {
+    if game.status != "LIVE" {
+        return None;
+    }
+    game.progress.as_ref()?.power_play.as_ref()
+}
+
+/// Running (home, away) score of a shootout that is still going on.
+/// None for games that aren't live in a shootout.
+pub fn live_shootout_score(game: &Game) -> Option<(usize, usize)> {
+    let in_shootout =
+        game.special == Special::Shootout || game.progress.as_ref().is_some_and(|p| p.shootout);
+    if game.status != "LIVE" || !in_shootout {
+        return None;
+    }
+
+    let shootout_goals = game
+        .goals
+        .iter()
+        .filter(|goal| goal.minute == SHOOTOUT_MINUTE);
+    let (home, away): (Vec<&Goal>, Vec<&Goal>) =
+        shootout_goals.partition(|goal| goal.team == game.home);
+    Some((home.len(), away.len()))
+}
+
+/// Returns true for live games worth switching to: within one goal
+/// in the third period or tied in overtime
+pub fn is_close_game(game: &Game) -> bool {
+    if game.status != "LIVE" {
+        return false;
+    }
+    match &game.progress {
+        Some(progress) if progress.period == 3 => game.home_score.abs_diff(game.away_score) <= 1,
+        Some(progress) if progress.period > 3 => game.home_score == game.away_score,
+        _ => false,
+    }
+}
+
+/// A team ranked this many places higher losing is an upset
+pub const UPSET_RANK_GAP: u64 = 10;
+/// Winning by this many goals is a blowout
+pub const BLOWOUT_MARGIN: u64 = 5;
+
+/// Results worth reading the recap of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Notable {
+    /// The winner was ranked at least `UPSET_RANK_GAP` places lower
+    Upset,
+    /// Won by at least `BLOWOUT_MARGIN` goals
+    Blowout,
+}
+
+/// Notable results of a finished game, empty for games still to be decided
+pub fn notable_results(game: &Game) -> Vec<Notable> {
+    let mut notable = Vec::new();
+    if game.status != "FINAL" {
+        return notable;
+    }
+    if let Some((home_rank, away_rank)) = game.ranks {
+        let (winner, loser) = if game.home_score > game.away_score {
+            (home_rank, away_rank)
+        } else {
+            (away_rank, home_rank)
+        };
+        if winner >= loser + UPSET_RANK_GAP {
+            notable.push(Notable::Upset);
+        }
+    }
+    if game.home_score.abs_diff(game.away_score) >= BLOWOUT_MARGIN {
+        notable.push(Notable::Blowout);
+    }
+    notable
+}
+
+/// Ranks from the standings before the game, or the current ones if the
+/// API only has those. Ranks come as strings like "12".
+fn parse_ranks(game_json: &GameResponse, home: &str, away: &str) -> Option<(u64, u64)> {
+    let standings = game_json
+        .pre_game_stats
+        .standings
+        .as_ref()
+        .unwrap_or(&game_json.current_stats.standings);
+    let rank = |team: &str| {
+        let rank = standings.get(team)?.get("leagueRank")?;
+        rank.as_u64()
+            .or_else(|| rank.as_str().and_then(|rank| rank.parse().ok()))
+    };
+    Some((rank(home)?, rank(away)?))
+}
+
+fn parse_records(game_json: &GameResponse, home: &str, away: &str) -> Option<(Record, Record)> {
+    let records = &game_json.current_stats.records;
+    let record = |team: &str| serde_json::from_value(records.get(team)?.clone()).ok();
+    Some((record(home)?, record(away)?))
+}
+
+fn parse_venue(venue: &VenueResponse) -> Venue {
+    Venue {
+        name: String::from(&venue.name),
+        city: venue.city.clone(),
+        neutral_site: venue.neutral_site.unwrap_or(false),
+        special_event: venue.special_event.clone(),
+    }
+}
+
+/// Picks the networks showing the game nationally. Regional broadcasts
+/// are left out since they are only available in the teams' home markets
+fn national_broadcasts(broadcasts: &[BroadcastResponse]) -> Vec<String> {
+    broadcasts
+        .iter()
+        .filter(|broadcast| match &broadcast.market {
+            Some(market) => market.eq_ignore_ascii_case("national"),
+            None => true,
+        })
+        .map(|broadcast| String::from(&broadcast.network))
+        .unique()
+        .collect()
+}
+
+/// Calculates how long the game clock ran in a finished game.
+/// Regulation games last 60 minutes and shootouts are always preceded
+/// by a full 5 minute overtime. Overtime games end with the winning goal.
+fn game_length(goals: &[GoalResponse], special: Special) -> GameLength {
+    let regulation = GameLength {
+        seconds: 60 * 60,
+        intermissions: 2,
+        shootout: false,
+    };
+
+    match (special, goals.last()) {
+        (Special::Shootout, _) => GameLength {
+            seconds: SHOOTOUT_MINUTE * 60,
+            intermissions: 2,
+            shootout: true,
+        },
+        (Special::Overtime { .. }, Some(winner)) => {
+            let min = winner.min.unwrap_or(0);
+            let sec = winner.sec.unwrap_or(0);
+            // Regular season overtime ("OT") starts without an intermission,
+            // playoff overtimes are numbered periods with a full intermission before each
+            let (minute, intermissions) = match winner.period.parse::<u64>() {
+                Ok(period) => (
+                    period
+                        .saturating_sub(1)
+                        .saturating_mul(20)
+                        .saturating_add(min),
+                    period.saturating_sub(1),
+                ),
+                Err(_) => (min.saturating_add(60), 2),
+            };
+            GameLength {
+                seconds: minute.saturating_mul(60).saturating_add(sec),
+                intermissions,
+                shootout: false,
+            }
+        }
+        _ => regulation,
+    }
+}
+
+/// Estimates the wall-clock time a game ended.
+/// Stoppages roughly double the time on the game clock, on top of which
+/// come the intermissions and a possible shootout.
+pub fn estimate_end_time(start_time: &str, length: &GameLength) -> Option<DateTime<Utc>> {
+    let start = DateTime::parse_from_rfc3339(start_time).ok()?;
+    let mut minutes = (length.seconds / 30)
+        .saturating_add(length.intermissions.saturating_mul(INTERMISSION_MINUTES));
+    if length.shootout {
+        minutes = minutes.saturating_add(SHOOTOUT_DURATION_MINUTES);
+    }
+
+    let duration = Duration::try_minutes(i64::try_from(minutes).ok()?)?;
+    Some(start.checked_add_signed(duration)?.with_timezone(&Utc))
+}
+
+/// Estimates the wall-clock time of a goal the same way as the end of a
+/// game, from the game clock and the intermissions before the goal
+pub fn estimate_goal_time(game: &Game, goal: &Goal) -> Option<DateTime<Utc>> {
+    let intermissions = match goal.period.as_str() {
+        "OT" | "SO" => 2,
+        period => period.parse::<u64>().ok()?.saturating_sub(1),
+    };
+    let length = GameLength {
+        seconds: goal.minute.saturating_mul(60),
+        intermissions,
+        shootout: goal.period == "SO",
+    };
+    estimate_end_time(&game.start_time, &length)
+}
+
+fn extract_player(name: &str, team: &str) -> Player {
+    let name = name.split(" ").collect::<Vec<&str>>();
+    let first_name = name[0];
+    let last_name = name[1..name.len()].to_vec().join(" ");
+    Player {
+        first_name: String::from(first_name),
+        last_name,
+        team: String::from(team),
+    }
+}
+
+/// A game as the API sends it, for tests that need games of their own:
+/// the `status` object, the start time, the home and away teams with
+/// their scores, and the `goals` array items
+#[cfg(test)]
+pub(crate) fn test_game_json(
+    status: &str,
+    start_time: &str,
+    (home, home_score): (&str, u64),
+    (away, away_score): (&str, u64),
+    goals: &str,
+) -> String {
+    let team = |abbreviation: &str| {
+        let known = crate::teams::TEAMS
+            .iter()
+            .find(|team| team.abbreviation == abbreviation);
+        format!(
+            r#"{{"abbreviation":"{}","id":0,"locationName":"{}","shortName":"{}","teamName":"{}"}}"#,
+            abbreviation,
+            known.map_or("", |team| team.name),
+            known.map_or("", |team| team.name),
+            known.map_or("", |team| team.nickname)
+        )
+    };
+    format!(
+        r#"{{
+            "status":{},
+            "startTime":"{}",
+            "goals":[{}],
+            "scores":{{"{}":{},"{}":{}}},
+            "teams":{{"away":{},"home":{}}},
+            "preGameStats":{{"records":{{}}}},
+            "currentStats":{{"records":{{}},"standings":{{}}}}
+        }}"#,
+        status,
+        start_time,
+        goals,
+        away,
+        away_score,
+        home,
+        home_score,
+        team(away),
+        team(home)
+    )
+}
+
+/// The games made with `test_game_json` as one API response
+#[cfg(test)]
+pub(crate) fn test_games(games: &[String]) -> Vec<Option<Game>> {
+    parse_api_response(&format!(
+        r#"{{"date":null,"errors":null,"games":[{}]}}"#,
+        games.join(",")
+    ))
+    .expect("test games are valid JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn minutes_are_converted_correctly() {
+        assert_eq!(format_minute(3, "1"), 3);
+        assert_eq!(format_minute(13, "2"), 33);
+        assert_eq!(format_minute(5, "3"), 45);
+        assert_eq!(format_minute(12, "4"), 72);
+        assert_eq!(format_minute(5, "5"), 85);
+        assert_eq!(format_minute(5, "6"), 105);
+        assert_eq!(format_minute(4, "OT"), 64);
+        assert_eq!(format_minute(0, "1"), 0);
+        assert_eq!(format_minute(0, "2"), 20);
+        assert_eq!(format_minute(0, "3"), 40);
+        assert_eq!(format_minute(0, "OT"), 60);
+    }
+
+    #[test]
+    fn is_special_works() -> serde_json::Result<()> {
+        let first =
+            r#"{ "team": "CHI", "period": "1", "scorer": { "player": "_", "seasonTotal": 10} }"#;
+        let second =
+            r#"{ "team": "CHI", "period": "2", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+        let third =
+            r#"{ "team": "CHI", "period": "3", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+        let overtime =
+            r#"{ "team": "CHI", "period": "OT", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+        let shootout =
+            r#"{ "team": "CHI", "period": "SO", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+        let playoff_ot =
+            r#"{ "team": "CHI", "period": "4", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+        let playoff_ot_2 =
+            r#"{ "team": "CHI", "period": "10", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+        let wrong_data =
+            r#"{ "team": "CHI", "period": "SP", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
+
+        let goal1: GoalResponse = serde_json::from_str(first)?;
+        let goal2: GoalResponse = serde_json::from_str(second)?;
+        let goal3: GoalResponse = serde_json::from_str(third)?;
+        let goal4: GoalResponse = serde_json::from_str(overtime)?;
+        let goal5: GoalResponse = serde_json::from_str(shootout)?;
+        let goal6: GoalResponse = serde_json::from_str(playoff_ot)?;
+        let goal7: GoalResponse = serde_json::from_str(playoff_ot_2)?;
+        let goal8: GoalResponse = serde_json::from_str(wrong_data)?;
+
+        assert!(!is_special(&goal1));
+        assert!(!is_special(&goal2));
+        assert!(!is_special(&goal3));
+        assert!(is_special(&goal4));
+        assert!(is_special(&goal5));
+        assert!(is_special(&goal6));
+        assert!(is_special(&goal7));
+        // I haven't yet really decided what this should be but
+        // important thing is that it does not crash the app
+        assert!(is_special(&goal8));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_full_live_game_data_correctly() -> serde_json::Result<()> {
+        let test_game: GameResponse = serde_json::from_str(
+            r#"{"status":{"state":"LIVE","progress":{"currentPeriod":3,"currentPeriodOrdinal":"3rd","currentPeriodTimeRemaining":{"min":12,"sec":21,"pretty":"12:21"}}},"startTime":"2021-01-23T19:00:00Z","goals":[{"team":"TBL","period":"1","scorer":{"player":"Victor Hedman","seasonTotal":1},"assists":[{"player":"Mitchell Stephens","seasonTotal":1},{"player":"Alexander Volkov","seasonTotal":1}],"min":4,"sec":10},{"team":"CBJ","period":"1","scorer":{"player":"Nick Foligno","seasonTotal":3},"assists":[{"player":"Cam Atkinson","seasonTotal":2},{"player":"Michael Del Zotto","seasonTotal":4}],"min":4,"sec":27},{"team":"CBJ","period":"1","scorer":{"player":"Mikhail Grigorenko","seasonTotal":1},"assists":[{"player":"Kevin Stenlund","seasonTotal":1},{"player":"Nathan Gerbe","seasonTotal":1}],"min":10,"sec":3},{"team":"CBJ","period":"1","scorer":{"player":"Vladislav Gavrikov","seasonTotal":1},"assists":[{"player":"Liam Foudy","seasonTotal":2},{"player":"Eric Robinson","seasonTotal":1}],"min":19,"sec":1},{"team":"TBL","period":"1","scorer":{"player":"Ondrej Palat","seasonTotal":3},"assists":[{"player":"Brayden Point","seasonTotal":3},{"player":"Victor Hedman","seasonTotal":4}],"min":19,"sec":46,"strength":"PPG"},{"team":"CBJ","period":"3","scorer":{"player":"Zach Werenski","seasonTotal":1},"assists":[{"player":"Alexandre Texier","seasonTotal":2},{"player":"Boone Jenner","seasonTotal":2}],"min":6,"sec":34}],"scores":{"TBL":2,"CBJ":4},"teams":{"away":{"abbreviation":"TBL","id":14,"locationName":"Tampa Bay","shortName":"Tampa Bay","teamName":"Lightning"},"home":{"abbreviation":"CBJ","id":29,"locationName":"Columbus","shortName":"Columbus","teamName":"Blue Jackets"}},"preGameStats":{"records":{"TBL":{"wins":3,"losses":0,"ot":0},"CBJ":{"wins":1,"losses":2,"ot":2}}},"currentStats":{"records":{"TBL":{"wins":3,"losses":0,"ot":0},"CBJ":{"wins":1,"losses":2,"ot":2}},"streaks":{"TBL":{"type":"WINS","count":3},"CBJ":{"type":"OT","count":2}},"standings":{"TBL":{"divisionRank":"1","leagueRank":"1"},"CBJ":{"divisionRank":"7","leagueRank":"24"}}}}"#,
+        )?;
+
+        let parsed_game = parse_game(&test_game).unwrap();
+
+        assert_eq!(parsed_game.home, "CBJ");
+        assert_eq!(parsed_game.away, "TBL");
+        assert_eq!(parsed_game.score, "4-2");
+        assert_eq!(parsed_game.goals.len(), 6);
+        assert_eq!(parsed_game.status, "LIVE");
+        assert_eq!(parsed_game.special, Special::Regulation);
+
+        let progress = parsed_game.progress.as_ref().unwrap();
+        assert_eq!(progress.ordinal, "3rd");
+        assert_eq!(progress.seconds_remaining, 12 * 60 + 21);
+        assert_eq!(progress.time_remaining, "12:21");
+        assert!(!progress.intermission);
+
+        let (home_record, away_record) = parsed_game.records.unwrap();
+        assert_eq!(home_record.to_string(), "1-2-2");
+        assert_eq!(away_record.wins, 3);
+
+        let json = serde_json::to_value(&parsed_game)?;
+        assert_eq!(json["progress"]["period"], 3);
+        assert_eq!(json["progress"]["time_remaining"], "12:21");
+        assert_eq!(json["special"], "regulation");
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_full_overtime_game_data_correctly() -> serde_json::Result<()> {
+        let test_game = serde_json::from_str(
+            r#"
+            {
+                "status":{
+                    "state":"FINAL"
+                },
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[
+                    {
+                        "team":"TOR",
+                        "period":"1",
+                        "scorer":{
+                            "player":"Mitch Marner",
+                            "seasonTotal":1
+                        },
+                        "assists":[
+                            {
+                                "player":"Mitchell Stephens",
+                                "seasonTotal":1
+                            },
+                            {
+                                "player":"Alexander Volkov",
+                                "seasonTotal":1
+                            }
+                        ],
+                        "min":4,
+                        "sec":10
+                    },
+                    {
+                        "team":"PIT",
+                        "period":"3",
+                        "scorer":{
+                            "player":"Sidney Crosby",
+                            "seasonTotal":3
+                        },
+                        "assists":[
+                            {
+                                "player":"Evgeni Malkin",
+                                "seasonTotal":2
+                            }
+                        ],
+                        "min":4,
+                        "sec":27
+                    },
+                    {
+                        "team":"PIT",
+                        "period":"OT",
+                        "scorer":{
+                            "player":"Sidney Crosby",
+                            "seasonTotal":4
+                        },
+                        "assists":[],
+                        "min":3,
+                        "sec":0
+                    }],
+                    "scores":{
+                        "PIT":2,"TOR":1
+                    },
+                    "teams":{
+                        "away":{
+                            "abbreviation":"PIT",
+                            "id":14,
+                            "locationName":"Pittsburgh",
+                            "shortName":"Pittsburgh",
+                            "teamName":"Penguins"
+                        },
+                        "home":{
+                            "abbreviation":"TOR",
+                            "id":29,
+                            "locationName":"Toronto",
+                            "shortName":"Toronto",
+                            "teamName":"Maple Leafs"
+                        }
+                    },
+                    "preGameStats":{"records":{"PIT":{"wins":3,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":2}}},
+                    "currentStats":{"records":{"PIT":{"wins":4,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":3}},
+                    "streaks":{"PIT":{"type":"WINS","count":3},"TOR":{"type":"OT","count":2}},
+                    "standings":{
+                        "PIT":{"divisionRank":"1","leagueRank":"1"},
+                        "CBJ":{"divisionRank":"7","leagueRank":"24"}
+                    }
+                }
+            }"#,
+        )?;
+
+        let parsed_game = parse_game(&test_game).unwrap();
+
+        assert_eq!(parsed_game.home, "TOR");
+        assert_eq!(parsed_game.away, "PIT");
+        assert_eq!(parsed_game.score, "1-2");
+        assert_eq!(parsed_game.goals.len(), 3);
+        assert_eq!(parsed_game.status, "FINAL");
+        assert_eq!(parsed_game.special, Special::Overtime { periods: 1 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_calculates_game_length_correctly() -> serde_json::Result<()> {
+        let regular_ot: GoalResponse = serde_json::from_str(
+            r#"{ "team": "PIT", "period": "OT", "scorer": { "player": "_" }, "min": 3, "sec": 12 }"#,
+        )?;
+        let double_ot: GoalResponse = serde_json::from_str(
+            r#"{ "team": "PIT", "period": "5", "scorer": { "player": "_" }, "min": 8, "sec": 3 }"#,
+        )?;
+
+        assert_eq!(game_length(&[], Special::Regulation).seconds, 60 * 60);
+        assert_eq!(
+            game_length(&[regular_ot], Special::Overtime { periods: 1 }),
+            GameLength {
+                seconds: 63 * 60 + 12,
+                intermissions: 2,
+                shootout: false
+            }
+        );
+        assert_eq!(
+            game_length(&[double_ot], Special::Overtime { periods: 2 }),
+            GameLength {
+                seconds: 88 * 60 + 3,
+                intermissions: 4,
+                shootout: false
+            }
+        );
+        assert!(game_length(&[], Special::Shootout).shootout);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_close_live_games() -> serde_json::Result<()> {
+        let test_game: GameResponse = serde_json::from_str(
+            r#"{
+                "status":{"state":"LIVE","progress":{"currentPeriod":3,"currentPeriodOrdinal":"3rd","currentPeriodTimeRemaining":{"min":4,"sec":10,"pretty":"04:10"}}},
+                "startTime":"2021-01-23T19:00:00Z",
+                "scores":{"PIT":2,"TOR":3},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }"#,
+        )?;
+
+        let mut game = parse_game(&test_game).unwrap();
+        assert!(is_close_game(&game));
+
+        game.home_score = 4;
+        assert!(!is_close_game(&game));
+
+        game.progress.as_mut().unwrap().period = 4;
+        assert!(!is_close_game(&game));
+
+        game.home_score = 2;
+        assert!(is_close_game(&game));
+
+        game.status = String::from("FINAL");
+        assert!(!is_close_game(&game));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_a_game_with_no_goals_correctly() -> serde_json::Result<()> {
+        let test_game = serde_json::from_str(
+            r#"
+            {
+                "status":{
+                    "state":"LIVE"
+                },
+                "startTime":"2021-01-23T19:00:00Z",
+                "scores":{
+                    "PIT":0,"TOR":0
+                },
+                "teams":{
+                    "away":{
+                        "abbreviation":"PIT",
+                        "id":14,
+                        "locationName":"Pittsburgh",
+                        "shortName":"Pittsburgh",
+                        "teamName":"Penguins"
+                    },
+                    "home":{
+                        "abbreviation":"TOR",
+                        "id":29,
+                        "locationName":"Toronto",
+                        "shortName":"Toronto",
+                        "teamName":"Maple Leafs"
+                    }
+                },
+                "preGameStats":{"records":{"PIT":{"wins":3,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":2}}},
+                "currentStats":{"records":{"PIT":{"wins":4,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":3}},
+                "streaks":{"PIT":{"type":"WINS","count":3},"TOR":{"type":"OT","count":2}},
+                "standings":{
+                    "PIT":{"divisionRank":"1","leagueRank":"1"},
+                    "CBJ":{"divisionRank":"7","leagueRank":"24"}
+                }
+            }
+        }"#,
+        )?;
+
+        let parsed_game = parse_game(&test_game).unwrap();
+
+        assert_eq!(parsed_game.home, "TOR");
+        assert_eq!(parsed_game.away, "PIT");
+        assert_eq!(parsed_game.score, "0-0");
+        assert_eq!(parsed_game.goals.len(), 0);
+        assert_eq!(parsed_game.status, "LIVE");
+        assert_eq!(parsed_game.special, Special::Regulation);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_a_playoffs_game_with_overtime_correctly() -> serde_json::Result<()> {
+        let test_game = serde_json::from_str(
+            r#"
+            {
+                "status":{
+                    "state":"FINAL"
+                },
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[{
+                    "team":"PIT",
+                    "period":"4",
+                    "scorer":{
+                        "player":"Sidney Crosby",
+                        "seasonTotal":3
+                    },
+                    "assists":[
+                        {
+                            "player":"Evgeni Malkin",
+                            "seasonTotal":2
+                        }
+                    ],
+                    "min":4,
+                    "sec":27
+                }],
+                    "scores":{
+                        "PIT":1,"TOR":0
+                    },
+                    "teams":{
+                        "away":{
+                            "abbreviation":"PIT",
+                            "id":14,
+                            "locationName":"Pittsburgh",
+                            "shortName":"Pittsburgh",
+                            "teamName":"Penguins"
+                        },
+                        "home":{
+                            "abbreviation":"TOR",
+                            "id":29,
+                            "locationName":"Toronto",
+                            "shortName":"Toronto",
+                            "teamName":"Maple Leafs"
+                        }
+                    },
+                    "preGameStats":{"records":{"PIT":{"wins":3,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":2}}},
+                    "currentStats":{"records":{"PIT":{"wins":4,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":3}},
+                    "streaks":{"PIT":{"type":"WINS","count":3},"TOR":{"type":"OT","count":2}},
+                    "standings":{
+                        "PIT":{"divisionRank":"1","leagueRank":"1"},
+                        "CBJ":{"divisionRank":"7","leagueRank":"24"}
+                    }
+                }
+            }"#,
+        )?;
+
+        let parsed_game = parse_game(&test_game).unwrap();
+
+        assert_eq!(parsed_game.home, "TOR");
+        assert_eq!(parsed_game.away, "PIT");
+        assert_eq!(parsed_game.score, "0-1");
+        assert_eq!(parsed_game.goals.len(), 1);
+        assert_eq!(parsed_game.status, "FINAL");
+        assert_eq!(parsed_game.special, Special::Overtime { periods: 1 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_marks_how_the_game_was_decided() {
+        assert_eq!(Special::Regulation.to_string(), "");
+        assert_eq!(Special::Overtime { periods: 1 }.to_string(), "ot");
+        assert_eq!(Special::Overtime { periods: 3 }.to_string(), "3ot");
+        assert_eq!(Special::Shootout.to_string(), "so");
+    }
+
+    #[test]
+    fn it_parses_incomplete_game_data_without_panicking() -> serde_json::Result<()> {
+        let games = parse_api_response(
+            r#"{"date":null,"errors":null,"games":[
+                {"status":{"state":"LIVE"},"startTime":"","goals":[{"team":"PIT","period":"X","scorer":{"player":""}},{"team":"PIT","period":"0","scorer":{"player":"Crosby"},"min":5}],"scores":{"PIT":2,"TOR":0},"teams":{"away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},"home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}},"preGameStats":{"records":{}},"currentStats":{"records":{},"standings":{}}},
+                {"status":{"state":"FINAL"},"startTime":"","scores":{},"teams":{"away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},"home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}},"preGameStats":{"records":{}},"currentStats":{"records":{},"standings":{}}}
+            ]}"#,
+        )?;
+
+        let live = games[0].as_ref().unwrap();
+        assert_eq!(live.goals[0].minute, 60);
+        assert_eq!(live.goals[1].minute, 5);
+        // Game without scores for its teams can't be shown
+        assert!(games[1].is_none());
+        assert!(parse_api_response(r#"{"games":["#).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_lists_only_national_broadcasts() -> serde_json::Result<()> {
+        let broadcasts: Vec<BroadcastResponse> = serde_json::from_str(
+            r#"[
+                { "network": "SNP", "market": "home" },
+                { "network": "ESPN", "market": "national", "country": "US" },
+                { "network": "SN", "market": "national", "country": "CA" },
+                { "network": "ESPN", "market": "NATIONAL", "country": "US" }
+            ]"#,
+        )?;
+
+        assert_eq!(
+            national_broadcasts(&broadcasts),
+            vec![String::from("ESPN"), String::from("SN")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_finds_upsets_and_blowouts() -> serde_json::Result<()> {
+        let game_with = |scores: &str, status: &str| -> serde_json::Result<Game> {
+            let game: GameResponse = serde_json::from_str(&format!(
+                r#"{{
+                    "status":{{"state":"{}"}},
+                    "startTime":"2021-01-23T19:00:00Z",
+                    "scores":{},
+                    "teams":{{
+                        "away":{{"abbreviation":"TBL","id":14,"locationName":"Tampa Bay","shortName":"Tampa Bay","teamName":"Lightning"}},
+                        "home":{{"abbreviation":"CBJ","id":29,"locationName":"Columbus","shortName":"Columbus","teamName":"Blue Jackets"}}
+                    }},
+                    "preGameStats":{{"records":{{}},"standings":{{"TBL":{{"leagueRank":"1"}},"CBJ":{{"leagueRank":"24"}}}}}},
+                    "currentStats":{{"records":{{}},"standings":{{}}}}
+                }}"#,
+                status, scores
+            ))?;
+            Ok(parse_game(&game).unwrap())
+        };
+
+        let upset = game_with(r#"{"CBJ":3,"TBL":2}"#, "FINAL")?;
+        assert_eq!(upset.ranks, Some((24, 1)));
+        assert_eq!(notable_results(&upset), [Notable::Upset]);
+
+        let blowout = game_with(r#"{"CBJ":0,"TBL":5}"#, "FINAL")?;
+        assert_eq!(notable_results(&blowout), [Notable::Blowout]);
+
+        let both = game_with(r#"{"CBJ":7,"TBL":2}"#, "FINAL")?;
+        assert_eq!(notable_results(&both), [Notable::Upset, Notable::Blowout]);
+
+        let live = game_with(r#"{"CBJ":7,"TBL":2}"#, "LIVE")?;
+        assert!(notable_results(&live).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_extracts_player_last_name_correctly() {
+        assert_eq!(
+            extract_player("Olli Maatta", "Chicago").last_name,
+            String::from("Maatta")
+        );
+        assert_eq!(
+            extract_player("James van Riemsdyk", "Philadelphia").last_name,
+            String::from("van Riemsdyk")
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! `235 history`, finished games from a local archive so past results can
+//! be looked through offline. The archive is JSON Lines in the format of
+//! `235 export`, so a season export can be appended to it as is.
+
+use crate::export::GameRecord;
+use crate::format::format_iso_date;
+use crate::game::Record;
+use crate::i18n::Text;
+use crate::options::RenderOptions;
+use crate::render::team_name;
+use crate::style::{Role, StyledLine};
+use std::collections::HashSet;
+
+/// Games of the archive, blank lines are skipped
+pub fn parse_archive(contents: &str) -> Result<Vec<GameRecord>, serde_json::Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect()
+}
+
+/// The records that aren't in the archive yet
+pub fn unarchived(archive: &[GameRecord], records: Vec<GameRecord>) -> Vec<GameRecord> {
+    let archived: HashSet<&str> = archive.iter().map(|record| record.id.as_str()).collect();
+    records
+        .into_iter()
+        .filter(|record| !archived.contains(record.id.as_str()))
+        .collect()
+}
+
+/// The `last` games of `teams`, or of every team when empty, oldest
+/// first. A game archived twice is only counted once.
+pub fn recent_games<'a>(
+    archive: &'a [GameRecord],
+    teams: &[String],
+    last: usize,
+) -> Vec<&'a GameRecord> {
+    let mut seen = HashSet::new();
+    let mut games: Vec<&GameRecord> = archive
+        .iter()
+        .filter(|record| seen.insert(record.id.as_str()))
+        .filter(|record| {
+            teams.is_empty() || teams.contains(&record.home) || teams.contains(&record.away)
+        })
+        .collect();
+    games.sort_by(|a, b| (&a.date, &a.start_time).cmp(&(&b.date, &b.start_time)));
+    let skip = games.len().saturating_sub(last);
+    games.split_off(skip)
+}
+
+/// Wins, regulation losses and overtime or shootout losses of `team`
+pub fn team_record(games: &[&GameRecord], team: &str) -> Record {
+    let mut record = Record {
+        wins: 0,
+        losses: 0,
+        ot: 0,
+    };
+    for game in games
+        .iter()
+        .filter(|game| game.home == team || game.away == team)
+    {
+        let (own, other) = if game.home == team {
+            (game.home_score, game.away_score)
+        } else {
+            (game.away_score, game.home_score)
+        };
+        if own > other {
+            record.wins += 1;
+        } else if game.decision == "regulation" {
+            record.losses += 1;
+        } else {
+            record.ot += 1;
+        }
+    }
+    record
+}
+
+/// A line per game with its date, teams and final score. When following
+/// a single team its record in the games comes last.
+pub fn history_lines(
+    games: &[&GameRecord],
+    teams: &[String],
+    options: &RenderOptions,
+) -> Vec<StyledLine> {
+    if games.is_empty() {
+        return vec![StyledLine::styled(
+            options.language.text(Text::NoHistory),
+            Role::Text,
+        )];
+    }
+
+    let mut lines: Vec<StyledLine> = games
+        .iter()
+        .map(|game| {
+            let mut line = StyledLine::styled(
+                format!("{:<7} ", format_iso_date(&game.date, options.language)),
+                Role::Info,
+            );
+            line.push(
+                format!(
+                    "{} - {} ",
+                    team_name(&game.home, options),
+                    team_name(&game.away, options)
+                ),
+                Role::Header,
+            );
+            let special = options.language.special(game.special());
+            let score = format!("{}-{}", game.home_score, game.away_score);
+            let score = if special.is_empty() {
+                score
+            } else {
+                format!("{} {}", special, score)
+            };
+            line.push(score, Role::FinalScore);
+            line
+        })
+        .collect();
+
+    if let [team] = teams {
+        lines.push(StyledLine::default());
+        lines.push(StyledLine::styled(
+            format!("{} {}", team_name(team, options), team_record(games, team)),
+            Role::SeriesInfo,
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::to_jsonl;
+
+    fn archive() -> Vec<GameRecord> {
+        let game = |date: &str, home: &str, away: &str, score: (u64, u64), decision: &str| {
+            format!(
+                r#"{{"schema":1,"id":"{}T23:00:00Z-{}-{}","date":"{}","start_time":"{}T23:00:00Z","home":"{}","away":"{}","home_score":{},"away_score":{},"decision":"{}","overtime_periods":{},"goals":[]}}"#,
+                date,
+                away,
+                home,
+                date,
+                date,
+                home,
+                away,
+                score.0,
+                score.1,
+                decision,
+                (decision == "overtime") as u64
+            )
+        };
+        let lines = [
+            game("2024-10-16", "FLA", "BOS", (4, 1), "regulation"),
+            game("2024-10-12", "TOR", "FLA", (3, 2), "overtime"),
+            game("2024-10-14", "PIT", "NYR", (2, 5), "regulation"),
+            game("2024-10-16", "FLA", "BOS", (4, 1), "regulation"),
+            String::new(),
+            game("2024-10-18", "FLA", "CAR", (1, 3), "regulation"),
+        ];
+        parse_archive(&lines.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn it_picks_the_latest_games_of_a_team() {
+        let archive = archive();
+        assert_eq!(archive.len(), 5);
+
+        let florida = [String::from("FLA")];
+        let games = recent_games(&archive, &florida, 10);
+        let dates: Vec<&str> = games.iter().map(|game| game.date.as_str()).collect();
+        assert_eq!(dates, ["2024-10-12", "2024-10-16", "2024-10-18"]);
+        assert_eq!(recent_games(&archive, &florida, 2)[0].date, "2024-10-16");
+        assert_eq!(recent_games(&archive, &[], 10).len(), 4);
+        assert_eq!(team_record(&games, "FLA").to_string(), "1-1-1");
+    }
+
+    #[test]
+    fn it_archives_only_new_games() {
+        let archive = archive();
+        let mut records = parse_archive(&to_jsonl(&archive[..2]).unwrap()).unwrap();
+        records[1].id = String::from("2024-10-20T23:00:00Z-BOS-FLA");
+        let fresh = unarchived(&archive, records);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].id, "2024-10-20T23:00:00Z-BOS-FLA");
+    }
+
+    #[test]
+    fn it_lists_the_games_and_the_record() {
+        let archive = archive();
+        let florida = [String::from("FLA")];
+        let games = recent_games(&archive, &florida, 10);
+        let options = RenderOptions::default().deterministic(true);
+        let lines: Vec<String> = history_lines(&games, &florida, &options)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "Oct 12  Toronto - Florida ot 3-2",
+                "Oct 16  Florida - Boston 4-1",
+                "Oct 18  Florida - Carolina 1-3",
+                "",
+                "Florida 1-1-1",
+            ]
+        );
+
+        let none = history_lines(&[], &florida, &options);
+        assert_eq!(none[0].text(), "No games in the history.");
+    }
+}
@@ -0,0 +1,99 @@
+//! The page as a static HTML fragment styled like YLE's teksti-tv page
+//! 235, white and coloured text on blue, for embedding the results on a
+//! web page.
+
+use crate::render::{Colour, Renderer};
+use crate::style::{Role, StyledLine};
+
+/// Styles of the fragment, scoped to its `nhl-235` class so they don't
+/// leak into the page around it
+const STYLE: &str = "<style>
+.nhl-235 { background: #0000aa; color: #ffffff; padding: 1em; font-family: monospace; line-height: 1.2; }
+.nhl-235 .white { color: #ffffff; }
+.nhl-235 .green { color: #00ff00; }
+.nhl-235 .red { color: #ff0000; }
+.nhl-235 .yellow { color: #ffff00; }
+.nhl-235 .cyan { color: #00ffff; }
+.nhl-235 .magenta { color: #ff00ff; }
+</style>
+";
+
+/// Renders the page as a `<pre>` with a span per coloured piece of text
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn paint(&self, text: &str, role: Role) -> String {
+        match Colour::of(role) {
+            Some(colour) => format!(
+                "<span class=\"{}\">{}</span>",
+                class_name(colour),
+                escape(text)
+            ),
+            None => escape(text),
+        }
+    }
+
+    fn render_lines(&self, lines: &[StyledLine]) -> String {
+        let mut output = String::from(STYLE);
+        output.push_str("<pre class=\"nhl-235\">\n");
+        for line in lines {
+            for span in &line.spans {
+                output.push_str(&self.paint(&span.text, span.role));
+            }
+            output.push('\n');
+        }
+        output.push_str("</pre>\n");
+        output
+    }
+}
+
+fn class_name(colour: Colour) -> &'static str {
+    match colour {
+        Colour::White => "white",
+        Colour::Green => "green",
+        Colour::Red => "red",
+        Colour::Yellow => "yellow",
+        Colour::Cyan => "cyan",
+        Colour::Magenta => "magenta",
+    }
+}
+
+/// Escapes the characters with a meaning in HTML
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_wraps_the_page_in_a_styled_pre() {
+        let mut line = StyledLine::styled("Pittsburgh - Toronto ", Role::Header);
+        line.push("2-1", Role::FinalScore);
+        let html = HtmlRenderer.render_lines(&[line, StyledLine::styled("Crosby", Role::Goal)]);
+
+        assert!(html.starts_with("<style>"));
+        assert!(html.contains(
+            "<pre class=\"nhl-235\">\n<span class=\"white\">Pittsburgh - Toronto </span><span class=\"green\">2-1</span>\n<span class=\"cyan\">Crosby</span>\n</pre>\n"
+        ));
+    }
+
+    #[test]
+    fn it_escapes_markup() {
+        assert_eq!(
+            HtmlRenderer.paint("<b> & \"", Role::Text),
+            "&lt;b&gt; &amp; &quot;"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use crate::game::Special;
+use std::str::FromStr;
+
+/// Language used for the fixed texts of the scoreboard.
+/// Team and player names are not translated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    En,
+    Fi,
+    Sv,
+}
+
+/// Fixed texts printed around the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    NoGames,
+    Series,
+    Postponed,
+    Length,
+    Ended,
+    NeutralSite,
+    ShootoutInProgress,
+    Total,
+    PowerPlay,
+    Upset,
+    Blowout,
+    SeasonSeries,
+    NoMeetings,
+    NoScheduledGames,
+    Goal,
+    NoPoints,
+    GamesPlayed,
+    Goals,
+    NoHistory,
+    Atlantic,
+    Metropolitan,
+    Central,
+    Pacific,
+    Eastern,
+    Western,
+}
+
+impl Lang {
+    pub fn text(self, text: Text) -> &'static str {
+        match (self, text) {
+            (Lang::En, Text::NoGames) => "No games today.",
+            (Lang::En, Text::Series) => "Series",
+            (Lang::En, Text::Postponed) => "POSTP.",
+            (Lang::En, Text::Length) => "Length",
+            (Lang::En, Text::Ended) => "ended",
+            (Lang::En, Text::NeutralSite) => "Neutral site",
+            (Lang::En, Text::ShootoutInProgress) => "SO in progress",
+            (Lang::En, Text::Total) => "Total",
+            (Lang::En, Text::PowerPlay) => "PP",
+            (Lang::En, Text::Upset) => "upset",
+            (Lang::En, Text::Blowout) => "blowout",
+            (Lang::En, Text::SeasonSeries) => "Season series",
+            (Lang::En, Text::NoMeetings) => "No games between the teams this season.",
+            (Lang::En, Text::NoScheduledGames) => "No games scheduled.",
+            (Lang::En, Text::Goal) => "GOAL",
+            (Lang::En, Text::NoPoints) => "No goals or assists.",
+            (Lang::En, Text::GamesPlayed) => "GP",
+            (Lang::En, Text::Goals) => "goals",
+            (Lang::En, Text::NoHistory) => "No games in the history.",
+            (Lang::En, Text::Atlantic) => "Atlantic",
+            (Lang::En, Text::Metropolitan) => "Metropolitan",
+            (Lang::En, Text::Central) => "Central",
+            (Lang::En, Text::Pacific) => "Pacific",
+            (Lang::En, Text::Eastern) => "Eastern",
+            (Lang::En, Text::Western) => "Western",
+            (Lang::Fi, Text::NoGames) => "Ei otteluita tänään.",
+            (Lang::Fi, Text::Series) => "Sarja",
+            (Lang::Fi, Text::Postponed) => "SIIRR.",
+            (Lang::Fi, Text::Length) => "Kesto",
+            (Lang::Fi, Text::Ended) => "päättyi",
+            (Lang::Fi, Text::NeutralSite) => "Puolueeton kenttä",
+            (Lang::Fi, Text::ShootoutInProgress) => "VL käynnissä",
+            (Lang::Fi, Text::Total) => "Yhteensä",
+            (Lang::Fi, Text::PowerPlay) => "YV",
+            (Lang::Fi, Text::Upset) => "yllätys",
+            (Lang::Fi, Text::Blowout) => "murskavoitto",
+            (Lang::Fi, Text::SeasonSeries) => "Kauden sarja",
+            (Lang::Fi, Text::NoMeetings) => "Joukkueet eivät ole kohdanneet tällä kaudella.",
+            (Lang::Fi, Text::NoScheduledGames) => "Ei tulevia otteluita.",
+            (Lang::Fi, Text::Goal) => "MAALI",
+            (Lang::Fi, Text::NoPoints) => "Ei maaleja eikä syöttöjä.",
+            (Lang::Fi, Text::GamesPlayed) => "O",
+            (Lang::Fi, Text::Goals) => "maalit",
+            (Lang::Fi, Text::NoHistory) => "Ei otteluita historiassa.",
+            (Lang::Fi, Text::Atlantic) => "Atlantin divisioona",
+            (Lang::Fi, Text::Metropolitan) => "Metropolitan-divisioona",
+            (Lang::Fi, Text::Central) => "Keskinen divisioona",
+            (Lang::Fi, Text::Pacific) => "Tyynenmeren divisioona",
+            (Lang::Fi, Text::Eastern) => "Itäinen konferenssi",
+            (Lang::Fi, Text::Western) => "Läntinen konferenssi",
+            (Lang::Sv, Text::NoGames) => "Inga matcher idag.",
+            (Lang::Sv, Text::Series) => "Serie",
+            (Lang::Sv, Text::Postponed) => "UPPSK.",
+            (Lang::Sv, Text::Length) => "Längd",
+            (Lang::Sv, Text::Ended) => "slutade",
+            (Lang::Sv, Text::NeutralSite) => "Neutral arena",
+            (Lang::Sv, Text::ShootoutInProgress) => "Straffar pågår",
+            (Lang::Sv, Text::Total) => "Totalt",
+            (Lang::Sv, Text::PowerPlay) => "PP",
+            (Lang::Sv, Text::Upset) => "skräll",
+            (Lang::Sv, Text::Blowout) => "storseger",
+            (Lang::Sv, Text::SeasonSeries) => "Säsongsserie",
+            (Lang::Sv, Text::NoMeetings) => "Lagen har inte mötts den här säsongen.",
+            (Lang::Sv, Text::NoScheduledGames) => "Inga kommande matcher.",
+            (Lang::Sv, Text::Goal) => "MÅL",
+            (Lang::Sv, Text::NoPoints) => "Inga mål eller assist.",
+            (Lang::Sv, Text::GamesPlayed) => "M",
+            (Lang::Sv, Text::Goals) => "mål",
+            (Lang::Sv, Text::NoHistory) => "Inga matcher i historiken.",
+            (Lang::Sv, Text::Atlantic) => "Atlantic-divisionen",
+            (Lang::Sv, Text::Metropolitan) => "Metropolitan-divisionen",
+            (Lang::Sv, Text::Central) => "Central-divisionen",
+            (Lang::Sv, Text::Pacific) => "Pacific-divisionen",
+            (Lang::Sv, Text::Eastern) => "Östra konferensen",
+            (Lang::Sv, Text::Western) => "Västra konferensen",
+        }
+    }
+
+    /// Marker printed before the final score of a game that went past
+    /// regulation, like "ot" or "so"
+    pub fn special(self, special: Special) -> String {
+        match (self, special) {
+            (Lang::Sv, Special::Regulation) => String::new(),
+            (Lang::Sv, Special::Overtime { periods: 1 }) => String::from("fl"),
+            (Lang::Sv, Special::Overtime { periods }) => format!("{}fl", periods),
+            (Lang::Sv, Special::Shootout) => String::from("str"),
+            (Lang::Fi, Special::Regulation) => String::new(),
+            (Lang::Fi, Special::Overtime { periods: 1 }) => String::from("ja"),
+            (Lang::Fi, Special::Overtime { periods }) => format!("{}ja", periods),
+            (Lang::Fi, Special::Shootout) => String::from("vl"),
+            (_, special) => special.to_string(),
+        }
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "en" => Ok(Lang::En),
+            "fi" => Ok(Lang::Fi),
+            "sv" => Ok(Lang::Sv),
+            _ => Err(format!(
+                "unsupported language '{}', use one of: en, fi, sv",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_marks_games_past_regulation_in_swedish() {
+        assert_eq!("SV".parse(), Ok(Lang::Sv));
+        assert_eq!(Lang::Sv.special(Special::Overtime { periods: 1 }), "fl");
+        assert_eq!(Lang::Sv.special(Special::Overtime { periods: 2 }), "2fl");
+        assert_eq!(Lang::Sv.special(Special::Shootout), "str");
+        assert_eq!(Lang::En.special(Special::Shootout), "so");
+        assert_eq!(Lang::Sv.text(Text::NoGames), "Inga matcher idag.");
+    }
+
+    #[test]
+    fn it_marks_games_past_regulation_in_finnish() {
+        assert_eq!(Lang::Fi.special(Special::Regulation), "");
+        assert_eq!(Lang::Fi.special(Special::Overtime { periods: 1 }), "ja");
+        assert_eq!(Lang::Fi.special(Special::Overtime { periods: 3 }), "3ja");
+        assert_eq!(Lang::Fi.special(Special::Shootout), "vl");
+    }
+}
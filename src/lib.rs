@@ -0,0 +1,51 @@
+/*!
+ * Library side of NHL-235. Other tools (bots, status bar widgets, dashboards)
+ * can use the same API client, parsing and teletext layout as the `235`
+ * command line tool without re-implementing them.
+ *
+ * Parsing and plain-text rendering don't depend on the network stack so they
+ * can be built without default features, for example for wasm32-unknown-unknown.
+ * The `client` feature adds `ScoreClient` for fetching the data and the
+ * `ffi` feature a C ABI for the renderer (see `include/nhl235.h`).
+ */
+
+pub mod api_types;
+pub mod ascii;
+pub mod boxscore;
+pub mod bracket;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod csv;
+pub mod events;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
+pub mod game;
+pub mod history;
+pub mod html;
+pub mod i18n;
+pub mod links;
+pub mod markdown;
+pub mod options;
+pub mod player;
+pub mod render;
+pub mod roster;
+pub mod schedule;
+pub mod standings;
+pub mod stats;
+pub mod style;
+pub mod table;
+pub mod teams;
+pub mod terminal;
+pub mod tsv;
+pub mod versus;
+
+#[cfg(feature = "client")]
+pub use client::{fetch_games, ClientConfig, ClientError, ScoreClient};
+pub use game::{is_close_game, parse_api_response, parse_games, Game, Goal, Player, Special};
+pub use i18n::Lang;
+pub use options::{GoalOrder, Layout, OutputFormat, RenderOptions, TeamNames};
+pub use render::render_games;
+pub use stats::{StatsFormat, StatsStyle};
+pub use terminal::TerminalInfo;
@@ -0,0 +1,69 @@
+//! Links to nhl.com for the teams and the game days on the page. Renderers
+//! that can, like `HyperlinkRenderer` on a terminal with OSC 8 support,
+//! make the team names and scores clickable with them.
+
+use crate::game::Game;
+use crate::teams::TEAMS;
+use chrono::{DateTime, Duration};
+
+const NHL_URL: &str = "https://www.nhl.com";
+
+/// Team page like "https://www.nhl.com/penguins", None for teams outside
+/// the league like all-star or international teams
+pub fn team_url(abbr: &str) -> Option<String> {
+    let team = TEAMS.iter().find(|team| team.abbreviation == abbr)?;
+    let slug = match team.abbreviation {
+        // The nickname of Utah is a placeholder, the page is named by city
+        "UTA" => String::from("utah"),
+        _ => team.nickname.to_lowercase().replace(' ', ""),
+    };
+    Some(format!("{}/{}", NHL_URL, slug))
+}
+
+/// Scores of the game's day on nhl.com, like
+/// "https://www.nhl.com/scores/2024-10-16". The NHL dates games in North
+/// American time and they start from midday on, so the date five hours
+/// behind UTC is the day of the game.
+pub fn game_url(game: &Game) -> Option<String> {
+    let start = DateTime::parse_from_rfc3339(&game.start_time).ok()?;
+    let day = (start - Duration::hours(5)).date_naive();
+    Some(format!("{}/scores/{}", NHL_URL, day.format("%Y-%m-%d")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::APIResponse;
+    use crate::game::parse_games;
+
+    #[test]
+    fn it_links_teams_to_their_pages() {
+        assert_eq!(
+            team_url("PIT").as_deref(),
+            Some("https://www.nhl.com/penguins")
+        );
+        assert_eq!(
+            team_url("TOR").as_deref(),
+            Some("https://www.nhl.com/mapleleafs")
+        );
+        assert_eq!(team_url("UTA").as_deref(), Some("https://www.nhl.com/utah"));
+        assert_eq!(team_url("FIN"), None);
+    }
+
+    #[test]
+    fn it_links_games_to_the_scores_of_their_day() {
+        let scores: APIResponse =
+            serde_json::from_str(include_str!("../tests/fixtures/live.json")).unwrap();
+        let mut game = parse_games(scores).remove(0).unwrap();
+        // An evening game and a late one on the west coast
+        for start in &["2024-10-16T23:00:00Z", "2024-10-17T02:30:00Z"] {
+            game.start_time = start.to_string();
+            assert_eq!(
+                game_url(&game).as_deref(),
+                Some("https://www.nhl.com/scores/2024-10-16")
+            );
+        }
+        game.start_time = String::new();
+        assert_eq!(game_url(&game), None);
+    }
+}
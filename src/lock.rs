@@ -0,0 +1,48 @@
+//! Advisory locks on the files several 235 runs can write at once, like
+//! `notify-once` from cron while a status bar refreshes the page. Each
+//! file is locked through a `.lock` file next to it, so the file itself
+//! can still be replaced with a rename.
+
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An exclusive lock on a file, released when dropped
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Waits until no other run holds the lock of `path`
+    pub fn acquire(path: &Path) -> io::Result<FileLock> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(with_suffix(path, ".lock"))?;
+        file.lock()?;
+        Ok(FileLock { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// A temporary file next to `path` that no other run or thread uses
+pub fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    with_suffix(path, &format!(".{}.{}.tmp", process::id(), count))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * NHL-235 is a command line tool for showing NHL results from previous day or current
  * in a format that's mimicing YLE's Tekstitv aesthetics
  *
@@ -8,62 +8,72 @@
  * Uses https://github.com/peruukki/nhl-score-api API for score info
  */
 
-#[macro_use]
-extern crate colour;
+mod config;
+mod console;
+mod examples;
+mod lock;
+mod notify;
+mod secrets;
+mod tui;
+
 use atty::Stream;
-use dirs::home_dir;
-use itertools::{EitherOrBoth::*, Itertools};
-use reqwest::Error;
-use std::collections::HashMap;
-use std::fs::File;
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use config::{
+    config_path, export_bundle, highlights_path, import_bundle, read_config, warn_if_slow, Config,
+    ConfigError, ConfigWatcher, Timeouts,
+};
+use lock::FileLock;
+use nhl_235::api_types::{APIResponse, PlayoffBracketResponse, RosterResponse, StandingsResponse};
+use nhl_235::ascii::{ascii_names, ascii_roster_names, transliterate};
+use nhl_235::boxscore::boxscore_lines;
+use nhl_235::bracket::{bracket_lines, parse_bracket};
+use nhl_235::csv::goals_csv;
+use nhl_235::events::{
+    changes, event_lines, refresh_delay, GoalTracker, WatchOptions, LIVE_REFRESH,
+};
+use nhl_235::export::{game_records, season_dates, season_of, to_jsonl, ExportFormat, GameRecord};
+use nhl_235::history::{history_lines, parse_archive, recent_games, unarchived};
+use nhl_235::html::HtmlRenderer;
+use nhl_235::markdown::games_markdown;
+use nhl_235::player::{player_lines, player_points};
+use nhl_235::render::{goal_banner, roster_lines, standings_lines, teams_lines, Renderer, Section};
+use nhl_235::roster::parse_roster;
+use nhl_235::schedule::{schedule_lines, upcoming_games};
+use nhl_235::standings::{parse_conferences, parse_standings, Division};
+use nhl_235::style::StyledLine;
+use nhl_235::table::{
+    bracket_table, events_table, history_table, player_table, roster_table, schedule_table,
+    standings_table, teams_table, versus_table, Table,
+};
+use nhl_235::teams::{
+    match_conference, match_division, match_team, resolve_team, team_listing, Group, NameMatch,
+    TeamMatch,
+};
+use nhl_235::tsv::games_tsv;
+use nhl_235::versus::{season_series, versus_lines};
+use nhl_235::{
+    parse_games, ClientConfig, ClientError, Game, GoalOrder, Lang, Layout, OutputFormat,
+    RenderOptions, ScoreClient, TeamNames, TerminalInfo,
+};
+use notify::{
+    default_log_path, default_state_path, notify_once, ping_healthcheck, read_event_log,
+    NotifyError,
+};
+use secrets::{delete_secret, set_secret};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs::{self, File};
 use std::io::Error as StdError;
-use std::io::Read;
+use std::io::ErrorKind;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::thread;
+use std::time::{Duration as StdDuration, Instant};
 use structopt::StructOpt;
 
-const SHOOTOUT_MINUTE: u64 = 65;
-
-mod api_types;
-use api_types::{APIResponse, GameResponse, GoalResponse};
-
-struct Goal {
-    scorer: Player,
-    assists: Vec<Player>,
-    minute: u64,
-    special: bool,
-    team: String,
-}
-
-#[derive(Debug)]
-struct Stat {
-    goals: u64,
-    assists: u64,
-}
-
-#[derive(Clone, Eq, Hash, PartialEq)]
-struct Player {
-    first_name: String,
-    last_name: String,
-    team: String,
-}
-
-struct Game {
-    home: String,
-    away: String,
-    score: String,
-    goals: Vec<Goal>,
-    status: String,
-    special: String,
-    playoff_series: Option<HashMap<String, serde_json::Value>>,
-}
-
-#[derive(Debug)]
-struct Options {
-    use_colors: bool,
-    show_highlights: bool,
-    show_stats: bool,
-}
-
 #[derive(StructOpt, Debug)]
 /// Display live or previous NHL match results on command line
 ///
@@ -77,6 +87,43 @@ struct Cli {
     #[structopt(long)]
     #[structopt(help = "Disable terminal colors")]
     nocolors: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "Print times in UTC and never use terminal colors so that runs against the same data produce identical output"
+    )]
+    deterministic: bool,
+    #[structopt(long, default_value = "en")]
+    #[structopt(help = "Language of the output: en, fi or sv")]
+    lang: Lang,
+    #[structopt(long)]
+    #[structopt(
+        help = "Write player names without diacritics, like Teravainen for Teräväinen, in scores, stats, rosters and exports"
+    )]
+    ascii_names: bool,
+    #[structopt(long)]
+    #[structopt(help = "Warn on stderr about network operations that are close to timing out")]
+    verbose: bool,
+    #[structopt(long, parse(try_from_str = parse_rate))]
+    #[structopt(help = "Maximum requests per second to the score API [default: 1]")]
+    max_rps: Option<f64>,
+    /// Developer option: read the scores from an API response saved in a
+    /// file, like tests/fixtures/live.json, instead of fetching them
+    #[structopt(long, hidden = true, parse(from_os_str))]
+    mock: Option<PathBuf>,
+    /// Developer option: base URL of the score API, for running against
+    /// a local mock server
+    #[structopt(long, hidden = true)]
+    api_url: Option<String>,
+    /// Flags of the scores page, also accepted without the `scores` subcommand
+    #[structopt(flatten)]
+    scores: ScoreArgs,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+/// What to show on the scores page
+#[derive(StructOpt, Debug, Clone)]
+struct ScoreArgs {
     #[structopt(long)]
     #[structopt(
         help = "Highlight players based on $HOME/.235.config file. If --nocolors is enabled, does nothing"
@@ -87,40 +134,1313 @@ struct Cli {
         help = "Display stats (goals + assists) for players defined in $HOME/.235.config file."
     )]
     stats: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "Comma-separated last names of the players for --stats and --highlight instead of $HOME/.235.config, like \"Aho,Barkov\""
+    )]
+    players: Option<String>,
+    #[structopt(long = "highlight-player", number_of_values = 1)]
+    #[structopt(
+        help = "Highlight a player in addition to those in $HOME/.235.config, by last name. Can be given several times"
+    )]
+    highlight_players: Vec<String>,
+    #[structopt(long)]
+    #[structopt(
+        help = "Exit with an error instead of a warning when --highlight, --stats or --only-highlights finds no players in $HOME/.235.config"
+    )]
+    require_config: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "With several dates, show who the highlighted players got their points against in the total stats"
+    )]
+    opponents: bool,
+    #[structopt(long)]
+    #[structopt(help = "Show game length and estimated end time for finished games")]
+    duration: bool,
+    #[structopt(long)]
+    #[structopt(help = "Show arena and city for scheduled and live games")]
+    venue: bool,
+    #[structopt(long)]
+    #[structopt(help = "Show national TV broadcasts for scheduled games")]
+    broadcasts: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "Mark live games that are within one goal in the third period or tied in overtime"
+    )]
+    close_games: bool,
+    #[structopt(long)]
+    #[structopt(help = "Show scorers with their first initial, like S. Crosby")]
+    initials: bool,
+    #[structopt(long, conflicts_with = "abbr")]
+    #[structopt(help = "Show teams by city and nickname, like Pittsburgh Penguins")]
+    full_names: bool,
+    #[structopt(long)]
+    #[structopt(help = "Show teams by abbreviation, like PIT - TOR, for narrow terminals")]
+    abbr: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "Color final scores green when a favorite team won and red when it lost. Favorites are set with favorites = [...] in $HOME/.235.toml, or are the --team teams"
+    )]
+    outcome_colors: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "Mark upsets (^), where a team ranked 10 or more places lower won, and blowouts (*) won by 5 or more goals"
+    )]
+    notable: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "Show games without favorite teams or highlighted players on one line, without goals"
+    )]
+    collapse_others: bool,
+    #[structopt(long)]
+    #[structopt(
+        help = "List only the goals scored or assisted by highlighted players under each game"
+    )]
+    only_highlights: bool,
+    #[structopt(long, default_value = "team")]
+    #[structopt(
+        help = "Order of goals: team lists each team's goals in its own column, time lists them one per row as the game went"
+    )]
+    goal_order: GoalOrder,
+    #[structopt(long)]
+    #[structopt(
+        help = "Column widths: classic, wide for long names or narrow for small panes. Fine-tune them in the [layout] section of $HOME/.235.toml"
+    )]
+    layout: Option<Layout>,
+    #[structopt(long)]
+    #[structopt(
+        help = "No blank lines between games. Set the default with spacing = 0-2 in $HOME/.235.toml"
+    )]
+    tight: bool,
+    #[structopt(long = "team", number_of_values = 1)]
+    #[structopt(
+        help = "Only show games of a team: abbreviation, name or nickname, or a group from [teams] in $HOME/.235.toml. Can be given several times"
+    )]
+    teams: Vec<String>,
+    #[structopt(long = "date", number_of_values = 1, parse(try_from_str = parse_date))]
+    #[structopt(
+        help = "Show results of a date (YYYY-MM-DD) instead of the latest ones. Can be given several times"
+    )]
+    dates: Vec<NaiveDate>,
+    #[structopt(long, conflicts_with = "dates", parse(try_from_str = parse_days))]
+    #[structopt(help = "Show results of the last N days up to today, at most 31")]
+    days: Option<u32>,
+    #[structopt(long, conflicts_with_all = &["dates", "days"], parse(try_from_str = parse_date))]
+    #[structopt(
+        help = "Show results of every day from this date (YYYY-MM-DD), up to --to or today"
+    )]
+    from: Option<NaiveDate>,
+    #[structopt(long, conflicts_with_all = &["dates", "days"], parse(try_from_str = parse_date))]
+    #[structopt(help = "Last date (YYYY-MM-DD) to show with --from")]
+    to: Option<NaiveDate>,
+    #[structopt(long)]
+    #[structopt(
+        help = "Keep running and refresh the scores, every 30 seconds while followed games are live, every 10 minutes before them and not at all from when they're over until midday"
+    )]
+    watch: bool,
+    #[structopt(long, conflicts_with = "watch")]
+    #[structopt(
+        help = "Print the games as JSON instead of the page. With several dates, a list of days with their games. Same as --format json"
+    )]
+    json: bool,
+    #[structopt(long, conflicts_with_all = &["watch", "json"])]
+    #[structopt(
+        help = "Output format: page, json, csv with one row per goal (date, home, away, scorer, assists, minute, period, strength), markdown with a table per game, html styled like the teletext page, or tsv with a line per game and goal [default: page]"
+    )]
+    format: Option<OutputFormat>,
+    #[structopt(long, conflicts_with_all = &["watch", "json", "format"])]
+    #[structopt(
+        help = "Print the response of the score API as pretty-printed JSON instead of the page, for debugging"
+    )]
+    raw: bool,
+    #[structopt(long, parse(try_from_str = parse_interval))]
+    #[structopt(
+        help = "Seconds between refreshes with --watch and in the TUI instead of following the games, at least 5"
+    )]
+    interval: Option<u64>,
+}
+
+impl ScoreArgs {
+    /// These flags given after `scores` together with the `before` it, so
+    /// that `235 --stats scores --team PIT` keeps both
+    fn after(&self, before: &ScoreArgs) -> ScoreArgs {
+        let list = |after: &[String], before: &[String]| [before, after].concat();
+        ScoreArgs {
+            highlight: self.highlight || before.highlight,
+            stats: self.stats || before.stats,
+            players: self.players.clone().or_else(|| before.players.clone()),
+            highlight_players: list(&self.highlight_players, &before.highlight_players),
+            require_config: self.require_config || before.require_config,
+            opponents: self.opponents || before.opponents,
+            duration: self.duration || before.duration,
+            venue: self.venue || before.venue,
+            broadcasts: self.broadcasts || before.broadcasts,
+            close_games: self.close_games || before.close_games,
+            initials: self.initials || before.initials,
+            full_names: self.full_names || before.full_names,
+            abbr: self.abbr || before.abbr,
+            outcome_colors: self.outcome_colors || before.outcome_colors,
+            notable: self.notable || before.notable,
+            collapse_others: self.collapse_others || before.collapse_others,
+            only_highlights: self.only_highlights || before.only_highlights,
+            goal_order: if self.goal_order == GoalOrder::default() {
+                before.goal_order
+            } else {
+                self.goal_order
+            },
+            layout: self.layout.or(before.layout),
+            tight: self.tight || before.tight,
+            teams: list(&self.teams, &before.teams),
+            dates: [&before.dates[..], &self.dates[..]].concat(),
+            days: self.days.or(before.days),
+            from: self.from.or(before.from),
+            to: self.to.or(before.to),
+            watch: self.watch || before.watch,
+            json: self.json || before.json,
+            format: self.format.or(before.format),
+            raw: self.raw || before.raw,
+            interval: self.interval.or(before.interval),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+enum Command {
+    /// Show live or latest scores, the default when no subcommand is given
+    Scores(ScoreArgs),
+    /// Show the league standings by division
+    Standings {
+        #[structopt(long, conflicts_with = "conference")]
+        #[structopt(help = "Show only this division, like MET or Pacific")]
+        division: Option<String>,
+        #[structopt(long)]
+        #[structopt(help = "Show only this conference, EAST or WEST")]
+        conference: Option<String>,
+    },
+    /// Browse the games in an interactive view with every goal and assist.
+    /// Takes the same flags as the scores page before the subcommand,
+    /// like `235 --team PIT tui`.
+    Tui,
+    /// Print new goals, game starts and final scores since the previous run
+    /// and send them to the webhook in $HOME/.235.toml, then exit.
+    /// Meant to be run from a systemd timer or cron.
+    NotifyOnce {
+        #[structopt(long, parse(from_os_str))]
+        #[structopt(
+            help = "File to keep already reported events in [default: $HOME/.235.state.json]"
+        )]
+        state: Option<PathBuf>,
+        #[structopt(long, parse(from_os_str))]
+        #[structopt(help = "File to log delivered events in [default: $HOME/.235.events.jsonl]")]
+        log: Option<PathBuf>,
+        #[structopt(long)]
+        #[structopt(help = "Also report any game of the league going to overtime or a shootout")]
+        alert_ot: bool,
+    },
+    /// Print the events notify-once has delivered as JSON Lines, for
+    /// integrations that missed them
+    Events {
+        #[structopt(long, parse(try_from_str = parse_since))]
+        #[structopt(
+            help = "Only events delivered at or after this time, RFC 3339 or a YYYY-MM-DD date in UTC"
+        )]
+        since: Option<DateTime<Utc>>,
+        #[structopt(long, parse(from_os_str))]
+        #[structopt(help = "Event log to read [default: $HOME/.235.events.jsonl]")]
+        log: Option<PathBuf>,
+    },
+    /// Write the completed games of a season to a file, one game per line.
+    /// The fields are described in the README.
+    Export {
+        #[structopt(long)]
+        #[structopt(help = "Season by its starting year, 2023 for 2023-24")]
+        season: i32,
+        #[structopt(long, default_value = "jsonl")]
+        #[structopt(help = "File format: jsonl")]
+        format: ExportFormat,
+        #[structopt(long, parse(from_os_str))]
+        #[structopt(help = "File to write to [default: stdout]")]
+        out: Option<PathBuf>,
+    },
+    /// Show the box score of one game: goals by period, every assist and
+    /// strength, the shootout and the records of the teams. Takes the
+    /// same flags as the scores page, like `235 --date 2024-10-16 game PIT-TOR`.
+    Game {
+        #[structopt(help = "The teams of the game, like PIT-TOR")]
+        matchup: String,
+    },
+    /// Show what changed between two snapshots of the results: new goals,
+    /// score changes, and games starting and ending. A snapshot is a date
+    /// to fetch or a saved response, like one written with --raw.
+    Diff {
+        #[structopt(help = "Earlier snapshot, YYYY-MM-DD or a JSON file")]
+        before: String,
+        #[structopt(help = "Later snapshot, YYYY-MM-DD or a JSON file")]
+        after: String,
+    },
+    /// Show this season's games between two teams, the season series and
+    /// the record and goals of the first team against the other
+    #[structopt(alias = "h2h")]
+    Versus {
+        #[structopt(help = "Team abbreviation, name or nickname")]
+        team: String,
+        #[structopt(help = "The other team")]
+        opponent: String,
+    },
+    /// Draw the playoff bracket with the series scores
+    Playoffs {
+        #[structopt(long)]
+        #[structopt(help = "Season by its starting year, 2023 for 2023-24 [default: current]")]
+        season: Option<i32>,
+    },
+    /// List the games of today and tomorrow that haven't started yet,
+    /// with their start times in the local time zone
+    Schedule,
+    /// List the goals and assists of a player over the last days with
+    /// the points added up
+    Player {
+        #[structopt(help = "Last name or full name of the player")]
+        name: String,
+        #[structopt(long, default_value = "14", parse(try_from_str = parse_days))]
+        #[structopt(help = "Number of days to look back, up to 31")]
+        days: u32,
+    },
+    /// List every team with its abbreviation, the name on the page, the
+    /// full name and the short names that --team and the config accept
+    Teams,
+    /// List finished games from the local archive without going online.
+    /// With `archive = true` under `[history]` every fetch of the scores
+    /// keeps its finished games in the archive, and `235 export` output can
+    /// be appended to it.
+    History {
+        #[structopt(long = "team", number_of_values = 1)]
+        #[structopt(
+            help = "Only games of a team, like with the scores page. Can be given several times"
+        )]
+        teams: Vec<String>,
+        #[structopt(long, default_value = "10")]
+        #[structopt(help = "Number of latest games to show")]
+        last: usize,
+        #[structopt(long, parse(from_os_str))]
+        #[structopt(help = "Archive to read [default: $HOME/.235.results.jsonl]")]
+        file: Option<PathBuf>,
+    },
+    /// Print the current roster of a team
+    Roster {
+        #[structopt(help = "Team abbreviation, name or nickname")]
+        team: String,
+    },
+    /// Show common ways to run 235, like following live games, setting up
+    /// highlighted players, status bars and cron digests
+    Examples,
+    /// Move settings between machines
+    Config(ConfigCommand),
+    /// Store tokens and passwords in the OS keyring
+    Secret(SecretCommand),
+}
+
+#[derive(StructOpt, Debug)]
+enum SecretCommand {
+    /// Read a secret from stdin and store it in the keyring under a name
+    /// that $HOME/.235.toml can refer to
+    Set { name: String },
+    /// Remove a secret from the keyring
+    Delete { name: String },
+}
+
+#[derive(StructOpt, Debug)]
+enum ConfigCommand {
+    /// Print $HOME/.235.toml and the highlighted players as one TOML file.
+    /// Secrets like the webhook URL are left out.
+    Export,
+    /// Replace the settings with an exported file. Secrets of this machine
+    /// are kept.
+    Import {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
 }
 
 fn main() {
-    let args = Cli::from_args();
+    console::use_utf8_output();
+    // The examples at the end of --help come from the same list as
+    // `235 examples`, leaked once to live as long as the parser
+    let help: &'static str = Box::leak(examples::help_text().into_boxed_str());
+    let args = Cli::from_clap(&Cli::clap().after_help(help).get_matches());
     if args.version {
         println!("{}", env!("CARGO_PKG_VERSION"));
         std::process::exit(0);
     }
 
-    let highlights = read_highlight_config().unwrap_or_default();
+    // Importing has to work even if the current config is broken
+    if let Some(Command::Config(command)) = &args.command {
+        if let Err(e) = run_config_command(command) {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Command::Examples) = &args.command {
+        print!("{}", examples::examples_text());
+        return;
+    }
 
-    let options: Options = Options {
-        // Using an inverse here because default is colors enabled
-        // and I want to keep the API easier to read down the line,
-        // hence colors need to be enabled rather than disabled
-        use_colors: !args.nocolors,
-        show_stats: args.stats,
-        show_highlights: args.highlight,
+    if let Some(Command::Events { since, log }) = &args.command {
+        if let Err(e) = print_event_log(*since, log.as_deref()) {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Command::Secret(command)) = &args.command {
+        if let Err(e) = run_secret_command(command) {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let config = read_config().unwrap_or_else(|e| {
+        println!("ERROR: {}", e);
+        process::exit(1);
+    });
+    // Legacy invocations like `235 --stats` keep working without `scores`
+    let merged;
+    let scores = match &args.command {
+        Some(Command::Scores(after)) => {
+            merged = after.after(&args.scores);
+            &merged
+        }
+        _ => &args.scores,
     };
 
-    match fetch_games() {
-        Ok(scores) => {
-            let parsed_games = parse_games(scores);
-            print_games(parsed_games, &highlights, &options);
+    let Settings { teams, options } = load_settings(&args, scores, &config).unwrap_or_else(|e| {
+        println!("ERROR: {}", e);
+        process::exit(1);
+    });
+    if (scores.highlight || scores.stats || scores.only_highlights) && options.highlights.is_empty()
+    {
+        let message = missing_highlights_message();
+        if scores.require_config {
+            println!("ERROR: {}", message);
+            process::exit(1);
         }
+        eprintln!("WARNING: {}", message);
+    }
+    // Long-running modes pick up changes to the config files
+    let reload_settings = || -> Result<Settings, String> {
+        let config = read_config().map_err(|e| e.to_string())?;
+        load_settings(&args, scores, &config)
+    };
+    let terminal = detect_terminal();
+
+    // One client for the whole run so that every request counts against
+    // the same rate limit
+    let mut client_config = ClientConfig {
+        timeout: Some(config.timeouts.api()),
+        ..ClientConfig::default()
+    };
+    if let Some(max_rps) = args.max_rps {
+        client_config.max_rps = Some(max_rps);
+    }
+    if let Some(api_url) = &args.api_url {
+        client_config.score_api_url = api_url.to_string();
+    }
+    let client = ScoreClient::new(client_config);
+
+    if let Some(Command::Export {
+        season,
+        format: ExportFormat::Jsonl,
+        out,
+    }) = &args.command
+    {
+        export_season(
+            &client,
+            *season,
+            out.as_deref(),
+            &teams,
+            args.ascii_names,
+            &config.timeouts,
+            args.verbose,
+        );
+        return;
+    }
+
+    if let Some(Command::Teams) = &args.command {
+        let teams = team_listing();
+        print_output(
+            output_format(scores),
+            &teams,
+            &teams_lines(&teams),
+            teams_table(&teams),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    if let Some(Command::History {
+        teams: history_teams,
+        last,
+        file,
+    }) = &args.command
+    {
+        let history_teams = if history_teams.is_empty() {
+            teams.clone()
+        } else {
+            resolve_teams(history_teams, &config).unwrap_or_else(|e| {
+                println!("ERROR: {}", e);
+                process::exit(1);
+            })
+        };
+        let path = file
+            .clone()
+            .or_else(|| config.history.path())
+            .unwrap_or_else(|| {
+                println!("ERROR: Can't find the home directory, pass --file");
+                process::exit(1);
+            });
+        let archive = read_archive(&path).unwrap_or_else(|e| {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        });
+        let games = recent_games(&archive, &history_teams, *last);
+        print_output(
+            output_format(scores),
+            &games,
+            &history_lines(&games, &history_teams, &options),
+            history_table(&games),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    if let Some(Command::Roster { team }) = &args.command {
+        let team = resolve_team_name(team).unwrap_or_else(|e| {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        });
+        print_roster(
+            &client,
+            &team,
+            args.ascii_names,
+            output_format(scores),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    if let Some(Command::Standings {
+        division,
+        conference,
+    }) = &args.command
+    {
+        let table = match (division, conference) {
+            (Some(division), _) => resolve_group("division", division, match_division).map(Some),
+            (None, Some(conference)) => {
+                resolve_group("conference", conference, match_conference).map(Some)
+            }
+            (None, None) => Ok(None),
+        };
+        let table = table.unwrap_or_else(|e| {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        });
+        print_standings(&client, table, output_format(scores), &terminal, &options);
+        return;
+    }
+
+    if let Some(Command::Playoffs { season }) = &args.command {
+        let season = season.unwrap_or_else(|| season_of(Local::now().date_naive()));
+        print_bracket(&client, season, output_format(scores), &terminal, &options);
+        return;
+    }
+
+    if let Some(Command::Diff { before, after }) = &args.command {
+        let load = |snapshot: &str| {
+            load_snapshot(
+                &client,
+                snapshot,
+                &teams,
+                args.ascii_names,
+                &config.timeouts,
+                args.verbose,
+            )
+        };
+        let (before, after) = (load(before), load(after));
+        // Every game is followed so that period changes show up too
+        let watch = WatchOptions {
+            followed_teams: after
+                .iter()
+                .flatten()
+                .flat_map(|game| vec![game.home.to_string(), game.away.to_string()])
+                .collect(),
+            alert_overtime: true,
+        };
+        let events = changes(&before, &after, &watch);
+        print_output(
+            output_format(scores),
+            &events,
+            &event_lines(&events),
+            events_table(&events),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    if let Some(Command::Player { name, days }) = &args.command {
+        print_player(
+            &client,
+            name,
+            *days,
+            &teams,
+            args.ascii_names,
+            &config.timeouts,
+            args.verbose,
+            output_format(scores),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    if let Some(Command::Schedule) = &args.command {
+        print_schedule(
+            &client,
+            &teams,
+            args.ascii_names,
+            &config.timeouts,
+            args.verbose,
+            output_format(scores),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    if let Some(Command::Versus { team, opponent }) = &args.command {
+        let teams: Result<Vec<String>, String> = vec![team, opponent]
+            .into_iter()
+            .map(|name| resolve_team_name(name))
+            .collect();
+        let teams = teams.unwrap_or_else(|e| {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        });
+        print_versus(
+            &client,
+            &teams,
+            args.ascii_names,
+            &config.timeouts,
+            args.verbose,
+            output_format(scores),
+            &terminal,
+            &options,
+        );
+        return;
+    }
+
+    let matchup = match &args.command {
+        Some(Command::Game { matchup }) => Some(parse_matchup(matchup).unwrap_or_else(|e| {
+            println!("ERROR: {}", e);
+            process::exit(1);
+        })),
+        _ => None,
+    };
+
+    let range = date_range(scores, Local::now().date_naive()).unwrap_or_else(|e| {
+        println!("ERROR: {}", e);
+        process::exit(1);
+    });
+    if scores.raw && matches!(args.command, Some(Command::Scores(_)) | None) {
+        let response = match &args.mock {
+            Some(path) => Ok(read_mock_json(path)),
+            None => fetch_raw_scores(&client, &scores.dates, range),
+        };
+        match response {
+            Ok(json) => println!("{:#}", json),
+            Err(err) => handle_request_error(err),
+        }
+        return;
+    }
+
+    // Watch mode and the TUI fetch the same finished games over and over
+    let archived = RefCell::new(HashSet::new());
+    let load_sections = |teams: &[String]| -> Result<Vec<Section>, ClientError> {
+        let days = match &args.mock {
+            Some(path) => read_mock(path),
+            None => fetch_games(
+                &client,
+                &scores.dates,
+                range,
+                &config.timeouts,
+                args.verbose,
+            )?,
+        };
+        Ok(days
+            .into_iter()
+            .map(|(title, scores)| {
+                let games = parse_games(scores);
+                // Saved responses aren't results of their own to keep
+                if args.mock.is_none() && config.history.archive() {
+                    if let Some(path) = config.history.path() {
+                        if let Err(e) =
+                            archive_results(&path, &title, &games, &mut archived.borrow_mut())
+                        {
+                            eprintln!("WARNING: {}", e);
+                        }
+                    }
+                }
+                Section {
+                    title,
+                    games: select_games(games, teams, args.ascii_names),
+                }
+            })
+            .collect())
+    };
+
+    if scores.watch && matches!(args.command, Some(Command::Scores(_)) | None) {
+        watch_games(
+            load_sections,
+            Settings { teams, options },
+            reload_settings,
+            scores.interval.map(StdDuration::from_secs),
+            &terminal,
+        );
+    }
+
+    let keymap = match &args.command {
+        Some(Command::Tui) => {
+            if !terminal.is_terminal {
+                println!("ERROR: 235 tui needs a terminal");
+                process::exit(1);
+            }
+            Some(
+                tui::Keymap::with_keys(&config.tui.keys).unwrap_or_else(|e| {
+                    println!("ERROR: {}", e);
+                    process::exit(1);
+                }),
+            )
+        }
+        _ => None,
+    };
+
+    let sections = match load_sections(&teams) {
+        Ok(sections) => sections,
         Err(err) => {
+            if let Some(Command::NotifyOnce { .. }) = &args.command {
+                ping_healthcheck(&config.notify, false, config.timeouts.webhook());
+            }
             handle_request_error(err);
+            return;
         }
     };
+
+    match args.command {
+        // Notifications follow a single game day, the first one requested
+        Some(Command::NotifyOnce {
+            state,
+            log,
+            alert_ot,
+        }) => {
+            // A range without games still runs so the healthcheck is pinged
+            let empty = Section {
+                title: String::new(),
+                games: Vec::new(),
+            };
+            let section = sections.first().unwrap_or(&empty);
+            let day = Some(section.title.as_str()).filter(|title| !title.is_empty());
+            let state_path = state.or_else(default_state_path).unwrap_or_else(|| {
+                println!("ERROR: Can't find the home directory, pass --state");
+                process::exit(1);
+            });
+            let log_path = log.or_else(default_log_path).unwrap_or_else(|| {
+                println!("ERROR: Can't find the home directory, pass --log");
+                process::exit(1);
+            });
+            let result = notify_once(
+                &section.games,
+                day,
+                &state_path,
+                &log_path,
+                &WatchOptions {
+                    followed_teams: options.favorite_teams.clone(),
+                    alert_overtime: alert_ot,
+                },
+                &config.notify,
+                &config.timeouts,
+                args.verbose,
+                &options,
+            );
+            ping_healthcheck(&config.notify, result.is_ok(), config.timeouts.webhook());
+            if let Err(e) = result {
+                println!("ERROR: {}", e);
+                process::exit(1);
+            }
+        }
+        Some(Command::Export { .. })
+        | Some(Command::Roster { .. })
+        | Some(Command::Teams)
+        | Some(Command::History { .. })
+        | Some(Command::Standings { .. })
+        | Some(Command::Versus { .. })
+        | Some(Command::Playoffs { .. })
+        | Some(Command::Schedule)
+        | Some(Command::Player { .. })
+        | Some(Command::Diff { .. })
+        | Some(Command::Events { .. })
+        | Some(Command::Examples)
+        | Some(Command::Config(_))
+        | Some(Command::Secret(_)) => unreachable!("handled before fetching"),
+        Some(Command::Tui) => {
+            let provider = TuiProvider {
+                load_sections,
+                reload_settings,
+                watcher: RefCell::new(ConfigWatcher::new()),
+                teams: RefCell::new(teams),
+                client: &client,
+                ascii: args.ascii_names,
+                timeouts: &config.timeouts,
+                verbose: args.verbose,
+            };
+            let interval = scores.interval.map(StdDuration::from_secs);
+            let keymap = keymap.expect("keymap is read before fetching");
+            let mouse = config.tui.mouse();
+            if let Err(e) = tui::run(sections, &provider, interval, &options, keymap, mouse) {
+                println!("ERROR: {}", e);
+                process::exit(1);
+            }
+        }
+        Some(Command::Game { .. }) => {
+            let teams = matchup.expect("matchup is parsed before fetching");
+            print_game(sections, &teams, output_format(scores), &terminal, &options);
+        }
+        Some(Command::Scores(_)) | None => {
+            print_scores(&sections, output_format(scores), &terminal, &options)
+        }
+    }
+}
+
+fn print_scores(
+    sections: &[Section],
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    match format {
+        OutputFormat::Page => print_games(sections, terminal, options),
+        OutputFormat::Json => print_json(sections),
+        OutputFormat::Csv => print!("{}", goals_csv(sections)),
+        OutputFormat::Markdown => print!("{}", games_markdown(sections, options)),
+        OutputFormat::Tsv => print!("{}", games_tsv(sections)),
+        OutputFormat::Html => print!("{}", render_sections_with(&HtmlRenderer, sections, options)),
+    }
+}
+
+/// Prints the box score of the game between `teams`. Structured formats
+/// get the game the same way as the scores.
+fn print_game(
+    mut sections: Vec<Section>,
+    teams: &(String, String),
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    let plays = |game: &Game| {
+        (game.home == teams.0 && game.away == teams.1)
+            || (game.home == teams.1 && game.away == teams.0)
+    };
+    for section in sections.iter_mut() {
+        section
+            .games
+            .retain(|game| game.as_ref().is_some_and(plays));
+    }
+    sections.retain(|section| !section.games.is_empty());
+    if sections.is_empty() {
+        println!("ERROR: No game between {} and {}", teams.0, teams.1);
+        process::exit(1);
+    }
+
+    let lines: Vec<StyledLine> = sections
+        .iter()
+        .flat_map(day_games)
+        .enumerate()
+        .flat_map(|(index, game)| {
+            let gap = (index > 0).then(StyledLine::default);
+            gap.into_iter().chain(boxscore_lines(game, options))
+        })
+        .collect();
+    match format {
+        OutputFormat::Page => print!("{}", terminal.renderer(options).render_lines(&lines)),
+        OutputFormat::Html => print!("{}", HtmlRenderer.render_lines(&lines)),
+        _ => print_scores(&sections, format, terminal, options),
+    }
+}
+
+/// Prints the division and conference tables, or only the one named
+/// `table` when given
+fn print_standings(
+    client: &ScoreClient,
+    table: Option<&Group>,
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    match fetch_standings(client) {
+        Ok(standings) => {
+            let mut tables = [parse_standings(&standings), parse_conferences(&standings)].concat();
+            if let Some(table) = table {
+                tables.retain(|division| division.name == table.name);
+            }
+            print_output(
+                format,
+                &tables,
+                &standings_lines(&tables, options),
+                standings_table(&tables),
+                terminal,
+                options,
+            );
+        }
+        Err(err) => handle_request_error(err),
+    }
+}
+
+#[tokio::main]
+async fn fetch_standings(client: &ScoreClient) -> Result<StandingsResponse, ClientError> {
+    client.standings().await
+}
+
+fn print_roster(
+    client: &ScoreClient,
+    team: &str,
+    ascii: bool,
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    let roster = match fetch_roster(client, team) {
+        Ok(roster) => roster,
+        Err(err) => {
+            handle_request_error(err);
+            return;
+        }
+    };
+    let team = resolve_team(team).expect("team was resolved from the same table");
+    let mut players = parse_roster(&roster);
+    if ascii {
+        ascii_roster_names(&mut players);
+    }
+    print_output(
+        format,
+        &players,
+        &roster_lines(team, &players),
+        roster_table(&players),
+        terminal,
+        options,
+    );
+}
+
+#[tokio::main]
+async fn fetch_roster(client: &ScoreClient, team: &str) -> Result<RosterResponse, ClientError> {
+    client.roster(team).await
+}
+
+fn print_bracket(
+    client: &ScoreClient,
+    season: i32,
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    match fetch_bracket(client, season + 1) {
+        Ok(bracket) => {
+            let series = parse_bracket(&bracket);
+            print_output(
+                format,
+                &series,
+                &bracket_lines(&series),
+                bracket_table(&series),
+                terminal,
+                options,
+            );
+        }
+        Err(err) => handle_request_error(err),
+    }
+}
+
+#[tokio::main]
+async fn fetch_bracket(
+    client: &ScoreClient,
+    year: i32,
+) -> Result<PlayoffBracketResponse, ClientError> {
+    client.playoff_bracket(year).await
+}
+
+/// Fetches this season up to today and prints the games between the two
+/// `teams`
+#[allow(clippy::too_many_arguments)]
+fn print_versus(
+    client: &ScoreClient,
+    teams: &[String],
+    ascii: bool,
+    timeouts: &Timeouts,
+    verbose: bool,
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    let today = Local::now().date_naive();
+    let (start, end) = season_dates(season_of(today)).expect("the current season has dates");
+    let days = match fetch_games(
+        client,
+        &[],
+        Some((start, end.min(today))),
+        timeouts,
+        verbose,
+    ) {
+        Ok(days) => days,
+        Err(err) => {
+            handle_request_error(err);
+            return;
+        }
+    };
+    let sections: Vec<Section> = days
+        .into_iter()
+        .map(|(title, scores)| Section {
+            title,
+            games: parse_day(scores, teams, ascii),
+        })
+        .collect();
+    let series = season_series(&sections, &teams[0], &teams[1]);
+    print_output(
+        format,
+        &series,
+        &versus_lines(&series, options),
+        versus_table(&series),
+        terminal,
+        options,
+    );
+}
+
+/// Fetches the last `days` days and prints the points of the player
+#[allow(clippy::too_many_arguments)]
+fn print_player(
+    client: &ScoreClient,
+    name: &str,
+    days: u32,
+    teams: &[String],
+    ascii: bool,
+    timeouts: &Timeouts,
+    verbose: bool,
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    let today = Local::now().date_naive();
+    let start = today - Duration::days(i64::from(days) - 1);
+    let days = match fetch_games(client, &[], Some((start, today)), timeouts, verbose) {
+        Ok(days) => days,
+        Err(err) => {
+            handle_request_error(err);
+            return;
+        }
+    };
+    let sections: Vec<Section> = days
+        .into_iter()
+        .map(|(title, scores)| Section {
+            title,
+            games: parse_day(scores, teams, ascii),
+        })
+        .collect();
+    let player = player_points(&sections, name);
+    print_output(
+        format,
+        &player,
+        &player_lines(&player, options),
+        player_table(&player),
+        terminal,
+        options,
+    );
+}
+
+/// Fetches today and tomorrow and prints the games that haven't started
+#[allow(clippy::too_many_arguments)]
+fn print_schedule(
+    client: &ScoreClient,
+    teams: &[String],
+    ascii: bool,
+    timeouts: &Timeouts,
+    verbose: bool,
+    format: OutputFormat,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    let sections = match load_schedule(client, teams, ascii, timeouts, verbose) {
+        Ok(sections) => sections,
+        Err(err) => {
+            handle_request_error(err);
+            return;
+        }
+    };
+    let games = if options.deterministic {
+        upcoming_games(&sections, &Utc)
+    } else {
+        upcoming_games(&sections, &Local)
+    };
+    print_output(
+        format,
+        &games,
+        &schedule_lines(&games, options),
+        schedule_table(&games),
+        terminal,
+        options,
+    );
+}
+
+/// Games of today and tomorrow, which have the next games to be played
+fn load_schedule(
+    client: &ScoreClient,
+    teams: &[String],
+    ascii: bool,
+    timeouts: &Timeouts,
+    verbose: bool,
+) -> Result<Vec<Section>, ClientError> {
+    let today = Local::now().date_naive();
+    let days = fetch_games(
+        client,
+        &[],
+        Some((today, today + Duration::days(1))),
+        timeouts,
+        verbose,
+    )?;
+    Ok(days
+        .into_iter()
+        .map(|(title, scores)| Section {
+            title,
+            games: parse_day(scores, teams, ascii),
+        })
+        .collect())
+}
+
+/// Data of the `235 tui` tabs, fetched with the same client and flags as
+/// the scores page
+struct TuiProvider<'a, F, R> {
+    load_sections: F,
+    reload_settings: R,
+    watcher: RefCell<ConfigWatcher>,
+    /// Teams of `--team`, which can be groups of the config
+    teams: RefCell<Vec<String>>,
+    client: &'a ScoreClient,
+    ascii: bool,
+    timeouts: &'a Timeouts,
+    verbose: bool,
+}
+
+impl<F, R> tui::Provider for TuiProvider<'_, F, R>
+where
+    F: Fn(&[String]) -> Result<Vec<Section>, ClientError>,
+    R: Fn() -> Result<Settings, String>,
+{
+    fn scores(&self) -> Result<Vec<Section>, ClientError> {
+        (self.load_sections)(&self.teams.borrow())
+    }
+
+    fn standings(&self) -> Result<Vec<Division>, ClientError> {
+        let standings = fetch_standings(self.client)?;
+        Ok([parse_standings(&standings), parse_conferences(&standings)].concat())
+    }
+
+    fn schedule(&self) -> Result<Vec<Section>, ClientError> {
+        load_schedule(
+            self.client,
+            &self.teams.borrow(),
+            self.ascii,
+            self.timeouts,
+            self.verbose,
+        )
+    }
+
+    fn reload(&self) -> Option<Result<RenderOptions, String>> {
+        if !self.watcher.borrow_mut().changed() {
+            return None;
+        }
+        Some((self.reload_settings)().map(|settings| {
+            *self.teams.borrow_mut() = settings.teams;
+            settings.options
+        }))
+    }
+}
+
+/// Writes the output of a subcommand in the format of `--format` or
+/// `--json`: the page, the data as JSON or a row per item
+fn print_output<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    data: &T,
+    lines: &[StyledLine],
+    table: Table,
+    terminal: &TerminalInfo,
+    options: &RenderOptions,
+) {
+    match format {
+        OutputFormat::Page => print!("{}", terminal.renderer(options).render_lines(lines)),
+        OutputFormat::Html => print!("{}", HtmlRenderer.render_lines(lines)),
+        OutputFormat::Csv => print!("{}", table.to_csv()),
+        OutputFormat::Tsv => print!("{}", table.to_tsv()),
+        OutputFormat::Markdown => print!("{}", table.to_markdown()),
+        OutputFormat::Json => match serde_json::to_string_pretty(data) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                println!("ERROR: Can't write the output as JSON: {}", e);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Fetches the season and writes its completed games as JSON Lines
+fn export_season(
+    client: &ScoreClient,
+    season: i32,
+    out: Option<&Path>,
+    teams: &[String],
+    ascii: bool,
+    timeouts: &Timeouts,
+    verbose: bool,
+) {
+    let days = match fetch_season(client, season, timeouts, verbose) {
+        Ok(days) => days,
+        Err(err) => {
+            handle_request_error(err);
+            return;
+        }
+    };
+    let records: Vec<_> = days
+        .into_iter()
+        .flat_map(|(date, scores)| game_records(&date, &parse_day(scores, teams, ascii)))
+        .collect();
+    let jsonl = to_jsonl(&records).unwrap_or_else(|e| {
+        println!("ERROR: {}", e);
+        process::exit(1);
+    });
+
+    match out {
+        Some(path) => {
+            if let Err(e) = fs::write(path, jsonl) {
+                println!("ERROR: Can't write {}: {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+        None => print!("{}", jsonl),
+    }
+}
+
+/// Fetches every day of a season. Each response is paired with its date
+/// in YYYY-MM-DD format.
+#[tokio::main]
+async fn fetch_season(
+    client: &ScoreClient,
+    season: i32,
+    timeouts: &Timeouts,
+    verbose: bool,
+) -> Result<Vec<(String, APIResponse)>, ClientError> {
+    let (start, last) = season_dates(season).unwrap_or_else(|| {
+        println!("ERROR: Invalid season {}", season);
+        process::exit(1);
+    });
+    fetch_range(client, start, last, timeouts, verbose).await
+}
+
+/// Fetches every day from `start` to `last` a month at a time to keep the
+/// responses reasonably small. Days without games aren't included.
+async fn fetch_range(
+    client: &ScoreClient,
+    mut start: NaiveDate,
+    last: NaiveDate,
+    timeouts: &Timeouts,
+    verbose: bool,
+) -> Result<Vec<(String, APIResponse)>, ClientError> {
+    let mut days = Vec::new();
+    while start <= last {
+        let end = (start + Duration::days(30)).min(last);
+        let started = Instant::now();
+        let scores = client.date_range(start, end).await?;
+        let operation = format!("Fetching scores from {} to {}", start, end);
+        warn_if_slow(verbose, &operation, started.elapsed(), timeouts.api());
+        for day in scores {
+            let date = day.date.as_ref().map(|date| date.raw.to_string());
+            days.push((date.unwrap_or_default(), day));
+        }
+        start = end + Duration::days(1);
+    }
+    Ok(days)
+}
+
+fn spacing(args: &ScoreArgs, config: &Config) -> usize {
+    if args.tight {
+        0
+    } else {
+        config.spacing.unwrap_or(1)
+    }
+}
+
+fn run_config_command(command: &ConfigCommand) -> Result<(), ConfigError> {
+    let (config_file, highlights_file) = match (config_path(), highlights_path()) {
+        (Some(config), Some(highlights)) => (config, highlights),
+        _ => {
+            return Err(ConfigError::Bundle(String::from(
+                "can't find the home directory",
+            )))
+        }
+    };
+    let current = match fs::read_to_string(&config_file) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(ConfigError::Read(config_file, e)),
+    };
+
+    match command {
+        ConfigCommand::Export => {
+            let highlights = read_highlight_config().unwrap_or_default();
+            print!("{}", export_bundle(&current, &highlights)?);
+        }
+        ConfigCommand::Import { file } => {
+            let bundle =
+                fs::read_to_string(file).map_err(|e| ConfigError::Read(file.to_path_buf(), e))?;
+            let (contents, highlights) = import_bundle(&bundle, &current)?;
+            fs::write(&config_file, contents).map_err(|e| ConfigError::Write(config_file, e))?;
+            fs::write(&highlights_file, highlights.join("\n") + "\n")
+                .map_err(|e| ConfigError::Write(highlights_file, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_secret_command(command: &SecretCommand) -> Result<(), String> {
+    match command {
+        SecretCommand::Set { name } => {
+            eprint!("Value for {}: ", name);
+            let mut value = String::new();
+            std::io::stdin()
+                .read_line(&mut value)
+                .map_err(|e| e.to_string())?;
+            let value = value.trim_end_matches(&['\r', '\n'][..]);
+            if value.is_empty() {
+                return Err(format!("No value given for secret '{}'", name));
+            }
+            set_secret(name, value).map_err(|e| e.to_string())
+        }
+        SecretCommand::Delete { name } => delete_secret(name).map_err(|e| e.to_string()),
+    }
 }
 
 fn read_highlight_config() -> Result<Vec<String>, StdError> {
-    let mut config_file = home_dir().unwrap();
-    config_file.push(".235.config");
+    let config_file = highlights_path()
+        .ok_or_else(|| StdError::new(ErrorKind::NotFound, "home directory not found"))?;
 
     let mut file = File::open(config_file.as_path())?;
     let mut contents = String::new();
@@ -129,989 +1449,707 @@ fn read_highlight_config() -> Result<Vec<String>, StdError> {
     parse_highlight_config(contents)
 }
 
+/// Hint for when --highlight, --stats or --only-highlights has no players
+/// to work with
+fn missing_highlights_message() -> String {
+    let path = highlights_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| String::from("$HOME/.235.config"));
+    format!(
+        "No highlighted players found in {}, add their last names there one per line",
+        path
+    )
+}
+
 fn parse_highlight_config(config: String) -> Result<Vec<String>, StdError> {
     let highlights: Vec<String> = config
         .lines()
         .map(str::to_string)
-        .filter(|s| s != "")
+        .filter(|s| !s.is_empty())
         .collect();
 
     Ok(highlights)
 }
 
-fn handle_request_error(e: reqwest::Error) {
-    if e.is_connect() {
-        println!("ERROR: Can't connect to the API. It might be because your Internet connection is down.");
-        process::exit(1);
-    } else if e.is_timeout() {
-        println!("ERROR: API timed out. Try again later.");
-        process::exit(1);
-    } else if e.is_decode() {
-        println!("ERROR: API returned malformed data. Try again later.");
-        println!("{:?}", e);
-        process::exit(1);
-    } else {
-        println!("ERROR: Unknown error.");
-        println!("{:?}", e);
-        process::exit(1);
+/// Last names from `--players`, separated by commas
+fn parse_player_list(players: &str) -> Vec<String> {
+    players
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn handle_request_error(e: ClientError) {
+    println!("{}", request_error_message(&e));
+    process::exit(1);
+}
+
+/// What went wrong with a request, with the details of unexpected errors
+/// on a line of their own
+fn request_error_message(e: &ClientError) -> String {
+    match e {
+        ClientError::Connect(_) => String::from(
+            "ERROR: Can't connect to the API. It might be because your Internet connection is down.",
+        ),
+        ClientError::Timeout(_) => String::from("ERROR: API timed out. Try again later."),
+        ClientError::Status(e) => {
+            let message = match e.status() {
+                Some(status) if status.as_u16() == 429 => {
+                    "ERROR: API is getting too many requests. Try again later."
+                }
+                _ => "ERROR: API returned an error. Try again later.",
+            };
+            format!("{}\n{:?}", message, e)
+        }
+        ClientError::Decode(e) => format!(
+            "ERROR: API returned malformed data. Try again later.\n{:?}",
+            e
+        ),
+        ClientError::Other(e) => format!("ERROR: Unknown error.\n{:?}", e),
     }
 }
 
-fn translate_team_name(abbr: &str) -> String {
-    let city = match abbr {
-        "BOS" => "Boston",
-        "BUF" => "Buffalo",
-        "NJD" => "New Jersey",
-        "NYI" => "NY Islanders", // Islanders is named like this to differentiate two New York teams
-        "NYR" => "NY Rangers",   // Rangers is named like this to differentiate two New York teams
-        "PHI" => "Philadelphia",
-        "PIT" => "Pittsburgh",
-        "WSH" => "Washington",
-        "CAR" => "Carolina",
-        "CHI" => "Chicago",
-        "CBJ" => "Columbus",
-        "DAL" => "Dallas",
-        "DET" => "Detroit",
-        "FLA" => "Florida",
-        "NSH" => "Nashville",
-        "TBL" => "Tampa Bay",
-        "ANA" => "Anaheim",
-        "COL" => "Colorado",
-        "LAK" => "Los Angeles",
-        "MIN" => "Minnesota",
-        "SJS" => "San Jose",
-        "STL" => "St. Louis",
-        "VGK" => "Vegas",
-        "CGY" => "Calgary",
-        "EDM" => "Edmonton",
-        "MTL" => "Montreal",
-        "OTT" => "Ottawa",
-        "TOR" => "Toronto",
-        "VAN" => "Vancouver",
-        "WPG" => "Winnipeg",
-        "SEA" => "Seattle",
-        "UTA" => "Utah",
-        _ => "[unknown]",
+/// What the flags and the config files make of the page
+struct Settings {
+    /// Teams whose games are shown, all games when empty
+    teams: Vec<String>,
+    options: RenderOptions,
+}
+
+/// Combines the flags with the config and the highlighted players. Read
+/// again when the files change in watch mode and the TUI.
+fn load_settings(args: &Cli, scores: &ScoreArgs, config: &Config) -> Result<Settings, String> {
+    let mut highlights = match &scores.players {
+        Some(players) => parse_player_list(players),
+        None => read_highlight_config().unwrap_or_default(),
+    };
+    highlights.extend(scores.highlight_players.iter().cloned());
+    if args.ascii_names {
+        // Highlighted names have to match the transliterated scorers
+        highlights = highlights.iter().map(|name| transliterate(name)).collect();
+    }
+
+    let teams = resolve_teams(&scores.teams, config)?;
+    let favorites = if config.favorites.is_empty() {
+        teams.clone()
+    } else {
+        resolve_teams(&config.favorites, config)?
+    };
+    let team_names = resolve_team_names(config)?;
+    let layout = match scores.layout {
+        Some(layout) => layout,
+        None => config.layout.layout()?,
     };
 
-    String::from(city)
+    let options = RenderOptions::default()
+        // Using an inverse here because default is colors enabled
+        // and I want to keep the API easier to read down the line,
+        // hence colors need to be enabled rather than disabled
+        .colors(!args.nocolors && !args.deterministic)
+        .highlights(highlights)
+        .show_stats(scores.stats)
+        .stats_style(config.stats.style())
+        .show_opponents(scores.opponents)
+        .show_highlights(scores.highlight || !scores.highlight_players.is_empty())
+        .show_duration(scores.duration)
+        .show_venue(scores.venue)
+        .show_broadcasts(scores.broadcasts)
+        .show_close_games(scores.close_games)
+        .outcome_colors(scores.outcome_colors)
+        .show_notable(scores.notable)
+        .collapse_others(scores.collapse_others)
+        .only_highlights(scores.only_highlights)
+        .favorite_teams(favorites)
+        .initials(scores.initials)
+        .team_names_style(team_names_style(scores))
+        .goal_order(scores.goal_order)
+        .layout(layout)
+        .spacing(spacing(scores, config))
+        .language(args.lang)
+        .team_names(team_names)
+        .deterministic(args.deterministic);
+    Ok(Settings { teams, options })
 }
 
-#[tokio::main]
-async fn fetch_games() -> Result<APIResponse, Error> {
-    let request_url = String::from("https://nhl-score-api.herokuapp.com/api/scores/latest");
-    let response = reqwest::get(&request_url).await?;
-    let scores: APIResponse = response.json().await?;
+/// Turns the `--team` arguments into team abbreviations.
+/// Groups defined in the config are expanded to their teams.
+fn resolve_teams(names: &[String], config: &Config) -> Result<Vec<String>, String> {
+    let mut teams = Vec::new();
+    for name in names {
+        match config.teams.get(name) {
+            Some(group) => {
+                for name in group {
+                    teams.push(resolve_team_name(name)?);
+                }
+            }
+            None => teams.push(resolve_team_name(name)?),
+        }
+    }
+    Ok(teams)
+}
+
+/// The `[team_names]` of the config by team abbreviation
+fn resolve_team_names(config: &Config) -> Result<HashMap<String, String>, String> {
+    config
+        .team_names
+        .iter()
+        .map(|(team, name)| Ok((resolve_team_name(team)?, name.clone())))
+        .collect()
+}
+
+/// Finds the division or conference given to `235 standings`, `kind`
+/// names which one for the error
+fn resolve_group(
+    kind: &str,
+    name: &str,
+    match_group: fn(&str) -> NameMatch<&'static Group>,
+) -> Result<&'static Group, String> {
+    match match_group(name) {
+        NameMatch::Found(group) => Ok(group),
+        NameMatch::Suggestion(group) => Err(format!(
+            "Unknown {} '{}', did you mean {} ({})?",
+            kind, name, group.abbreviation, group.name
+        )),
+        NameMatch::Unknown => Err(format!("Unknown {} '{}'", kind, name)),
+    }
+}
+
+fn resolve_team_name(name: &str) -> Result<String, String> {
+    match match_team(name) {
+        TeamMatch::Found(team) => Ok(team.abbreviation.to_string()),
+        TeamMatch::Suggestion(team) => Err(format!(
+            "Unknown team '{}', did you mean {} ({})?",
+            name, team.abbreviation, team.name
+        )),
+        TeamMatch::Unknown => Err(format!("Unknown team '{}'", name)),
+    }
+}
 
-    Ok(scores)
+/// Teams of a `235 game` matchup like "PIT-TOR"
+fn parse_matchup(matchup: &str) -> Result<(String, String), String> {
+    let (team, opponent) = matchup
+        .split_once('-')
+        .ok_or_else(|| format!("invalid game '{}', use two teams like PIT-TOR", matchup))?;
+    Ok((
+        resolve_team_name(team.trim())?,
+        resolve_team_name(opponent.trim())?,
+    ))
 }
 
-/// Transforms a JSON structure of multiple games into
-/// a vector of Option<Game> so they can be processed by
-/// other parts of the application
-fn parse_games(scores: APIResponse) -> Vec<Option<Game>> {
-    let games = scores.games;
+/// Games of one API response, limited to `teams` when given
+fn parse_day(scores: APIResponse, teams: &[String], ascii: bool) -> Vec<Option<Game>> {
+    select_games(parse_games(scores), teams, ascii)
+}
 
+/// The games of `teams` with names transliterated if asked
+fn select_games(games: Vec<Option<Game>>, teams: &[String], ascii: bool) -> Vec<Option<Game>> {
+    let mut games = filter_games(games, teams);
+    if ascii {
+        games.iter_mut().flatten().for_each(ascii_names);
+    }
     games
-        .iter()
-        .map(|game| parse_game(game))
-        .collect::<Vec<Option<Game>>>()
 }
 
-/// Handler function to print multiple Games
-fn print_games(games: Vec<Option<Game>>, highlights: &[String], options: &Options) {
-    match games.len() {
-        0 => println!("No games today."),
-        _ => {
-            games.into_iter().for_each(|game| match game {
-                Some(game) => print_game(&game, &highlights, &options),
-                None => (),
-            });
-        }
+/// Keeps the games where either team is one of `teams`. No teams means no filtering.
+fn filter_games(games: Vec<Option<Game>>, teams: &[String]) -> Vec<Option<Game>> {
+    if teams.is_empty() {
+        return games;
     }
+    games
+        .into_iter()
+        .filter(|game| {
+            game.as_ref()
+                .is_some_and(|game| teams.contains(&game.home) || teams.contains(&game.away))
+        })
+        .collect()
 }
 
-/// Transforms a combination of min (between 0 and 19) and
-/// period ("OT", "SO" or number > 0 in number form)
-/// into a numeric minute given 20 minute periods
-fn format_minute(min: u64, period: &str) -> u64 {
-    if period == "OT" {
-        60 + min
-    } else {
-        let period: u64 = period.parse().unwrap();
-        20 * (period - 1) + min
+fn parse_rate(rate: &str) -> Result<f64, String> {
+    match rate.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate.is_finite() => Ok(rate),
+        _ => Err(format!("invalid rate '{}', use a positive number", rate)),
     }
 }
 
-/// Returns true if the goal scored was done in
-/// overtime or in a shootout
-fn is_special(goal: &GoalResponse) -> bool {
-    match goal.period.parse::<u64>() {
-        Ok(period) => period >= 4,
-        Err(_) => true,
+fn parse_days(days: &str) -> Result<u32, String> {
+    match days.parse::<u32>() {
+        Ok(days) if (1..=MAX_DAYS).contains(&days) => Ok(days),
+        _ => Err(format!(
+            "invalid number of days '{}', use 1-{}",
+            days, MAX_DAYS
+        )),
     }
 }
 
-/// Transforms a JSON structure of an individual game into a Game
-fn parse_game(game_json: &GameResponse) -> Option<Game> {
-    let home_team = &game_json.teams.home.abbreviation;
-    let away_team = &game_json.teams.away.abbreviation;
-
-    let home_score = &game_json.scores[home_team];
-    let away_score = &game_json.scores[away_team];
+fn parse_interval(seconds: &str) -> Result<u64, String> {
+    match seconds.parse::<u64>() {
+        Ok(seconds) if seconds >= MIN_INTERVAL => Ok(seconds),
+        _ => Err(format!(
+            "invalid interval '{}', use at least {} seconds",
+            seconds, MIN_INTERVAL
+        )),
+    }
+}
 
-    let empty_vec: &Vec<GoalResponse> = &Vec::<GoalResponse>::new();
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{}', use YYYY-MM-DD", date))
+}
 
-    let all_goals = match &game_json.goals {
-        Some(goals) => goals,
-        None => &empty_vec,
-    };
+/// A full RFC 3339 time, or midnight UTC of a YYYY-MM-DD date
+fn parse_since(since: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(since)
+        .map(|time| time.with_timezone(&Utc))
+        .or_else(|_| parse_date(since).map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc()))
+        .map_err(|_| {
+            format!(
+                "invalid time '{}', use YYYY-MM-DD or RFC 3339 like 2024-10-16T19:00:00Z",
+                since
+            )
+        })
+}
 
-    let special = match all_goals.last() {
-        None => "",
-        Some(last_goal) => {
-            let period = &last_goal.period;
-            match period.as_str() {
-                "1" | "2" | "3" => "",
-                "OT" => "ot",
-                "SO" => "so",
-                // The default case is "ot" because the only ones
-                // with chars should be OT and SO and this matches
-                // Any digit larger than 3.
-                // If other periods occur, new arms should be added
-                _ => "ot",
-            }
+fn print_event_log(since: Option<DateTime<Utc>>, log: Option<&Path>) -> Result<(), NotifyError> {
+    let path = log
+        .map(Path::to_path_buf)
+        .or_else(default_log_path)
+        .unwrap_or_else(|| {
+            println!("ERROR: Can't find the home directory, pass --log");
+            process::exit(1);
+        });
+    for logged in read_event_log(&path)? {
+        if since.is_none_or(|since| logged.is_since(since)) {
+            let line = serde_json::to_string(&logged)
+                .map_err(|e| NotifyError::InvalidState(path.clone(), e))?;
+            println!("{}", line);
         }
-    };
+    }
+    Ok(())
+}
 
-    let goals: &Vec<GoalResponse> = all_goals;
+/// The archive of `235 history`, a missing file is an empty archive
+fn read_archive(path: &Path) -> Result<Vec<GameRecord>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_archive(&contents)
+            .map_err(|e| format!("Invalid archive {}: {}", path.display(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("Can't read {}: {}", path.display(), e)),
+    }
+}
 
-    let goals = goals
+/// Appends the finished games of a day that aren't in the archive yet,
+/// skipping the file for games this run already archived
+fn archive_results(
+    path: &Path,
+    date: &str,
+    games: &[Option<Game>],
+    archived: &mut HashSet<String>,
+) -> Result<(), String> {
+    let records: Vec<GameRecord> = game_records(date, games)
         .into_iter()
-        .map(|goal| {
-            let minute = match goal.period.as_str() {
-                "SO" => SHOOTOUT_MINUTE,
-                _ => format_minute(goal.min.unwrap(), &goal.period),
-            };
+        .filter(|record| !archived.contains(&record.id))
+        .collect();
+    if records.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<String> = records.iter().map(|record| record.id.clone()).collect();
+    // Another run could append the same games between the read and the write
+    let _lock =
+        FileLock::acquire(path).map_err(|e| format!("Can't lock {}: {}", path.display(), e))?;
+    let records = unarchived(&read_archive(path)?, records);
+    if !records.is_empty() {
+        let jsonl = to_jsonl(&records).map_err(|e| e.to_string())?;
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(jsonl.as_bytes()))
+            .map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    }
+    archived.extend(ids);
+    Ok(())
+}
 
-            let scorer = extract_player(&goal.scorer.player, &goal.team);
-            let assists = &goal
-                .assists
-                .as_ref()
-                .unwrap_or(&Vec::new())
-                .iter()
-                .map(|assist| extract_player(&assist.player, &goal.team))
-                .collect::<Vec<Player>>();
-
-            return Goal {
-                scorer: scorer,
-                assists: assists.to_vec(),
-                minute: minute,
-                team: goal.team.replace("\"", ""),
-                special: is_special(goal),
-            };
-        })
-        .collect::<Vec<Goal>>();
-
-    let score = format!("{}-{}", home_score, away_score);
-    let game = Game {
-        home: String::from(home_team),
-        away: String::from(away_team),
-        score: score.to_owned(),
-        goals: goals,
-        status: String::from(&game_json.status.state),
-        special: String::from(special),
-        playoff_series: game_json.current_stats.playoff_series.clone(),
+/// The games of a `235 diff` snapshot, fetched for a date or read from a
+/// saved response
+fn load_snapshot(
+    client: &ScoreClient,
+    snapshot: &str,
+    teams: &[String],
+    ascii: bool,
+    timeouts: &Timeouts,
+    verbose: bool,
+) -> Vec<Option<Game>> {
+    let days = match NaiveDate::parse_from_str(snapshot, "%Y-%m-%d") {
+        Ok(date) => fetch_games(client, &[date], None, timeouts, verbose).unwrap_or_else(|err| {
+            handle_request_error(err);
+            process::exit(1);
+        }),
+        Err(_) => read_mock(Path::new(snapshot)),
     };
-
-    Some(game)
+    days.into_iter()
+        .flat_map(|(_, scores)| parse_day(scores, teams, ascii))
+        .collect()
 }
 
-fn extract_player(name: &str, team: &str) -> Player {
-    let name = name.split(" ").collect::<Vec<&str>>();
-    let first_name = name[0];
-    let last_name = name[1..name.len()].to_vec().join(" ");
-    Player {
-        first_name: String::from(first_name),
-        last_name: String::from(last_name),
-        team: String::from(team),
-    }
+/// Reads a saved API response for `--mock` as if it was the latest results
+fn read_mock(path: &Path) -> Vec<(String, APIResponse)> {
+    let scores: APIResponse = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            println!("ERROR: Can't read mock response {}: {}", path.display(), e);
+            process::exit(1);
+        });
+    let day = scores.date.as_ref().map(|date| date.raw.to_string());
+    vec![(day.unwrap_or_default(), scores)]
 }
 
-fn print_game(game: &Game, highlights: &[String], options: &Options) {
-    let home_scores: Vec<&Goal> = game
-        .goals
-        .iter()
-        .filter(|goal| {
-            goal.team == game.home
-                && (goal.minute != SHOOTOUT_MINUTE
-                    || goal.minute == SHOOTOUT_MINUTE && game.special == "ot")
-        })
-        .collect::<Vec<&Goal>>();
-    let away_scores: Vec<&Goal> = game
-        .goals
-        .iter()
-        .filter(|goal| {
-            goal.team == game.away
-                && (goal.minute != SHOOTOUT_MINUTE
-                    || goal.minute == SHOOTOUT_MINUTE && game.special == "ot")
+/// Reads a saved API response for `--raw --mock` without parsing it
+fn read_mock_json(path: &Path) -> serde_json::Value {
+    fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            println!("ERROR: Can't read mock response {}: {}", path.display(), e);
+            process::exit(1);
         })
-        .collect::<Vec<&Goal>>();
+}
 
-    let mut shootout_scorer = None;
+/// Most days `--days` fetches
+const MAX_DAYS: u32 = 31;
+/// Shortest --watch interval, to keep the load on the API reasonable
+const MIN_INTERVAL: u64 = 5;
+/// Moves the cursor to the top left corner without clearing anything
+const CURSOR_HOME: &str = "\x1B[H";
+/// Clears from the cursor to the end of the line
+const CLEAR_LINE: &str = "\x1B[K";
+/// Clears from the cursor to the end of the screen
+const CLEAR_BELOW: &str = "\x1B[J";
+
+/// First and last date to show from `--days` or `--from` and `--to`.
+/// `--from` alone runs up to today and `--to` alone shows just that day.
+fn date_range(
+    scores: &ScoreArgs,
+    today: NaiveDate,
+) -> Result<Option<(NaiveDate, NaiveDate)>, String> {
+    let range = match (scores.days, scores.from, scores.to) {
+        (Some(days), _, _) => (today - Duration::days(i64::from(days) - 1), today),
+        (None, Some(from), to) => (from, to.unwrap_or(today)),
+        (None, None, Some(to)) => (to, to),
+        (None, None, None) => return Ok(None),
+    };
+    if range.0 > range.1 {
+        return Err(format!("--from {} is after --to {}", range.0, range.1));
+    }
+    Ok(Some(range))
+}
 
-    if game.special == "so" {
-        shootout_scorer = Some(game.goals.iter().last().unwrap());
+/// Fetches the latest results, the results of each date or of every day
+/// in `range`. Each response is paired with its date in YYYY-MM-DD format.
+#[tokio::main]
+async fn fetch_games(
+    client: &ScoreClient,
+    dates: &[NaiveDate],
+    range: Option<(NaiveDate, NaiveDate)>,
+    timeouts: &Timeouts,
+    verbose: bool,
+) -> Result<Vec<(String, APIResponse)>, ClientError> {
+    if let Some((start, last)) = range {
+        return fetch_range(client, start, last, timeouts, verbose).await;
     }
 
-    // Print header
-    if atty::is(Stream::Stdout) && options.use_colors {
-        white!(
-            "{:<15} {:>2} {:<15} {:<2} ",
-            translate_team_name(&game.home[..]),
-            '-',
-            translate_team_name(&game.away[..]),
-            ""
-        );
-        if game.status == "LIVE" {
-            white_ln!("{:>6}", game.score);
-        } else if game.status == "FINAL" {
-            green_ln!("{:>6}", format!("{} {}", game.special, game.score));
-        } else if game.status == "POSTPONED" {
-            white_ln!("{:>6}", "POSTP.");
-        }
-    } else {
-        print!(
-            "{:<15} {:>2} {:<15} {:<2} ",
-            translate_team_name(&game.home[..]),
-            '-',
-            translate_team_name(&game.away[..]),
-            ""
+    if dates.is_empty() {
+        let started = Instant::now();
+        let scores = client.latest().await?;
+        warn_if_slow(
+            verbose,
+            "Fetching scores",
+            started.elapsed(),
+            timeouts.api(),
         );
-        if game.status == "LIVE" {
-            println!("{:>6}", game.score);
-        } else if game.status == "FINAL" {
-            println!("{:>6}", format!("{} {}", game.special, game.score));
-        } else if game.status == "POSTPONED" {
-            println!("{:>6}", "POSTP.");
-        }
+        let day = scores.date.as_ref().map(|date| date.raw.to_string());
+        return Ok(vec![(day.unwrap_or_default(), scores)]);
     }
 
-    // Print scores
-    let score_pairs = home_scores.iter().zip_longest(away_scores.iter());
-    for pair in score_pairs {
-        match pair {
-            Both(home, away) => print_both_goals(home, away, highlights, &options),
-            Left(home) => print_home_goal(home, highlights, &options),
-            Right(away) => print_away_goal(away, highlights, &options),
-        }
+    let mut days = Vec::new();
+    for date in dates {
+        let started = Instant::now();
+        let scores = client.for_date(*date).await?;
+        let operation = format!("Fetching scores of {}", date);
+        warn_if_slow(verbose, &operation, started.elapsed(), timeouts.api());
+        days.push((date.format("%Y-%m-%d").to_string(), scores));
     }
+    Ok(days)
+}
 
-    // Game-winning shootout goal is always on its own line because
-    // the game must be tied before it so it's safe to print it after everything.
-    // If we later add assists by Finns, this needs to be rewritten.
-    if let Some(shootout_goal) = shootout_scorer {
-        if shootout_goal.team == game.home {
-            print_home_goal(shootout_goal, highlights, options)
-        } else {
-            print_away_goal(shootout_goal, highlights, options)
-        }
+/// Fetches the responses `--raw` prints: the latest scores, or a list of
+/// the days in `range` or of each date
+#[tokio::main]
+async fn fetch_raw_scores(
+    client: &ScoreClient,
+    dates: &[NaiveDate],
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<serde_json::Value, ClientError> {
+    if let Some((start, last)) = range {
+        return client.date_range_json(start, last).await;
     }
-    println!();
-
-    if options.show_stats && !highlights.is_empty() {
-        print_stats(&game.goals, &highlights, &options);
+    if dates.is_empty() {
+        return client.latest_json().await;
     }
 
-    match &game.playoff_series {
-        Some(playoff_series) => {
-            let series_wins = &playoff_series["wins"];
-            let home_wins = &series_wins[&game.home];
-            let away_wins = &series_wins[&game.away];
-
-            if atty::is(Stream::Stdout) && options.use_colors {
-                yellow_ln!("Series {}-{}", home_wins, away_wins);
-            } else {
-                println!("Series {}-{}", home_wins, away_wins);
-            }
-            println!();
+    let mut days = Vec::new();
+    for date in dates {
+        match client.date_range_json(*date, *date).await? {
+            serde_json::Value::Array(day) => days.extend(day),
+            day => days.push(day),
         }
-        None => (),
     }
+    Ok(serde_json::Value::Array(days))
 }
 
-fn print_both_goals(home: &Goal, away: &Goal, highlights: &[String], options: &Options) {
-    let home_message = format!("{:<15} {:>2} ", home.scorer.last_name, home.minute);
-    if atty::is(Stream::Stdout) && options.use_colors {
-        if home.special {
-            magenta!("{}", home_message);
-        } else if options.show_highlights && highlights.contains(&home.scorer.last_name) {
-            yellow!("{}", home_message);
-        } else {
-            cyan!("{}", home_message);
-        }
-    } else {
-        print!("{}", home_message);
-    }
+/// Checks what stdout is once for the whole run
+fn detect_terminal() -> TerminalInfo {
+    TerminalInfo::new(atty::is(Stream::Stdout), env::var("TERM").ok().as_deref())
+        .with_hyperlinks(|name| env::var(name).ok())
+}
 
-    let away_message = format!("{:<15} {:>2}", away.scorer.last_name, away.minute);
-    if atty::is(Stream::Stdout) && options.use_colors {
-        if away.special {
-            magenta_ln!("{}", away_message);
-        } else if options.show_highlights && highlights.contains(&away.scorer.last_name) {
-            yellow_ln!("{}", away_message);
-        } else {
-            cyan_ln!("{}", away_message);
-        }
-    } else {
-        println!("{}", away_message);
-    }
+/// Handler function to print multiple Games.
+/// Several days are printed in sections under their dates.
+fn print_games(sections: &[Section], terminal: &TerminalInfo, options: &RenderOptions) {
+    print!("{}", render_page(sections, terminal, options));
 }
 
-fn print_home_goal(home: &Goal, highlights: &[String], options: &Options) {
-    let message = format!("{:<15} {:>2}", home.scorer.last_name, home.minute);
-    if atty::is(Stream::Stdout) && options.use_colors {
-        if home.special {
-            magenta_ln!("{}", message);
-        } else if options.show_highlights && highlights.contains(&home.scorer.last_name) {
-            yellow_ln!("{}", message);
-        } else {
-            cyan_ln!("{}", message);
-        }
+/// A day of games in `--json` output of several dates
+#[derive(Serialize)]
+struct JsonDay<'a> {
+    date: &'a str,
+    games: Vec<&'a Game>,
+}
+
+fn team_names_style(scores: &ScoreArgs) -> TeamNames {
+    if scores.full_names {
+        TeamNames::Full
+    } else if scores.abbr {
+        TeamNames::Abbreviation
     } else {
-        println!("{}", message);
+        TeamNames::City
     }
 }
 
-fn print_away_goal(away: &Goal, highlights: &[String], options: &Options) {
-    let message = format!(
-        "{:<15} {:>2} {:<15} {:>2}",
-        "", "", away.scorer.last_name, away.minute
-    );
-    if atty::is(Stream::Stdout) && options.use_colors {
-        if away.special {
-            magenta_ln!("{}", message);
-        } else if options.show_highlights && highlights.contains(&away.scorer.last_name) {
-            yellow_ln!("{}", message);
-        } else {
-            cyan_ln!("{}", message);
-        }
+fn output_format(scores: &ScoreArgs) -> OutputFormat {
+    if scores.json {
+        OutputFormat::Json
     } else {
-        println!("{}", message);
+        scores.format.unwrap_or_default()
     }
 }
 
-fn count_stats<'a>(
-    goals: &'a Vec<Goal>,
-    highlights: &[String],
-    stats: &mut HashMap<&'a Player, Stat>,
-) {
-    goals.iter().for_each(|goal| {
-        if goal.minute == 65 {
-            return;
-        }
-        if highlights.contains(&goal.scorer.last_name) {
-            stats
-                .entry(&goal.scorer)
-                .and_modify(|stat| stat.goals += 1)
-                .or_insert(Stat {
-                    goals: 1,
-                    assists: 0,
-                });
-        }
-        goal.assists.iter().for_each(|assist| {
-            if highlights.contains(&assist.last_name) {
-                stats
-                    .entry(assist)
-                    .and_modify(|stat| stat.assists += 1)
-                    .or_insert(Stat {
-                        goals: 0,
-                        assists: 1,
-                    });
-            }
-        })
-    });
-
-    ()
+fn day_games(section: &Section) -> Vec<&Game> {
+    section.games.iter().flatten().collect()
 }
 
-fn has_last_name_namesake(player: &Player, stats: &HashMap<&Player, Stat>) -> bool {
-    for other in stats.keys() {
-        if other.last_name == player.last_name && other.team != player.team {
-            return true;
-        }
-        if other.last_name == player.last_name && other.team == player.team {
-            if other.first_name != player.first_name {
-                return true;
-            }
+fn print_json(sections: &[Section]) {
+    let json = match sections {
+        [section] => serde_json::to_string_pretty(&day_games(section)),
+        _ => serde_json::to_string_pretty(
+            &sections
+                .iter()
+                .map(|section| JsonDay {
+                    date: &section.title,
+                    games: day_games(section),
+                })
+                .collect::<Vec<_>>(),
+        ),
+    };
+    match json {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            println!("ERROR: Can't write the games as JSON: {}", e);
+            process::exit(1);
         }
     }
-    false
 }
 
-fn craft_stats_message(goals: &Vec<Goal>, highlights: &[String]) -> Option<String> {
-    let mut stats: HashMap<&Player, Stat> = HashMap::new();
-    count_stats(&goals, &highlights, &mut stats);
+fn render_page(sections: &[Section], terminal: &TerminalInfo, options: &RenderOptions) -> String {
+    render_sections_with(terminal.renderer(options), sections, options)
+}
 
-    if stats.is_empty() {
-        return None;
+/// A single day as its page, several days under their dates
+fn render_sections_with(
+    renderer: &dyn Renderer,
+    sections: &[Section],
+    options: &RenderOptions,
+) -> String {
+    match sections {
+        [section] => renderer.render_games(&section.games, options),
+        _ => renderer.render_sections(sections, options),
     }
+}
 
-    let mut stats_messages: Vec<String> = Vec::new();
-    for (player, player_stats) in stats.iter() {
-        let needs_first_name: bool = has_last_name_namesake(*player, &stats);
-        let player_name: String = if needs_first_name {
-            format!(
-                "{}. {}",
-                &player.first_name.chars().next().unwrap(),
-                &player.last_name
-            )
-        } else {
-            String::from(&player.last_name)
-        };
-        let sub_message = format!(
-            "{} {}+{}",
-            player_name,
-            &player_stats.goals.to_string(),
-            &player_stats.assists.to_string()
-        );
-        stats_messages.push(sub_message);
+/// Draws `page` over the previous one. Clearing the whole screen first
+/// would blank it for a moment on every refresh, so each line only clears
+/// what is left of the old line after it.
+fn redraw_frame(page: &str) -> String {
+    let mut frame = String::with_capacity(page.len() + 256);
+    frame.push_str(CURSOR_HOME);
+    for line in page.lines() {
+        frame.push_str(line);
+        frame.push_str(CLEAR_LINE);
+        frame.push('\n');
     }
-    return Some(format!("({})", stats_messages.join(", ")));
+    frame.push_str(CLEAR_BELOW);
+    frame
 }
 
-fn print_stats(goals: &Vec<Goal>, highlights: &[String], options: &Options) {
-    let message: Option<String> = craft_stats_message(&goals, &highlights);
-
-    match message {
-        Some(message) => {
-            if options.show_highlights {
-                yellow_ln!("{}", message);
-            } else if options.use_colors {
-                white_ln!("{}", message);
-            } else {
-                println!("{}", message);
+/// Redraws the scores until interrupted with Ctrl-C, every `interval` or
+/// at the pace of `events::refresh_delay` without one. The old scores
+/// stay up while waiting for the API and are only drawn over once the new
+/// ones are in, and a failed fetch is retried on the next round instead
+/// of ending the watch. The latest goal of all games is kept in a banner
+/// on the first line. When the config files change, the settings are read
+/// again with `reload_settings` for the next round.
+fn watch_games(
+    load_sections: impl Fn(&[String]) -> Result<Vec<Section>, ClientError>,
+    mut settings: Settings,
+    reload_settings: impl Fn() -> Result<Settings, String>,
+    interval: Option<StdDuration>,
+    terminal: &TerminalInfo,
+) -> ! {
+    let mut goals = GoalTracker::default();
+    let mut delay = interval.unwrap_or(LIVE_REFRESH);
+    let mut watcher = ConfigWatcher::new();
+    loop {
+        if watcher.changed() {
+            match reload_settings() {
+                Ok(reloaded) => settings = reloaded,
+                // The settings so far stay until the config is fixed
+                Err(e) => eprintln!("{} WARNING: {}", Local::now().format("%H:%M:%S"), e),
             }
-            println!();
         }
-        None => (),
+        let options = &settings.options;
+        let result = load_sections(&settings.teams);
+        // A failed fetch is tried again as soon as the previous pace allows
+        if let (Ok(sections), None) = (&result, interval) {
+            let games = sections
+                .iter()
+                .flat_map(|section| section.games.iter().flatten());
+            delay = refresh_delay(games, &options.favorite_teams, &Local::now());
+        }
+        match result {
+            // The page goes out in one write so it's never seen half drawn
+            Ok(sections) if terminal.is_terminal => {
+                let days: Vec<&[Option<Game>]> = sections
+                    .iter()
+                    .map(|section| section.games.as_slice())
+                    .collect();
+                let banner = goals
+                    .update(&days)
+                    .and_then(|goal| goal_banner(goal, options))
+                    .map(|banner| terminal.renderer(options).render_lines(&[banner]))
+                    .unwrap_or_default();
+                let now = Local::now();
+                let next = now + Duration::from_std(delay).unwrap_or_else(|_| Duration::zero());
+                let page = format!(
+                    "{}{}\nUpdated {}, next refresh at {}\n",
+                    banner,
+                    render_page(&sections, terminal, options),
+                    now.format("%H:%M:%S"),
+                    next.format("%H:%M:%S")
+                );
+                let _ = io::stdout()
+                    .lock()
+                    .write_all(redraw_frame(&page).as_bytes());
+            }
+            Ok(sections) => print_games(&sections, terminal, options),
+            // The last good page stays up and the next round tries again
+            Err(err) => eprintln!(
+                "{} {}",
+                Local::now().format("%H:%M:%S"),
+                request_error_message(&err)
+            ),
+        }
+        let _ = io::stdout().flush();
+        thread::sleep(delay);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[test]
-    fn minutes_are_converted_correctly() {
-        assert_eq!(format_minute(3, "1"), 3);
-        assert_eq!(format_minute(13, "2"), 33);
-        assert_eq!(format_minute(5, "3"), 45);
-        assert_eq!(format_minute(12, "4"), 72);
-        assert_eq!(format_minute(5, "5"), 85);
-        assert_eq!(format_minute(5, "6"), 105);
-        assert_eq!(format_minute(4, "OT"), 64);
-        assert_eq!(format_minute(0, "1"), 0);
-        assert_eq!(format_minute(0, "2"), 20);
-        assert_eq!(format_minute(0, "3"), 40);
-        assert_eq!(format_minute(0, "OT"), 60);
-    }
-
-    #[test]
-    fn is_special_works() -> serde_json::Result<()> {
-        let first =
-            r#"{ "team": "CHI", "period": "1", "scorer": { "player": "_", "seasonTotal": 10} }"#;
-        let second =
-            r#"{ "team": "CHI", "period": "2", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-        let third =
-            r#"{ "team": "CHI", "period": "3", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-        let overtime =
-            r#"{ "team": "CHI", "period": "OT", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-        let shootout =
-            r#"{ "team": "CHI", "period": "SO", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-        let playoff_ot =
-            r#"{ "team": "CHI", "period": "4", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-        let playoff_ot_2 =
-            r#"{ "team": "CHI", "period": "10", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-        let wrong_data =
-            r#"{ "team": "CHI", "period": "SP", "scorer": { "player": "_", "seasonTotal": 10}  }"#;
-
-        let goal1: GoalResponse = serde_json::from_str(&first)?;
-        let goal2: GoalResponse = serde_json::from_str(&second)?;
-        let goal3: GoalResponse = serde_json::from_str(&third)?;
-        let goal4: GoalResponse = serde_json::from_str(&overtime)?;
-        let goal5: GoalResponse = serde_json::from_str(&shootout)?;
-        let goal6: GoalResponse = serde_json::from_str(&playoff_ot)?;
-        let goal7: GoalResponse = serde_json::from_str(&playoff_ot_2)?;
-        let goal8: GoalResponse = serde_json::from_str(&wrong_data)?;
-
-        assert_eq!(is_special(&goal1), false);
-        assert_eq!(is_special(&goal2), false);
-        assert_eq!(is_special(&goal3), false);
-        assert_eq!(is_special(&goal4), true);
-        assert_eq!(is_special(&goal5), true);
-        assert_eq!(is_special(&goal6), true);
-        assert_eq!(is_special(&goal7), true);
-        // I haven't yet really decided what this should be but
-        // important thing is that it does not crash the app
-        assert_eq!(is_special(&goal8), true);
-
-        Ok(())
-    }
-
-    #[test]
-    fn it_parses_full_live_game_data_correctly() -> serde_json::Result<()> {
-        let test_game: GameResponse = serde_json::from_str(
-            r#"{"status":{"state":"LIVE","progress":{"currentPeriod":3,"currentPeriodOrdinal":"3rd","currentPeriodTimeRemaining":{"min":12,"sec":21,"pretty":"12:21"}}},"startTime":"2021-01-23T19:00:00Z","goals":[{"team":"TBL","period":"1","scorer":{"player":"Victor Hedman","seasonTotal":1},"assists":[{"player":"Mitchell Stephens","seasonTotal":1},{"player":"Alexander Volkov","seasonTotal":1}],"min":4,"sec":10},{"team":"CBJ","period":"1","scorer":{"player":"Nick Foligno","seasonTotal":3},"assists":[{"player":"Cam Atkinson","seasonTotal":2},{"player":"Michael Del Zotto","seasonTotal":4}],"min":4,"sec":27},{"team":"CBJ","period":"1","scorer":{"player":"Mikhail Grigorenko","seasonTotal":1},"assists":[{"player":"Kevin Stenlund","seasonTotal":1},{"player":"Nathan Gerbe","seasonTotal":1}],"min":10,"sec":3},{"team":"CBJ","period":"1","scorer":{"player":"Vladislav Gavrikov","seasonTotal":1},"assists":[{"player":"Liam Foudy","seasonTotal":2},{"player":"Eric Robinson","seasonTotal":1}],"min":19,"sec":1},{"team":"TBL","period":"1","scorer":{"player":"Ondrej Palat","seasonTotal":3},"assists":[{"player":"Brayden Point","seasonTotal":3},{"player":"Victor Hedman","seasonTotal":4}],"min":19,"sec":46,"strength":"PPG"},{"team":"CBJ","period":"3","scorer":{"player":"Zach Werenski","seasonTotal":1},"assists":[{"player":"Alexandre Texier","seasonTotal":2},{"player":"Boone Jenner","seasonTotal":2}],"min":6,"sec":34}],"scores":{"TBL":2,"CBJ":4},"teams":{"away":{"abbreviation":"TBL","id":14,"locationName":"Tampa Bay","shortName":"Tampa Bay","teamName":"Lightning"},"home":{"abbreviation":"CBJ","id":29,"locationName":"Columbus","shortName":"Columbus","teamName":"Blue Jackets"}},"preGameStats":{"records":{"TBL":{"wins":3,"losses":0,"ot":0},"CBJ":{"wins":1,"losses":2,"ot":2}}},"currentStats":{"records":{"TBL":{"wins":3,"losses":0,"ot":0},"CBJ":{"wins":1,"losses":2,"ot":2}},"streaks":{"TBL":{"type":"WINS","count":3},"CBJ":{"type":"OT","count":2}},"standings":{"TBL":{"divisionRank":"1","leagueRank":"1"},"CBJ":{"divisionRank":"7","leagueRank":"24"}}}}"#,
-        )?;
-
-        let parsed_game = parse_game(&test_game).unwrap();
-
-        assert_eq!(parsed_game.home, "CBJ");
-        assert_eq!(parsed_game.away, "TBL");
-        assert_eq!(parsed_game.score, "4-2");
-        assert_eq!(parsed_game.goals.len(), 6);
-        assert_eq!(parsed_game.status, "LIVE");
-        assert_eq!(parsed_game.special, "");
-
-        Ok(())
-    }
-
-    #[test]
-    fn it_parses_full_overtime_game_data_correctly() -> serde_json::Result<()> {
-        let test_game = serde_json::from_str(
-            r#"
-            {
-                "status":{
-                    "state":"FINAL"
-                },
-                "startTime":"2021-01-23T19:00:00Z",
-                "goals":[
-                    {
-                        "team":"TOR",
-                        "period":"1",
-                        "scorer":{
-                            "player":"Mitch Marner",
-                            "seasonTotal":1
-                        },
-                        "assists":[
-                            {
-                                "player":"Mitchell Stephens",
-                                "seasonTotal":1
-                            },
-                            {
-                                "player":"Alexander Volkov",
-                                "seasonTotal":1
-                            }
-                        ],
-                        "min":4,
-                        "sec":10
-                    },
-                    {
-                        "team":"PIT",
-                        "period":"3",
-                        "scorer":{
-                            "player":"Sidney Crosby",
-                            "seasonTotal":3
-                        },
-                        "assists":[
-                            {
-                                "player":"Evgeni Malkin",
-                                "seasonTotal":2
-                            }
-                        ],
-                        "min":4,
-                        "sec":27
-                    },
-                    {
-                        "team":"PIT",
-                        "period":"OT",
-                        "scorer":{
-                            "player":"Sidney Crosby",
-                            "seasonTotal":4
-                        },
-                        "assists":[],
-                        "min":3,
-                        "sec":0
-                    }],
-                    "scores":{
-                        "PIT":2,"TOR":1
-                    },
-                    "teams":{
-                        "away":{
-                            "abbreviation":"PIT",
-                            "id":14,
-                            "locationName":"Pittsburgh",
-                            "shortName":"Pittsburgh",
-                            "teamName":"Penguins"
-                        },
-                        "home":{
-                            "abbreviation":"TOR",
-                            "id":29,
-                            "locationName":"Toronto",
-                            "shortName":"Toronto",
-                            "teamName":"Maple Leafs"
-                        }
-                    },
-                    "preGameStats":{"records":{"PIT":{"wins":3,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":2}}},
-                    "currentStats":{"records":{"PIT":{"wins":4,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":3}},
-                    "streaks":{"PIT":{"type":"WINS","count":3},"TOR":{"type":"OT","count":2}},
-                    "standings":{
-                        "PIT":{"divisionRank":"1","leagueRank":"1"},
-                        "CBJ":{"divisionRank":"7","leagueRank":"24"}
-                    }
-                }
-            }"#,
-        )?;
-
-        let parsed_game = parse_game(&test_game).unwrap();
-
-        assert_eq!(parsed_game.home, "TOR");
-        assert_eq!(parsed_game.away, "PIT");
-        assert_eq!(parsed_game.score, "1-2");
-        assert_eq!(parsed_game.goals.len(), 3);
-        assert_eq!(parsed_game.status, "FINAL");
-        assert_eq!(parsed_game.special, "ot");
-
-        Ok(())
-    }
-
-    #[test]
-    fn it_parses_a_game_with_no_goals_correctly() -> serde_json::Result<()> {
-        let test_game = serde_json::from_str(
-            r#"
-            {
-                "status":{
-                    "state":"LIVE"
-                },
-                "startTime":"2021-01-23T19:00:00Z",
-                "scores":{
-                    "PIT":0,"TOR":0
-                },
-                "teams":{
-                    "away":{
-                        "abbreviation":"PIT",
-                        "id":14,
-                        "locationName":"Pittsburgh",
-                        "shortName":"Pittsburgh",
-                        "teamName":"Penguins"
-                    },
-                    "home":{
-                        "abbreviation":"TOR",
-                        "id":29,
-                        "locationName":"Toronto",
-                        "shortName":"Toronto",
-                        "teamName":"Maple Leafs"
-                    }
-                },
-                "preGameStats":{"records":{"PIT":{"wins":3,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":2}}},
-                "currentStats":{"records":{"PIT":{"wins":4,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":3}},
-                "streaks":{"PIT":{"type":"WINS","count":3},"TOR":{"type":"OT","count":2}},
-                "standings":{
-                    "PIT":{"divisionRank":"1","leagueRank":"1"},
-                    "CBJ":{"divisionRank":"7","leagueRank":"24"}
-                }
-            }
-        }"#,
-        )?;
-
-        let parsed_game = parse_game(&test_game).unwrap();
-
-        assert_eq!(parsed_game.home, "TOR");
-        assert_eq!(parsed_game.away, "PIT");
-        assert_eq!(parsed_game.score, "0-0");
-        assert_eq!(parsed_game.goals.len(), 0);
-        assert_eq!(parsed_game.status, "LIVE");
-        assert_eq!(parsed_game.special, "");
-
-        Ok(())
-    }
 
     #[test]
-    fn it_parses_a_playoffs_game_with_overtime_correctly() -> serde_json::Result<()> {
-        let test_game = serde_json::from_str(
-            r#"
-            {
-                "status":{
-                    "state":"FINAL"
-                },
-                "startTime":"2021-01-23T19:00:00Z",
-                "goals":[{
-                    "team":"PIT",
-                    "period":"4",
-                    "scorer":{
-                        "player":"Sidney Crosby",
-                        "seasonTotal":3
-                    },
-                    "assists":[
-                        {
-                            "player":"Evgeni Malkin",
-                            "seasonTotal":2
-                        }
-                    ],
-                    "min":4,
-                    "sec":27
-                }],
-                    "scores":{
-                        "PIT":1,"TOR":0
-                    },
-                    "teams":{
-                        "away":{
-                            "abbreviation":"PIT",
-                            "id":14,
-                            "locationName":"Pittsburgh",
-                            "shortName":"Pittsburgh",
-                            "teamName":"Penguins"
-                        },
-                        "home":{
-                            "abbreviation":"TOR",
-                            "id":29,
-                            "locationName":"Toronto",
-                            "shortName":"Toronto",
-                            "teamName":"Maple Leafs"
-                        }
-                    },
-                    "preGameStats":{"records":{"PIT":{"wins":3,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":2}}},
-                    "currentStats":{"records":{"PIT":{"wins":4,"losses":0,"ot":0},"TOR":{"wins":1,"losses":2,"ot":3}},
-                    "streaks":{"PIT":{"type":"WINS","count":3},"TOR":{"type":"OT","count":2}},
-                    "standings":{
-                        "PIT":{"divisionRank":"1","leagueRank":"1"},
-                        "CBJ":{"divisionRank":"7","leagueRank":"24"}
-                    }
-                }
-            }"#,
-        )?;
-
-        let parsed_game = parse_game(&test_game).unwrap();
-
-        assert_eq!(parsed_game.home, "TOR");
-        assert_eq!(parsed_game.away, "PIT");
-        assert_eq!(parsed_game.score, "0-1");
-        assert_eq!(parsed_game.goals.len(), 1);
-        assert_eq!(parsed_game.status, "FINAL");
-        assert_eq!(parsed_game.special, "ot");
-
-        Ok(())
+    fn parses_windows_line_endings() {
+        let highlights: String = String::from("Crosby\r\nMalkin");
+        let lines = parse_highlight_config(highlights);
+        assert!(lines.is_ok());
+        assert_eq!("Crosby", lines.as_ref().unwrap().first().unwrap());
+        assert_eq!("Malkin", lines.as_ref().unwrap().last().unwrap());
     }
 
     #[test]
-    fn it_extracts_player_last_name_correctly() {
+    fn resolves_divisions_and_conferences() {
         assert_eq!(
-            extract_player("Olli Maatta", "Chicago").last_name,
-            String::from("Maatta")
+            resolve_group("division", "metro", match_division).map(|group| group.name),
+            Ok("Metropolitan")
         );
         assert_eq!(
-            extract_player("James van Riemsdyk", "Philadelphia").last_name,
-            String::from("van Riemsdyk")
+            resolve_group("conference", "Westen", match_conference).map(|group| group.name),
+            Err(String::from(
+                "Unknown conference 'Westen', did you mean WEST (Western)?"
+            ))
         );
     }
 
     #[test]
-    fn it_crafts_no_message_if_no_highlighted_players_gain_stats() {
-        let highlights: Vec<String> = vec![String::from("Crosby")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Evgeni"),
-                last_name: String::from("Malkin"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Kris"),
-                    last_name: String::from("Letang"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Erik"),
-                    last_name: String::from("Karlsson"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let expected: Option<String> = None;
-        let actual: Option<String> = craft_stats_message(&vec![goal], &highlights);
-
-        assert_eq!(actual, expected);
-    }
-
-    #[test]
-    fn it_crafts_good_message_if_player_scored() {
-        let highlights: Vec<String> = vec![String::from("Crosby")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Sidney"),
-                last_name: String::from("Crosby"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Kris"),
-                    last_name: String::from("Letang"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Erik"),
-                    last_name: String::from("Karlsson"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let expected: Option<String> = Some(String::from("(Crosby 1+0)"));
-        let actual: Option<String> = craft_stats_message(&vec![goal], &highlights);
-
-        assert_eq!(actual, expected);
-    }
-
-    #[test]
-    fn it_crafts_good_message_if_player_gained_assist() {
-        let highlights: Vec<String> = vec![String::from("Crosby")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Evgeni"),
-                last_name: String::from("Malkin"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Sidney"),
-                    last_name: String::from("Crosby"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Erik"),
-                    last_name: String::from("Karlsson"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let expected: Option<String> = Some(String::from("(Crosby 0+1)"));
-        let actual: Option<String> = craft_stats_message(&vec![goal], &highlights);
-
-        assert_eq!(actual, expected);
+    fn parses_the_teams_of_a_game() {
+        assert_eq!(
+            parse_matchup("pit-Leafs"),
+            Ok((String::from("PIT"), String::from("TOR")))
+        );
+        assert!(parse_matchup("PIT").is_err());
+        assert!(parse_matchup("PIT-XYZ").is_err());
     }
 
     #[test]
-    fn it_crafts_good_message_if_player_gained_both_goal_and_assist() {
-        let highlights: Vec<String> = vec![String::from("Crosby")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Evgeni"),
-                last_name: String::from("Malkin"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Sidney"),
-                    last_name: String::from("Crosby"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Erik"),
-                    last_name: String::from("Karlsson"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let goal2: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Sidney"),
-                last_name: String::from("Crosby"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![Player {
-                first_name: String::from("Brian"),
-                last_name: String::from("Rust"),
-                team: String::from("Pittsburgh"),
-            }],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let expected: Option<String> = Some(String::from("(Crosby 1+1)"));
-        let actual: Option<String> = craft_stats_message(&vec![goal, goal2], &highlights);
-
-        assert_eq!(actual, expected);
+    fn resolves_team_groups_and_names() {
+        let mut config = Config::default();
+        config.teams.insert(
+            String::from("mine"),
+            vec![String::from("PIT"), String::from("canes")],
+        );
+        let names = vec![String::from("mine"), String::from("leafs")];
+        assert_eq!(
+            resolve_teams(&names, &config).unwrap(),
+            ["PIT", "CAR", "TOR"]
+        );
+        assert_eq!(
+            resolve_teams(&[String::from("Pitsburgh")], &config),
+            Err(String::from(
+                "Unknown team 'Pitsburgh', did you mean PIT (Pittsburgh)?"
+            ))
+        );
+        assert!(resolve_teams(&[String::from("yours")], &config).is_err());
     }
 
     #[test]
-    fn it_crafts_good_message_if_player_gained_two_assists() {
-        let highlights: Vec<String> = vec![String::from("Crosby")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Evgeni"),
-                last_name: String::from("Malkin"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Sidney"),
-                    last_name: String::from("Crosby"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Erik"),
-                    last_name: String::from("Karlsson"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let goal2: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Evgeni"),
-                last_name: String::from("Malkin"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Brian"),
-                    last_name: String::from("Rust"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Sidney"),
-                    last_name: String::from("Crosby"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let expected: Option<String> = Some(String::from("(Crosby 0+2)"));
-        let actual: Option<String> = craft_stats_message(&vec![goal, goal2], &highlights);
-
-        assert_eq!(actual, expected);
+    fn resolves_team_names_by_abbreviation() {
+        let mut config = Config::default();
+        config
+            .team_names
+            .insert(String::from("canes"), String::from("Carolina Canes"));
+        assert_eq!(
+            resolve_team_names(&config).unwrap()["CAR"],
+            "Carolina Canes"
+        );
+        config
+            .team_names
+            .insert(String::from("yours"), String::from("Yours"));
+        assert!(resolve_team_names(&config).is_err());
     }
 
-    #[test]
-    fn parses_windows_line_endings() {
-        let highlights: String = String::from("Crosby\r\nMalkin");
-        let lines = parse_highlight_config(highlights);
-        assert!(lines.is_ok());
-        assert_eq!("Crosby", lines.as_ref().unwrap().first().unwrap());
-        assert_eq!("Malkin", lines.as_ref().unwrap().last().unwrap());
-    }
     #[test]
     fn parses_unix_line_endings() {
         let highlights: String = String::from("Crosby\nMalkin");
@@ -1120,185 +2158,77 @@ mod tests {
         assert_eq!("Crosby", lines.as_ref().unwrap().first().unwrap());
         assert_eq!("Malkin", lines.as_ref().unwrap().last().unwrap());
     }
-    #[test]
-    fn it_crafts_good_message_if_multiple_players_gain_points() {
-        let highlights: Vec<String> = vec![String::from("Crosby"), String::from("Malkin")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Evgeni"),
-                last_name: String::from("Malkin"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Sidney"),
-                    last_name: String::from("Crosby"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Erik"),
-                    last_name: String::from("Karlsson"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let goal2: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Sidney"),
-                last_name: String::from("Crosby"),
-                team: String::from("Pittsburgh"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Brian"),
-                    last_name: String::from("Rust"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Evgeni"),
-                    last_name: String::from("Malkin"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
-
-        let goal3: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Brian"),
-                last_name: String::from("Rust"),
-                team: String::from("Pittsburg"),
-            },
-            assists: vec![
-                Player {
-                    first_name: String::from("Kris"),
-                    last_name: String::from("Letang"),
-                    team: String::from("Pittsburgh"),
-                },
-                Player {
-                    first_name: String::from("Evgeni"),
-                    last_name: String::from("Malkin"),
-                    team: String::from("Pittsburgh"),
-                },
-            ],
-            minute: 21,
-            special: false,
-            team: String::from("Pittsburg"),
-        };
 
-        let expected: String = String::from("Malkin 1+2");
-        let expected2: String = String::from("Crosby 1+1");
-        let actual: Option<String> = craft_stats_message(&vec![goal, goal2, goal3], &highlights);
-
-        assert_eq!(actual.as_ref().unwrap().contains(&expected), true);
-        assert_eq!(actual.as_ref().unwrap().contains(&expected2), true);
-    }
     #[test]
-    fn it_crafts_good_message_if_different_players_from_different_teams_with_same_last_name() {
-        let highlights: Vec<String> = vec![String::from("Hughes")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Jack"),
-                last_name: String::from("Hughes"),
-                team: String::from("New Jersey"),
-            },
-            assists: vec![],
-            minute: 21,
-            special: false,
-            team: String::from("New Jersey"),
-        };
-
-        let goal2: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Quinn"),
-                last_name: String::from("Hughes"),
-                team: String::from("Vancouver"),
-            },
-            assists: vec![],
-            minute: 23,
-            special: false,
-            team: String::from("Vancouver"),
-        };
-
-        let expected: String = String::from("Q. Hughes 1+0");
-        let expected2: String = String::from("J. Hughes 1+0");
-        let actual: Option<String> = craft_stats_message(&vec![goal, goal2], &highlights);
-
-        assert_eq!(actual.as_ref().unwrap().contains(&expected), true);
-        assert_eq!(actual.as_ref().unwrap().contains(&expected2), true);
+    fn parses_every_example() {
+        for topic in &examples::TOPICS {
+            for example in topic.examples {
+                let args = example.command.split_whitespace();
+                if let Err(e) = Cli::from_iter_safe(args) {
+                    panic!("`{}` doesn't parse: {}", example.command, e);
+                }
+            }
+        }
     }
 
     #[test]
-    fn it_crafts_good_message_if_different_players_from_same_team_with_same_last_name() {
-        let highlights: Vec<String> = vec![String::from("Hughes")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Jack"),
-                last_name: String::from("Hughes"),
-                team: String::from("New Jersey"),
-            },
-            assists: vec![],
-            minute: 21,
-            special: false,
-            team: String::from("New Jersey"),
-        };
-
-        let goal2: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Quinn"),
-                last_name: String::from("Hughes"),
-                team: String::from("New Jersey"),
-            },
-            assists: vec![],
-            minute: 23,
-            special: false,
-            team: String::from("New Jersey"),
+    fn picks_the_dates_to_show() {
+        let date = |date| parse_date(date).unwrap();
+        let today = date("2024-10-16");
+        let range = |args: &[&str]| {
+            let args =
+                ScoreArgs::from_iter_safe(std::iter::once("235").chain(args.iter().copied()))
+                    .unwrap();
+            date_range(&args, today)
         };
 
-        let expected: String = String::from("Q. Hughes 1+0");
-        let expected2: String = String::from("J. Hughes 1+0");
-        let actual: Option<String> = craft_stats_message(&vec![goal, goal2], &highlights);
-
-        assert_eq!(actual.as_ref().unwrap().contains(&expected), true);
-        assert_eq!(actual.as_ref().unwrap().contains(&expected2), true);
+        assert_eq!(range(&[]), Ok(None));
+        assert_eq!(
+            range(&["--days", "3"]),
+            Ok(Some((date("2024-10-14"), today)))
+        );
+        assert_eq!(
+            range(&["--from", "2024-04-20", "--to", "2024-04-27"]),
+            Ok(Some((date("2024-04-20"), date("2024-04-27"))))
+        );
+        assert_eq!(
+            range(&["--from", "2024-10-10"]),
+            Ok(Some((date("2024-10-10"), today)))
+        );
+        assert!(range(&["--from", "2024-04-27", "--to", "2024-04-20"]).is_err());
     }
 
     #[test]
-    fn it_doesnt_count_shootout_goals_to_stats() {
-        let highlights: Vec<String> = vec![String::from("Barkov")];
-        let goal: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Alexander"),
-                last_name: String::from("Barkov"),
-                team: String::from("Florida"),
-            },
-            assists: vec![],
-            minute: 21,
-            special: false,
-            team: String::from("Florida"),
+    fn keeps_the_flags_before_the_scores_subcommand() {
+        let args = Cli::from_iter_safe(
+            "235 --lang fi --stats --team PIT scores --team TOR --venue".split_whitespace(),
+        )
+        .unwrap();
+        assert_eq!(args.lang, Lang::Fi);
+        let scores = match &args.command {
+            Some(Command::Scores(after)) => after.after(&args.scores),
+            _ => panic!("scores isn't the command"),
         };
+        assert!(scores.stats && scores.venue);
+        assert_eq!(scores.teams, ["PIT", "TOR"]);
 
-        let goal2: Goal = Goal {
-            scorer: Player {
-                first_name: String::from("Alexander"),
-                last_name: String::from("Barkov"),
-                team: String::from("Florida"),
-            },
-            assists: vec![],
-            minute: 65,
-            special: false,
-            team: String::from("Florida"),
-        };
+        // A team doesn't take the subcommand for another team
+        let args = Cli::from_iter_safe("235 --team PIT tui".split_whitespace()).unwrap();
+        assert!(matches!(args.command, Some(Command::Tui)));
+    }
 
-        let expected: String = String::from("Barkov 1+0");
-        let actual: Option<String> = craft_stats_message(&vec![goal, goal2], &highlights);
+    #[test]
+    fn redraws_over_the_previous_frame() {
+        assert_eq!(
+            redraw_frame("Pittsburgh - Toronto 2-1\n\nUpdated 19:30\n"),
+            "\x1B[HPittsburgh - Toronto 2-1\x1B[K\n\x1B[K\nUpdated 19:30\x1B[K\n\x1B[J"
+        );
+    }
 
-        assert_eq!(actual.as_ref().unwrap().contains(&expected), true);
+    #[test]
+    fn parses_players_from_the_command_line() {
+        assert_eq!(parse_player_list("Aho,Barkov"), ["Aho", "Barkov"]);
+        assert_eq!(parse_player_list(" Aho , ,Barkov,"), ["Aho", "Barkov"]);
+        assert!(parse_player_list("").is_empty());
     }
 }
@@ -0,0 +1,101 @@
+//! The games as Markdown tables for pasting into forums and chats, one
+//! table per game with the teams and the score on the header row and the
+//! goals under the team that scored them.
+
+use crate::format::format_iso_date;
+use crate::game::{Game, Goal};
+use crate::options::RenderOptions;
+use crate::render::{game_team_names, goal_rows, score_text, scorer_name, Section};
+
+/// The games of `sections`, each day under a heading when there are
+/// several days
+pub fn games_markdown(sections: &[Section], options: &RenderOptions) -> String {
+    let mut output = String::new();
+    for section in sections {
+        if sections.len() > 1 {
+            output.push_str(&format!(
+                "## {}\n\n",
+                format_iso_date(&section.title, options.language)
+            ));
+        }
+        for game in section.games.iter().flatten() {
+            output.push_str(&game_table(game, options));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// A game as a table of three columns: the home team's goals, the score
+/// and the away team's goals
+pub fn game_table(game: &Game, options: &RenderOptions) -> String {
+    let score = score_text(game, options)
+        .map(|score| score.trim().to_string())
+        .unwrap_or_else(|| String::from("-"));
+    let (home, away) = game_team_names(game, options);
+    let mut table = format!(
+        "| {} | {} | {} |\n| :-- | :-: | --: |\n",
+        escape(&home),
+        escape(&score),
+        escape(&away)
+    );
+    for (home, away) in goal_rows(game, options) {
+        table.push_str(&format!(
+            "| {} | | {} |\n",
+            goal_cell(home, options),
+            goal_cell(away, options)
+        ));
+    }
+    table
+}
+
+fn goal_cell(goal: Option<&Goal>, options: &RenderOptions) -> String {
+    match goal {
+        Some(goal) => format!("{} {}", escape(&scorer_name(goal, options)), goal.minute),
+        None => String::new(),
+    }
+}
+
+/// Escapes the characters that would break the table or turn into
+/// formatting
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '|' | '*' | '_' | '`' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::APIResponse;
+    use crate::game::parse_games;
+
+    #[test]
+    fn it_writes_a_table_per_game() {
+        let json = include_str!("../tests/fixtures/live.json");
+        let scores: APIResponse = serde_json::from_str(json).unwrap();
+        let games = parse_games(scores);
+        let game = games[0].as_ref().unwrap();
+
+        let table = game_table(game, &RenderOptions::default());
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows[1], "| :-- | :-: | --: |");
+        assert_eq!(
+            rows.len(),
+            2 + goal_rows(game, &RenderOptions::default()).len()
+        );
+        assert!(rows[0].starts_with("| Pittsburgh | "));
+    }
+
+    #[test]
+    fn it_escapes_table_characters() {
+        assert_eq!(escape("Crosby"), "Crosby");
+        assert_eq!(escape("A|B"), "A\\|B");
+        assert_eq!(escape("*_"), "\\*\\_");
+    }
+}
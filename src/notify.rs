@@ -0,0 +1,323 @@
+//! `235 notify-once`: fetches the latest scores, reports what happened since
+//! the previous run and exits. Meant to be run from a systemd timer or cron.
+
+use crate::config::{warn_if_slow, EmailConfig, NotifyConfig, Timeouts};
+use crate::lock::{temp_path, FileLock};
+use crate::secrets::{get_secret, SecretError};
+use chrono::{Local, Utc};
+use dirs::home_dir;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use nhl_235::events::{
+    all_games_over, diff, parse_event_log, Event, LoggedEvent, NotifyState, WatchOptions,
+};
+use nhl_235::render::render_games;
+use nhl_235::{Game, RenderOptions};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum NotifyError {
+    State(PathBuf, std::io::Error),
+    InvalidState(PathBuf, serde_json::Error),
+    Webhook(reqwest::Error),
+    Email(String),
+    Secret(SecretError),
+}
+
+const SMTP_PASSWORD_ENV: &str = "NHL235_SMTP_PASSWORD";
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyError::State(path, e) => write!(f, "Can't access {}: {}", path.display(), e),
+            NotifyError::InvalidState(path, e) => {
+                write!(f, "Invalid state in {}: {}", path.display(), e)
+            }
+            NotifyError::Webhook(e) => write!(f, "Can't send events to the webhook: {}", e),
+            NotifyError::Email(e) => write!(f, "Can't send the digest email: {}", e),
+            NotifyError::Secret(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+pub fn default_state_path() -> Option<PathBuf> {
+    let mut path = home_dir()?;
+    path.push(".235.state.json");
+    Some(path)
+}
+
+pub fn default_log_path() -> Option<PathBuf> {
+    let mut path = home_dir()?;
+    path.push(".235.events.jsonl");
+    Some(path)
+}
+
+/// Reports new events on stdout and to the webhook. The state is only
+/// updated once everything has been sent so a failed run is retried
+/// on the next one. During quiet hours the events are kept in the state
+/// and reported together on the first run after them.
+///
+/// Once all games of `day` are over, the whole page is emailed as a digest
+/// if email is configured. The digest is read in the morning anyway so it
+/// doesn't wait for quiet hours to end.
+///
+/// Delivered events are appended to the event log at `log_path`. Events
+/// already in the log are never delivered again, even if the state file
+/// is lost.
+///
+/// The webhook and the email have their own timeouts so that a hung
+/// server fails the run instead of blocking the next scheduled one.
+///
+/// The state is locked for the whole run, so runs that overlap take turns
+/// and each event is delivered by one of them.
+#[allow(clippy::too_many_arguments)]
+pub fn notify_once(
+    games: &[Option<Game>],
+    day: Option<&str>,
+    state_path: &Path,
+    log_path: &Path,
+    watch: &WatchOptions,
+    config: &NotifyConfig,
+    timeouts: &Timeouts,
+    verbose: bool,
+    options: &RenderOptions,
+) -> Result<(), NotifyError> {
+    let _lock = FileLock::acquire(state_path)
+        .map_err(|e| NotifyError::State(state_path.to_path_buf(), e))?;
+    let previous = read_state(state_path)?;
+    let (new_events, mut state) = diff(&previous, games, watch);
+    let mut events = previous.pending;
+    events.extend(new_events);
+    state.digest_date = previous.digest_date;
+
+    let now = Local::now().time();
+    if config.quiet_hours.is_some_and(|quiet| quiet.contains(now)) {
+        state.pending = events;
+    } else {
+        let delivered: HashSet<String> = read_event_log(log_path)?
+            .into_iter()
+            .map(|logged| logged.id)
+            .collect();
+        events.retain(|event| !delivered.contains(&event.id()));
+
+        for event in &events {
+            println!("{}", event);
+        }
+        if let (Some(webhook), false) = (webhook_url(config)?, events.is_empty()) {
+            let started = Instant::now();
+            send_webhook(&webhook, &events, timeouts.webhook()).map_err(NotifyError::Webhook)?;
+            warn_if_slow(
+                verbose,
+                "Sending the webhook",
+                started.elapsed(),
+                timeouts.webhook(),
+            );
+        }
+        append_event_log(log_path, &events)?;
+    }
+    write_state(state_path, &state)?;
+
+    if let (Some(email), Some(day)) = (&config.email, day) {
+        if all_games_over(games) && state.digest_date.as_deref() != Some(day) {
+            let started = Instant::now();
+            send_digest(email, day, &render_games(games, options), timeouts.email())?;
+            warn_if_slow(
+                verbose,
+                "Sending the digest email",
+                started.elapsed(),
+                timeouts.email(),
+            );
+            state.digest_date = Some(day.to_string());
+            write_state(state_path, &state)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tells the healthcheck URL in the config how the run went, so that a
+/// monitoring service notices when runs fail or stop altogether. Failing
+/// to ping is only a warning, it shouldn't fail the run itself.
+pub fn ping_healthcheck(config: &NotifyConfig, success: bool, timeout: Duration) {
+    let url = match &config.healthcheck {
+        Some(url) if success => url.to_string(),
+        Some(url) => format!("{}/fail", url.trim_end_matches('/')),
+        None => return,
+    };
+    if let Err(e) = send_ping(&url, timeout) {
+        eprintln!("WARNING: Can't ping the healthcheck: {}", e);
+    }
+}
+
+#[tokio::main]
+async fn send_ping(url: &str, timeout: Duration) -> Result<(), reqwest::Error> {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Webhook URL from the config or the keyring
+fn webhook_url(config: &NotifyConfig) -> Result<Option<String>, NotifyError> {
+    match &config.webhook_secret {
+        Some(name) => get_secret(name).map(Some).map_err(NotifyError::Secret),
+        None => Ok(config.webhook.clone()),
+    }
+}
+
+pub fn read_event_log(path: &Path) -> Result<Vec<LoggedEvent>, NotifyError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            parse_event_log(&contents).map_err(|e| NotifyError::InvalidState(path.to_path_buf(), e))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(NotifyError::State(path.to_path_buf(), e)),
+    }
+}
+
+fn append_event_log(path: &Path, events: &[Event]) -> Result<(), NotifyError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let now = Utc::now();
+    let mut lines = String::new();
+    for event in events {
+        let logged = LoggedEvent::new(event, now);
+        let line = serde_json::to_string(&logged)
+            .map_err(|e| NotifyError::InvalidState(path.to_path_buf(), e))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    // The log can be shared by runs with states of their own
+    FileLock::acquire(path)
+        .and_then(|_lock| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?
+                .write_all(lines.as_bytes())
+        })
+        .map_err(|e| NotifyError::State(path.to_path_buf(), e))
+}
+
+fn read_state(path: &Path) -> Result<NotifyState, NotifyError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| NotifyError::InvalidState(path.to_path_buf(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(NotifyState::default()),
+        Err(e) => Err(NotifyError::State(path.to_path_buf(), e)),
+    }
+}
+
+/// Writes the state through a temporary file of its own so that an
+/// interrupted run never leaves a half-written state behind
+fn write_state(path: &Path, state: &NotifyState) -> Result<(), NotifyError> {
+    let contents = serde_json::to_string_pretty(state)
+        .map_err(|e| NotifyError::InvalidState(path.to_path_buf(), e))?;
+    let tmp = temp_path(path);
+    fs::write(&tmp, contents)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| NotifyError::State(path.to_path_buf(), e))
+}
+
+/// Body of the webhook request. `text` makes it work with Slack and
+/// Mattermost style incoming webhooks as is.
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    text: String,
+    events: &'a [Event],
+}
+
+#[tokio::main]
+async fn send_webhook(
+    url: &str,
+    events: &[Event],
+    timeout: Duration,
+) -> Result<(), reqwest::Error> {
+    let payload = WebhookPayload {
+        text: events
+            .iter()
+            .map(Event::to_string)
+            .collect::<Vec<String>>()
+            .join("\n"),
+        events,
+    };
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()?
+        .post(url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Sends the page as plain text with an HTML alternative wrapped in <pre>
+/// so that mail clients keep the columns aligned
+fn send_digest(
+    email: &EmailConfig,
+    day: &str,
+    page: &str,
+    timeout: Duration,
+) -> Result<(), NotifyError> {
+    let address = |address: &str| {
+        address
+            .parse::<Mailbox>()
+            .map_err(|e| NotifyError::Email(format!("invalid address '{}': {}", address, e)))
+    };
+    let html = format!(
+        "<pre>{}</pre>",
+        page.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    );
+    let message = Message::builder()
+        .from(address(&email.from)?)
+        .to(address(&email.to)?)
+        .subject(format!("NHL {}", day))
+        .multipart(MultiPart::alternative_plain_html(page.to_string(), html))
+        .map_err(|e| NotifyError::Email(e.to_string()))?;
+
+    let relay = if email.starttls {
+        SmtpTransport::starttls_relay(&email.smtp_host)
+    } else {
+        SmtpTransport::relay(&email.smtp_host)
+    };
+    let mut transport = relay
+        .map_err(|e| NotifyError::Email(e.to_string()))?
+        .timeout(Some(timeout));
+    if let Some(port) = email.smtp_port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = &email.username {
+        let password = match &email.password_secret {
+            Some(name) => get_secret(name).map_err(NotifyError::Secret)?,
+            None => env::var(SMTP_PASSWORD_ENV).map_err(|_| {
+                NotifyError::Email(format!(
+                    "set the SMTP password in {} or password_secret",
+                    SMTP_PASSWORD_ENV
+                ))
+            })?,
+        };
+        transport = transport.credentials(Credentials::new(username.to_string(), password));
+    }
+
+    transport
+        .build()
+        .send(&message)
+        .map(|_| ())
+        .map_err(|e| NotifyError::Email(e.to_string()))
+}
@@ -0,0 +1,370 @@
+use crate::i18n::Lang;
+use crate::stats::StatsStyle;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub const MAX_SPACING: usize = 2;
+
+/// How goals are laid out under the game header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GoalOrder {
+    /// Each team's goals in their own column, paired row by row
+    #[default]
+    Team,
+    /// One goal per row in the order they were scored
+    Time,
+}
+
+impl FromStr for GoalOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "team" => Ok(GoalOrder::Team),
+            "time" => Ok(GoalOrder::Time),
+            _ => Err(format!(
+                "unsupported goal order '{}', use one of: time, team",
+                s
+            )),
+        }
+    }
+}
+
+/// How teams are named in the game headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TeamNames {
+    /// By city, "Pittsburgh", like on the 235 page
+    #[default]
+    City,
+    /// By city and nickname, "Pittsburgh Penguins"
+    Full,
+    /// By abbreviation, "PIT", for narrow terminals and status bars
+    Abbreviation,
+}
+
+/// Widths of the columns of the page. The away team's name and its goals
+/// start at the same column, so the header and the goal rows share them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    /// Team names in the header and scorers under them
+    pub name_width: usize,
+    /// Minutes of the goals, and the gap after the home team in the header
+    pub minute_width: usize,
+    /// Score at the end of the header, "so 2-1" and "POSTP." need 6
+    pub score_width: usize,
+}
+
+impl Layout {
+    /// The 15/2/15/6 columns of the 235 page
+    pub const CLASSIC: Layout = Layout {
+        name_width: 15,
+        minute_width: 2,
+        score_width: 6,
+    };
+    /// Room for names like van Riemsdyk with initials and minutes past 100
+    /// in long playoff overtimes
+    pub const WIDE: Layout = Layout {
+        name_width: 20,
+        minute_width: 3,
+        score_width: 7,
+    };
+    /// For split panes and phones, longer names run into the next column
+    pub const NARROW: Layout = Layout {
+        name_width: 11,
+        minute_width: 2,
+        score_width: 6,
+    };
+
+    pub const NAME_WIDTHS: std::ops::RangeInclusive<usize> = 8..=30;
+    pub const MINUTE_WIDTHS: std::ops::RangeInclusive<usize> = 2..=3;
+    pub const SCORE_WIDTHS: std::ops::RangeInclusive<usize> = 6..=10;
+
+    /// Checks that every column is wide enough for what goes in it and
+    /// that the page still fits a terminal
+    pub fn validate(self) -> Result<Layout, String> {
+        let columns = [
+            ("name width", self.name_width, Layout::NAME_WIDTHS),
+            ("minute width", self.minute_width, Layout::MINUTE_WIDTHS),
+            ("score width", self.score_width, Layout::SCORE_WIDTHS),
+        ];
+        for (name, width, range) in columns.iter() {
+            if !range.contains(width) {
+                return Err(format!(
+                    "invalid layout, {} {} is not within {}-{}",
+                    name,
+                    width,
+                    range.start(),
+                    range.end()
+                ));
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::CLASSIC
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "classic" => Ok(Layout::CLASSIC),
+            "wide" => Ok(Layout::WIDE),
+            "narrow" => Ok(Layout::NARROW),
+            _ => Err(format!(
+                "unsupported layout '{}', use one of: classic, wide, narrow",
+                s
+            )),
+        }
+    }
+}
+
+/// What the scores are written out as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// The teletext page
+    #[default]
+    Page,
+    /// The parsed games as JSON
+    Json,
+    /// One row per goal, see `csv::goals_csv`
+    Csv,
+    /// A Markdown table per game, see `markdown::games_markdown`
+    Markdown,
+    /// The page as an HTML fragment, see `html::HtmlRenderer`
+    Html,
+    /// A line per game and goal separated by tabs, see `tsv::games_tsv`
+    Tsv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "page" => Ok(OutputFormat::Page),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(format!(
+                "unsupported format '{}', use one of: page, json, csv, markdown, html, tsv",
+                s
+            )),
+        }
+    }
+}
+
+/// Options for rendering the scoreboard. Build with the builder methods
+/// starting from `RenderOptions::default()`:
+///
+/// ```
+/// use nhl_235::{Lang, RenderOptions};
+///
+/// let options = RenderOptions::default()
+///     .colors(false)
+///     .highlights(vec![String::from("Barkov")])
+///     .language(Lang::Fi);
+/// assert!(!options.colors);
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RenderOptions {
+    pub colors: bool,
+    /// Last names of players to highlight and to count stats for
+    pub highlights: Vec<String>,
+    pub show_highlights: bool,
+    pub show_stats: bool,
+    pub stats_style: StatsStyle,
+    /// Tells who the points were scored against in stats added up over
+    /// several games
+    pub show_opponents: bool,
+    pub show_duration: bool,
+    pub show_venue: bool,
+    pub show_broadcasts: bool,
+    pub show_close_games: bool,
+    /// Colours the final score by how the favourite teams did, green for
+    /// a win and red for a loss
+    pub outcome_colors: bool,
+    /// Marks upsets and blowouts after the final score, with a legend
+    /// under the games
+    pub show_notable: bool,
+    /// Games that aren't followed, see `render::is_followed`, are shown on
+    /// a single line without goals
+    pub collapse_others: bool,
+    /// Only the goals scored or assisted by highlighted players are listed
+    /// under the game headers
+    pub only_highlights: bool,
+    /// Abbreviations of the user's favourite teams
+    pub favorite_teams: Vec<String>,
+    /// Scorers are shown with their first initial, "S. Crosby"
+    pub initials: bool,
+    pub team_names_style: TeamNames,
+    pub goal_order: GoalOrder,
+    pub layout: Layout,
+    /// Blank lines printed after each game, stats and series block
+    pub spacing: usize,
+    pub language: Lang,
+    /// Names to show for teams by abbreviation instead of the ones of
+    /// `language`, like "TBL" = "Tampa"
+    pub team_names: HashMap<String, String>,
+    /// Renders times in UTC instead of the local timezone so that the
+    /// same input always produces byte-identical output
+    pub deterministic: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            colors: true,
+            highlights: Vec::new(),
+            show_highlights: false,
+            show_stats: false,
+            stats_style: StatsStyle::default(),
+            show_opponents: false,
+            show_duration: false,
+            show_venue: false,
+            show_broadcasts: false,
+            show_close_games: false,
+            outcome_colors: false,
+            show_notable: false,
+            collapse_others: false,
+            only_highlights: false,
+            favorite_teams: Vec::new(),
+            initials: false,
+            team_names_style: TeamNames::default(),
+            goal_order: GoalOrder::default(),
+            layout: Layout::default(),
+            spacing: 1,
+            language: Lang::default(),
+            team_names: HashMap::new(),
+            deterministic: false,
+        }
+    }
+}
+
+impl RenderOptions {
+    pub fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn highlights(mut self, highlights: Vec<String>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    pub fn show_highlights(mut self, show_highlights: bool) -> Self {
+        self.show_highlights = show_highlights;
+        self
+    }
+
+    pub fn show_stats(mut self, show_stats: bool) -> Self {
+        self.show_stats = show_stats;
+        self
+    }
+
+    pub fn stats_style(mut self, stats_style: StatsStyle) -> Self {
+        self.stats_style = stats_style;
+        self
+    }
+
+    pub fn show_opponents(mut self, show_opponents: bool) -> Self {
+        self.show_opponents = show_opponents;
+        self
+    }
+
+    pub fn show_duration(mut self, show_duration: bool) -> Self {
+        self.show_duration = show_duration;
+        self
+    }
+
+    pub fn show_venue(mut self, show_venue: bool) -> Self {
+        self.show_venue = show_venue;
+        self
+    }
+
+    pub fn show_broadcasts(mut self, show_broadcasts: bool) -> Self {
+        self.show_broadcasts = show_broadcasts;
+        self
+    }
+
+    pub fn show_close_games(mut self, show_close_games: bool) -> Self {
+        self.show_close_games = show_close_games;
+        self
+    }
+
+    pub fn outcome_colors(mut self, outcome_colors: bool) -> Self {
+        self.outcome_colors = outcome_colors;
+        self
+    }
+
+    pub fn show_notable(mut self, show_notable: bool) -> Self {
+        self.show_notable = show_notable;
+        self
+    }
+
+    pub fn collapse_others(mut self, collapse_others: bool) -> Self {
+        self.collapse_others = collapse_others;
+        self
+    }
+
+    pub fn only_highlights(mut self, only_highlights: bool) -> Self {
+        self.only_highlights = only_highlights;
+        self
+    }
+
+    pub fn favorite_teams(mut self, favorite_teams: Vec<String>) -> Self {
+        self.favorite_teams = favorite_teams;
+        self
+    }
+
+    pub fn initials(mut self, initials: bool) -> Self {
+        self.initials = initials;
+        self
+    }
+
+    pub fn team_names_style(mut self, team_names_style: TeamNames) -> Self {
+        self.team_names_style = team_names_style;
+        self
+    }
+
+    pub fn goal_order(mut self, goal_order: GoalOrder) -> Self {
+        self.goal_order = goal_order;
+        self
+    }
+
+    /// Sets the column widths, see `Layout::validate` for checking ones
+    /// given by the user
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the number of blank lines between blocks, at most `MAX_SPACING`
+    pub fn spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing.min(MAX_SPACING);
+        self
+    }
+
+    pub fn language(mut self, language: Lang) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn team_names(mut self, team_names: HashMap<String, String>) -> Self {
+        self.team_names = team_names;
+        self
+    }
+
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+}
@@ -0,0 +1,214 @@
+//! `235 player`, every goal and assist of one player over the last days
+//! with their points added up.
+
+use crate::ascii::transliterate;
+use crate::format::format_iso_date;
+use crate::game::{Game, Goal, Player};
+use crate::i18n::Text;
+use crate::options::RenderOptions;
+use crate::render::Section;
+use crate::style::{Role, StyledLine};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PointKind {
+    Goal,
+    Assist,
+}
+
+/// A goal the player scored or assisted on
+#[derive(Serialize)]
+pub struct PlayerPoint<'a> {
+    pub date: &'a str,
+    pub kind: PointKind,
+    pub home: &'a str,
+    pub away: &'a str,
+    pub goal: &'a Goal,
+}
+
+/// The points of a player over the days looked at
+#[derive(Serialize)]
+pub struct PlayerPoints<'a> {
+    /// Name as the API writes it, or as it was asked for when the player
+    /// has no points
+    pub name: String,
+    pub team: Option<String>,
+    /// Games of the player's team that have started
+    pub games: usize,
+    pub goals: usize,
+    pub assists: usize,
+    pub points: Vec<PlayerPoint<'a>>,
+}
+
+/// Collects the goals and assists of the player called `name`, by last
+/// name or full name. Case and diacritics don't matter, so "barkov" and
+/// "Kärnä" match. Shootout goals aren't points.
+pub fn player_points<'a>(sections: &'a [Section], name: &str) -> PlayerPoints<'a> {
+    let wanted = transliterate(name).to_lowercase();
+    let matches = |player: &Player| {
+        let last = transliterate(&player.last_name).to_lowercase();
+        let full = transliterate(&format!("{} {}", player.first_name, player.last_name));
+        last == wanted || full.to_lowercase() == wanted
+    };
+
+    let mut found: Option<&Player> = None;
+    let mut points = Vec::new();
+    for section in sections {
+        for game in section.games.iter().flatten() {
+            for goal in game.goals.iter().filter(|goal| goal.period != "SO") {
+                let kind = if matches(&goal.scorer) {
+                    found = found.or(Some(&goal.scorer));
+                    PointKind::Goal
+                } else if let Some(assist) = goal.assists.iter().find(|assist| matches(assist)) {
+                    found = found.or(Some(assist));
+                    PointKind::Assist
+                } else {
+                    continue;
+                };
+                points.push(PlayerPoint {
+                    date: &section.title,
+                    kind,
+                    home: &game.home,
+                    away: &game.away,
+                    goal,
+                });
+            }
+        }
+    }
+
+    let team = found.map(|player| player.team.to_string());
+    let plays = |game: &&Game| {
+        team.as_ref()
+            .is_some_and(|team| &game.home == team || &game.away == team)
+            && (game.status == "LIVE" || game.status == "FINAL")
+    };
+    let games = sections
+        .iter()
+        .flat_map(|section| section.games.iter().flatten())
+        .filter(plays)
+        .count();
+    let count = |kind| points.iter().filter(|point| point.kind == kind).count();
+    PlayerPoints {
+        name: found.map_or_else(
+            || name.to_string(),
+            |player| format!("{} {}", player.first_name, player.last_name),
+        ),
+        team,
+        games,
+        goals: count(PointKind::Goal),
+        assists: count(PointKind::Assist),
+        points,
+    }
+}
+
+/// The player's name and team, a line per point with its date and the
+/// points added up like "5 GP  3+4=7"
+pub fn player_lines(player: &PlayerPoints, options: &RenderOptions) -> Vec<StyledLine> {
+    let title = match &player.team {
+        Some(team) => format!("{} ({})", player.name, team),
+        None => player.name.to_string(),
+    };
+    let mut lines = vec![StyledLine::styled(title, Role::Title)];
+    if player.points.is_empty() {
+        lines.push(StyledLine::styled(
+            options.language.text(Text::NoPoints),
+            Role::Text,
+        ));
+        return lines;
+    }
+
+    for point in &player.points {
+        let mut line = StyledLine::styled(
+            format!("{:<7} ", format_iso_date(point.date, options.language)),
+            Role::Info,
+        );
+        line.push(format!("{}-{} ", point.home, point.away), Role::Header);
+        let goal = point.goal;
+        let mut text = format!("{:>2} {}", goal.minute, goal.scorer.last_name);
+        if !goal.assists.is_empty() {
+            let assists: Vec<&str> = goal
+                .assists
+                .iter()
+                .map(|assist| assist.last_name.as_str())
+                .collect();
+            text.push_str(&format!(" ({})", assists.join(", ")));
+        }
+        let role = match point.kind {
+            PointKind::Goal => Role::Highlight,
+            PointKind::Assist => Role::Goal,
+        };
+        line.push(text, role);
+        lines.push(line);
+    }
+
+    lines.push(StyledLine::default());
+    lines.push(StyledLine::styled(
+        format!(
+            "{} {}  {}+{}={}",
+            player.games,
+            options.language.text(Text::GamesPlayed),
+            player.goals,
+            player.assists,
+            player.goals + player.assists
+        ),
+        Role::Highlight,
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::APIResponse;
+    use crate::game::parse_games;
+
+    fn sections() -> Vec<Section> {
+        let scores: APIResponse =
+            serde_json::from_str(include_str!("../tests/fixtures/live.json")).unwrap();
+        vec![Section {
+            title: String::from("2024-10-16"),
+            games: parse_games(scores),
+        }]
+    }
+
+    #[test]
+    fn it_finds_goals_and_assists_of_a_player() {
+        let sections = sections();
+        let malkin = player_points(&sections, "malkin");
+
+        assert_eq!(malkin.name, "Evgeni Malkin");
+        assert_eq!(malkin.team.as_deref(), Some("PIT"));
+        assert_eq!((malkin.goals, malkin.assists, malkin.games), (1, 1, 1));
+        assert_eq!(malkin.points[0].kind, PointKind::Assist);
+        assert_eq!(malkin.points[1].goal.minute, 42);
+
+        let options = RenderOptions::default().deterministic(true);
+        let lines: Vec<String> = player_lines(&malkin, &options)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "Evgeni Malkin (PIT)",
+                "Oct 16  PIT-TOR 32 Crosby (Malkin, Karlsson)",
+                "Oct 16  PIT-TOR 42 Malkin (Crosby)",
+                "",
+                "1 GP  1+1=2",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_says_when_the_player_has_no_points() {
+        let sections = sections();
+        let nobody = player_points(&sections, "Barkov");
+        assert_eq!(nobody.name, "Barkov");
+        assert_eq!(nobody.games, 0);
+
+        let options = RenderOptions::default().deterministic(true);
+        let lines = player_lines(&nobody, &options);
+        assert_eq!(lines[1].text(), "No goals or assists.");
+    }
+}
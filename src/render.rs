@@ -0,0 +1,1434 @@
+use crate::events::Event;
+use crate::format::{format_iso_date, format_time};
+use crate::game::{
+    estimate_end_time, is_close_game, live_shootout_score, notable_results, power_play, Game, Goal,
+    Notable, Special, Venue, SHOOTOUT_MINUTE,
+};
+use crate::i18n::{Lang, Text};
+use crate::links::{game_url, team_url};
+use crate::options::{GoalOrder, Layout, RenderOptions, TeamNames};
+use crate::roster::RosterPlayer;
+use crate::standings::{Division, Standing};
+use crate::stats::{craft_opponent_stats_message, craft_stats_message};
+use crate::style::{Role, StyledLine};
+use crate::teams::{localized_team_name, Team, TeamListing};
+use chrono::Local;
+use itertools::{EitherOrBoth::*, Itertools};
+use std::fmt::Write;
+
+/// Colours of the teletext palette used on the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    White,
+    Green,
+    Red,
+    Yellow,
+    Cyan,
+    Magenta,
+}
+
+impl Colour {
+    /// Colour of a role on the teletext page, None for unstyled text
+    pub fn of(role: Role) -> Option<Colour> {
+        match role {
+            Role::Text => None,
+            Role::Header | Role::Score | Role::Info => Some(Colour::White),
+            Role::FinalScore | Role::PlayoffSpot => Some(Colour::Green),
+            Role::Loss | Role::CloseGame => Some(Colour::Red),
+            Role::Goal => Some(Colour::Cyan),
+            Role::SpecialGoal => Some(Colour::Magenta),
+            Role::Title | Role::Highlight | Role::Notice | Role::SeriesInfo => Some(Colour::Yellow),
+        }
+    }
+}
+
+/// Writes out the 235 page. The layout is built as styled lines by
+/// `page_lines` and friends, implementors only decide how text in each
+/// role is presented so that every output lines up the same way.
+pub trait Renderer {
+    fn paint(&self, text: &str, role: Role) -> String;
+
+    /// Appends `text` painted for `role` to `output`. Renderers can
+    /// override this to write into the page without a string per piece.
+    fn paint_into(&self, output: &mut String, text: &str, role: Role) {
+        output.push_str(&self.paint(text, role));
+    }
+
+    /// Whether `link_into` writes links. Renderers without them paint a
+    /// link and its neighbours in the same role as one piece.
+    fn links(&self) -> bool {
+        false
+    }
+
+    /// Appends `text` painted for `role` as a link to `url`. Renderers
+    /// without links paint the text alone.
+    fn link_into(&self, output: &mut String, text: &str, role: Role, _url: &str) {
+        self.paint_into(output, text, role);
+    }
+
+    /// Writes out styled lines, each ending in a newline
+    fn render_lines(&self, lines: &[StyledLine]) -> String {
+        let mut output = String::new();
+        let mut run = String::new();
+        for line in lines {
+            let mut spans = line.spans.iter().peekable();
+            while let Some(span) = spans.next() {
+                match &span.link {
+                    Some(url) if self.links() => {
+                        self.link_into(&mut output, &span.text, span.role, url)
+                    }
+                    _ => {
+                        run.push_str(&span.text);
+                        let joins_next = !self.links()
+                            && spans.peek().is_some_and(|next| {
+                                next.role == span.role
+                                    && (span.link.is_some() || next.link.is_some())
+                            });
+                        if !joins_next {
+                            self.paint_into(&mut output, &run, span.role);
+                            run.clear();
+                        }
+                    }
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Renders all games in the teletext layout
+    fn render_games(&self, games: &[Option<Game>], options: &RenderOptions) -> String {
+        self.render_lines(&page_lines(games, options))
+    }
+
+    /// Renders the teams and the score of a game on one line
+    fn render_summary(&self, game: &Game, options: &RenderOptions) -> String {
+        self.render_lines(&[summary_line(game, options)])
+    }
+
+    /// Renders a single game in the teletext layout
+    fn render_game(&self, game: &Game, options: &RenderOptions) -> String {
+        self.render_lines(&game_lines(game, options))
+    }
+
+    /// Renders several game days one after another under their titles.
+    /// With stats enabled the stats of all days are added up in a footer.
+    fn render_sections(&self, sections: &[Section], options: &RenderOptions) -> String {
+        self.render_lines(&sections_lines(sections, options))
+    }
+
+    /// Renders a team's roster under the team's name, one player per row
+    /// with number, name, position and nationality
+    fn render_roster(&self, team: &Team, players: &[RosterPlayer]) -> String {
+        self.render_lines(&roster_lines(team, players))
+    }
+
+    /// Renders the standings one division or conference at a time, each
+    /// team with games played, wins, losses, overtime losses and points
+    fn render_standings(&self, divisions: &[Division], options: &RenderOptions) -> String {
+        self.render_lines(&standings_lines(divisions, options))
+    }
+}
+
+/// All games in the teletext layout
+pub fn page_lines(games: &[Option<Game>], options: &RenderOptions) -> Vec<StyledLine> {
+    if games.is_empty() {
+        return vec![StyledLine::styled(
+            options.language.text(Text::NoGames),
+            Role::Text,
+        )];
+    }
+
+    // Collapsed games are stacked one per line with the gap after the run
+    let mut lines = Vec::new();
+    let mut collapsed_run = false;
+    for game in games.iter().flatten() {
+        if options.collapse_others && !is_followed(game, options) {
+            lines.push(summary_line(game, options));
+            collapsed_run = true;
+        } else {
+            if collapsed_run {
+                push_gap(&mut lines, options);
+            }
+            lines.extend(game_lines(game, options));
+            collapsed_run = false;
+        }
+    }
+    if collapsed_run {
+        push_gap(&mut lines, options);
+    }
+    if let Some(legend) = notable_legend(games, options) {
+        lines.push(StyledLine::styled(legend, Role::Notice));
+    }
+    lines
+}
+
+/// Blank lines between blocks, as many as `RenderOptions::spacing`
+fn push_gap(lines: &mut Vec<StyledLine>, options: &RenderOptions) {
+    lines.extend(std::iter::repeat_n(StyledLine::default(), options.spacing));
+}
+
+/// The teams and the score of a game
+pub fn summary_line(game: &Game, options: &RenderOptions) -> StyledLine {
+    let mut line = header_line(game, options);
+    if let Some(score) = score_text(game, options) {
+        // The padding in front of the score isn't part of the link
+        let trimmed = score.trim_start();
+        let role = score_role(game, options);
+        line.push(&score[..score.len() - trimmed.len()], role);
+        line.push_link(trimmed, role, game_url(game));
+    }
+    if let Some(power_play) = power_play_text(game, options) {
+        line.push(power_play, Role::Notice);
+    }
+    if let Some(markers) = notable_text(game, options) {
+        line.push(markers, Role::Notice);
+    }
+    line
+}
+
+/// A single game in the teletext layout
+pub fn game_lines(game: &Game, options: &RenderOptions) -> Vec<StyledLine> {
+    let mut lines = vec![summary_line(game, options)];
+
+    let info_lines = vec![
+        (annotation_line(game, options), Role::Notice),
+        (shootout_line(game, options), Role::Info),
+        (venue_line(game, options), Role::Info),
+        (broadcasts_line(game, options), Role::Info),
+    ];
+    for (line, role) in info_lines {
+        if let Some(line) = line {
+            lines.push(StyledLine::styled(line, role));
+        }
+    }
+
+    for (home, away) in goal_rows(game, options) {
+        lines.push(goal_row_line(home, away, options));
+    }
+
+    if let Some(duration) = duration_line(game, options) {
+        lines.push(StyledLine::styled(duration, Role::Info));
+    }
+    // Game, stats and series blocks are all followed by the same gap
+    push_gap(&mut lines, options);
+
+    if let Some(stats) = stats_line(game, options) {
+        let role = if options.show_highlights {
+            Role::Highlight
+        } else {
+            Role::Info
+        };
+        lines.push(StyledLine::styled(stats, role));
+        push_gap(&mut lines, options);
+    }
+
+    if let Some(series) = series_line(game, options) {
+        lines.push(StyledLine::styled(series, Role::SeriesInfo));
+        push_gap(&mut lines, options);
+    }
+
+    lines
+}
+
+/// Banner over the watch mode page with the latest goal, like
+/// "GOAL 58' Crosby — PIT 3-2 TOR". None for other events.
+pub fn goal_banner(event: &Event, options: &RenderOptions) -> Option<StyledLine> {
+    match event {
+        Event::Goal {
+            home,
+            away,
+            scorer,
+            minute,
+            score,
+            ..
+        } => Some(StyledLine::styled(
+            format!(
+                "{} {}' {} — {} {} {}",
+                options.language.text(Text::Goal),
+                minute,
+                scorer,
+                home,
+                score,
+                away
+            ),
+            Role::Notice,
+        )),
+        _ => None,
+    }
+}
+
+/// Several game days one after another under their titles, with the
+/// stats of all days added up in a footer. Days without games aren't
+/// returned by the API, so a range without any reads like an empty day.
+pub fn sections_lines(sections: &[Section], options: &RenderOptions) -> Vec<StyledLine> {
+    if sections.is_empty() {
+        return page_lines(&[], options);
+    }
+    let mut lines = Vec::new();
+    for section in sections {
+        let title = format_iso_date(&section.title, options.language);
+        lines.push(StyledLine::styled(title, Role::Title));
+        lines.extend(page_lines(&section.games, options));
+    }
+
+    if let Some(total) = total_stats_line(sections, options) {
+        lines.push(StyledLine::styled(total, Role::Highlight));
+    }
+
+    lines
+}
+
+/// A team's roster under the team's name
+pub fn roster_lines(team: &Team, players: &[RosterPlayer]) -> Vec<StyledLine> {
+    let mut lines = vec![StyledLine::styled(team.full_name(), Role::Title)];
+    lines.extend(
+        players
+            .iter()
+            .map(|player| StyledLine::styled(roster_row(player), Role::Info)),
+    );
+    lines
+}
+
+/// A line per team with the abbreviation, the name on the page, the full
+/// name and the short names it's also known by
+pub fn teams_lines(teams: &[TeamListing]) -> Vec<StyledLine> {
+    teams
+        .iter()
+        .map(|team| {
+            let mut line = StyledLine::styled(format!("{:<5}", team.abbreviation), Role::Header);
+            line.push(format!("{:<14}", team.name), Role::Info);
+            if team.aliases.is_empty() {
+                line.push(team.full_name.as_str(), Role::Info);
+            } else {
+                line.push(format!("{:<24}", team.full_name), Role::Info);
+                line.push(team.aliases.join(", "), Role::Text);
+            }
+            line
+        })
+        .collect()
+}
+
+/// The standings one division or conference at a time, the teams in a
+/// playoff spot in their own colour
+pub fn standings_lines(divisions: &[Division], options: &RenderOptions) -> Vec<StyledLine> {
+    let mut lines = Vec::new();
+    for (i, division) in divisions.iter().enumerate() {
+        if i > 0 {
+            lines.push(StyledLine::default());
+        }
+        lines.push(StyledLine::styled(
+            table_title(&division.name, options.language),
+            Role::Title,
+        ));
+        for (rank, standing) in division.teams.iter().enumerate() {
+            let role = if standing.playoff_spot {
+                Role::PlayoffSpot
+            } else {
+                Role::Info
+            };
+            lines.push(StyledLine::styled(
+                standing_row(rank + 1, standing, options),
+                role,
+            ));
+        }
+    }
+    lines
+}
+
+/// Name of a division or a conference in the language of the page
+fn table_title(name: &str, lang: Lang) -> &str {
+    let text = match name {
+        "Atlantic" => Text::Atlantic,
+        "Metropolitan" => Text::Metropolitan,
+        "Central" => Text::Central,
+        "Pacific" => Text::Pacific,
+        "Eastern" => Text::Eastern,
+        "Western" => Text::Western,
+        _ => return name,
+    };
+    lang.text(text)
+}
+
+/// One row of goals, the home team's on the left and the away team's on
+/// the right
+pub fn goal_row_line(
+    home: Option<&Goal>,
+    away: Option<&Goal>,
+    options: &RenderOptions,
+) -> StyledLine {
+    let mut line = StyledLine::default();
+    match (home, away) {
+        (Some(home), Some(away)) => {
+            let mut cell = goal_cell(home, options);
+            cell.push(' ');
+            line.push(cell, goal_role(home, options));
+            line.push(goal_cell(away, options), goal_role(away, options));
+        }
+        (Some(home), None) => line.push(goal_cell(home, options), goal_role(home, options)),
+        (None, Some(away)) => {
+            let mut cell = empty_cell(&options.layout);
+            write_goal_cell(&mut cell, away, options);
+            line.push(cell, goal_role(away, options));
+        }
+        (None, None) => {}
+    }
+    line
+}
+
+/// Games of one day on a page with several days
+pub struct Section {
+    /// Date as YYYY-MM-DD, printed in the format of the language
+    pub title: String,
+    pub games: Vec<Option<Game>>,
+}
+
+/// Renders the page as plain text
+pub struct PlainRenderer;
+
+impl Renderer for PlainRenderer {
+    fn paint(&self, text: &str, _role: Role) -> String {
+        text.to_string()
+    }
+
+    fn paint_into(&self, output: &mut String, text: &str, _role: Role) {
+        output.push_str(text);
+    }
+}
+
+/// Renders the page with ANSI 256-colour escape codes in the teletext
+/// palette, see `Colour::of`
+pub struct AnsiRenderer;
+
+impl Renderer for AnsiRenderer {
+    fn paint(&self, text: &str, role: Role) -> String {
+        let mut output = String::new();
+        self.paint_into(&mut output, text, role);
+        output
+    }
+
+    fn paint_into(&self, output: &mut String, text: &str, role: Role) {
+        match Colour::of(role) {
+            // Writing to a String can't fail
+            Some(colour) => {
+                let _ = write!(output, "\x1b[38;5;{}m{}\x1b[0m", ansi_code(colour), text);
+            }
+            None => output.push_str(text),
+        }
+    }
+}
+
+/// Renders the page like `AnsiRenderer` with team names and scores as
+/// OSC 8 hyperlinks, for terminals that support them
+pub struct HyperlinkRenderer;
+
+impl Renderer for HyperlinkRenderer {
+    fn paint(&self, text: &str, role: Role) -> String {
+        AnsiRenderer.paint(text, role)
+    }
+
+    fn paint_into(&self, output: &mut String, text: &str, role: Role) {
+        AnsiRenderer.paint_into(output, text, role);
+    }
+
+    fn links(&self) -> bool {
+        true
+    }
+
+    fn link_into(&self, output: &mut String, text: &str, role: Role, url: &str) {
+        let _ = write!(output, "\x1b]8;;{}\x1b\\", url);
+        self.paint_into(output, text, role);
+        output.push_str("\x1b]8;;\x1b\\");
+    }
+}
+
+fn ansi_code(colour: Colour) -> u8 {
+    match colour {
+        Colour::White => 15,
+        Colour::Cyan => 14,
+        Colour::Magenta => 13,
+        Colour::Yellow => 11,
+        Colour::Green => 10,
+        Colour::Red => 9,
+    }
+}
+
+/// Renders all games as plain text in the teletext layout
+pub fn render_games(games: &[Option<Game>], options: &RenderOptions) -> String {
+    PlainRenderer.render_games(games, options)
+}
+
+/// Renders a single game as plain text in the teletext layout
+pub fn render_game(game: &Game, options: &RenderOptions) -> String {
+    PlainRenderer.render_game(game, options)
+}
+
+/// Games of a favourite team or with points by a highlighted player
+pub fn is_followed(game: &Game, options: &RenderOptions) -> bool {
+    options.favorite_teams.contains(&game.home)
+        || options.favorite_teams.contains(&game.away)
+        || game
+            .goals
+            .iter()
+            .any(|goal| has_highlighted_player(goal, options))
+}
+
+/// Goal scored or assisted by a highlighted player
+fn has_highlighted_player(goal: &Goal, options: &RenderOptions) -> bool {
+    std::iter::once(&goal.scorer)
+        .chain(goal.assists.iter())
+        .any(|player| options.highlights.contains(&player.last_name))
+}
+
+fn score_role(game: &Game, options: &RenderOptions) -> Role {
+    match game.status.as_str() {
+        "LIVE" if options.show_close_games && is_close_game(game) => Role::CloseGame,
+        "FINAL" if options.outcome_colors => outcome_role(game, &options.favorite_teams),
+        "FINAL" => Role::FinalScore,
+        _ => Role::Score,
+    }
+}
+
+/// A loss if a favourite team lost, otherwise a regular final score. Games
+/// without a favourite, or with favourites on both sides, aren't losses.
+fn outcome_role(game: &Game, favorites: &[String]) -> Role {
+    let home_won = game.home_score > game.away_score;
+    match (
+        favorites.contains(&game.home),
+        favorites.contains(&game.away),
+    ) {
+        (true, false) if !home_won => Role::Loss,
+        (false, true) if home_won => Role::Loss,
+        _ => Role::FinalScore,
+    }
+}
+
+pub(crate) fn goal_role(goal: &Goal, options: &RenderOptions) -> Role {
+    if goal.special {
+        Role::SpecialGoal
+    } else if options.show_highlights && options.highlights.contains(&goal.scorer.last_name) {
+        Role::Highlight
+    } else {
+        Role::Goal
+    }
+}
+
+/// Team names part of the header line, the score is printed right after it
+pub fn header(game: &Game, options: &RenderOptions) -> String {
+    header_line(game, options).text()
+}
+
+/// The header with the team names linking to the teams' pages
+fn header_line(game: &Game, options: &RenderOptions) -> StyledLine {
+    let (home, away) = game_team_names(game, options);
+    let width = match options.team_names_style {
+        TeamNames::City => options.layout.name_width,
+        TeamNames::Full => FULL_NAME_WIDTH,
+        TeamNames::Abbreviation => 3,
+    };
+    let padding = |name: &str| " ".repeat(width.saturating_sub(name.chars().count()));
+    let minute = options.layout.minute_width;
+    let mut line = StyledLine::default();
+    line.push_link(home.as_str(), Role::Header, team_url(&game.home));
+    line.push(
+        format!("{} {:>minute$} ", padding(&home), '-', minute = minute),
+        Role::Header,
+    );
+    line.push_link(away.as_str(), Role::Header, team_url(&game.away));
+    line.push(
+        format!("{} {:<minute$} ", padding(&away), "", minute = minute),
+        Role::Header,
+    );
+    line
+}
+
+/// Width of the team names in the header with `TeamNames::Full`,
+/// enough for "Columbus Blue Jackets"
+const FULL_NAME_WIDTH: usize = 21;
+
+/// Names of the home and away teams of a game in the style of
+/// `RenderOptions::team_names_style`. Names of the user's own are used
+/// unless the headers only show abbreviations.
+pub fn game_team_names(game: &Game, options: &RenderOptions) -> (String, String) {
+    let name = |abbr: &str, full_name: &str| match options.team_names_style {
+        TeamNames::Abbreviation => abbr.to_string(),
+        _ if options.team_names.contains_key(abbr) => options.team_names[abbr].clone(),
+        TeamNames::Full if !full_name.is_empty() => full_name.to_string(),
+        _ => team_name(abbr, options),
+    };
+    (
+        name(&game.home, &game.home_name),
+        name(&game.away, &game.away_name),
+    )
+}
+
+/// Name of a team in the language of the page unless the user has named
+/// it themselves
+pub fn team_name(abbr: &str, options: &RenderOptions) -> String {
+    match options.team_names.get(abbr) {
+        Some(name) => name.clone(),
+        None => localized_team_name(abbr, options.language),
+    }
+}
+
+/// Score part of the header line or None for games that haven't started
+pub fn score_text(game: &Game, options: &RenderOptions) -> Option<String> {
+    let score = match game.status.as_str() {
+        "LIVE" if options.show_close_games && is_close_game(game) => {
+            format!("! {}", game.score)
+        }
+        "LIVE" => game.score.to_string(),
+        "FINAL" => format!("{} {}", options.language.special(game.special), game.score),
+        "POSTPONED" => options.language.text(Text::Postponed).to_string(),
+        _ => return None,
+    };
+    Some(format!(
+        "{:>width$}",
+        score,
+        width = options.layout.score_width
+    ))
+}
+
+/// Power play indicator printed after a live score, like " PP TOR 1:23"
+pub fn power_play_text(game: &Game, options: &RenderOptions) -> Option<String> {
+    let power_play = power_play(game)?;
+    Some(format!(
+        " {} {} {}",
+        options.language.text(Text::PowerPlay),
+        power_play.team,
+        power_play.time_remaining
+    ))
+}
+
+fn notable_marker(notable: Notable) -> char {
+    match notable {
+        Notable::Upset => '^',
+        Notable::Blowout => '*',
+    }
+}
+
+/// Markers printed after a final score, like " ^*" for an upset blowout
+pub fn notable_text(game: &Game, options: &RenderOptions) -> Option<String> {
+    if !options.show_notable {
+        return None;
+    }
+    let markers: String = notable_results(game)
+        .into_iter()
+        .map(notable_marker)
+        .collect();
+    if markers.is_empty() {
+        None
+    } else {
+        Some(format!(" {}", markers))
+    }
+}
+
+/// Explains the markers used on the page, like "^ upset  * blowout"
+pub fn notable_legend(games: &[Option<Game>], options: &RenderOptions) -> Option<String> {
+    if !options.show_notable {
+        return None;
+    }
+    let used: Vec<Notable> = games.iter().flatten().flat_map(notable_results).collect();
+    let legend: Vec<String> = [
+        (Notable::Upset, Text::Upset),
+        (Notable::Blowout, Text::Blowout),
+    ]
+    .iter()
+    .filter(|(notable, _)| used.contains(notable))
+    .map(|(notable, text)| {
+        format!(
+            "{} {}",
+            notable_marker(*notable),
+            options.language.text(*text)
+        )
+    })
+    .collect();
+    if legend.is_empty() {
+        None
+    } else {
+        Some(legend.join("  "))
+    }
+}
+
+/// Lays the goals out into (home, away) rows. With `GoalOrder::Team` each
+/// team's goals are listed in their own column in the order they were scored,
+/// with `GoalOrder::Time` every goal gets its own row by the minute it was
+/// scored, leaving the other team's column blank like on the real 235 page.
+/// With `RenderOptions::only_highlights` only the goals highlighted players
+/// scored or assisted are laid out.
+pub fn goal_rows<'a>(
+    game: &'a Game,
+    options: &RenderOptions,
+) -> Vec<(Option<&'a Goal>, Option<&'a Goal>)> {
+    // Shootout goals aren't listed apart from the game winner
+    let overtime = matches!(game.special, Special::Overtime { .. });
+    let shown = |goal: &&Goal| {
+        (goal.minute != SHOOTOUT_MINUTE || overtime)
+            && (!options.only_highlights || has_highlighted_player(goal, options))
+    };
+
+    let mut rows: Vec<(Option<&Goal>, Option<&Goal>)> = match options.goal_order {
+        GoalOrder::Team => {
+            let home_scores = game
+                .goals
+                .iter()
+                .filter(shown)
+                .filter(|goal| goal.team == game.home);
+            let away_scores = game
+                .goals
+                .iter()
+                .filter(shown)
+                .filter(|goal| goal.team == game.away);
+
+            home_scores
+                .zip_longest(away_scores)
+                .map(|pair| match pair {
+                    Both(home, away) => (Some(home), Some(away)),
+                    Left(home) => (Some(home), None),
+                    Right(away) => (None, Some(away)),
+                })
+                .collect()
+        }
+        // The API usually lists goals in the order they were scored but
+        // sorting by the minute makes sure rows follow the flow of the game.
+        // The sort is stable so goals scored within the same minute keep their order.
+        GoalOrder::Time => game
+            .goals
+            .iter()
+            .filter(shown)
+            .sorted_by_key(|goal| goal.minute)
+            .filter_map(|goal| team_column(game, goal))
+            .collect(),
+    };
+
+    // Game-winning shootout goal is always on its own line because
+    // the game must be tied before it so it's safe to print it after everything.
+    // If we later add assists by Finns, this needs to be rewritten.
+    // While the shootout is still going on there's no winner to show yet.
+    if game.special == Special::Shootout && game.status == "FINAL" {
+        let winner = game
+            .goals
+            .last()
+            .filter(|goal| !options.only_highlights || has_highlighted_player(goal, options));
+        if let Some(row) = winner.and_then(|goal| team_column(game, goal)) {
+            rows.push(row);
+        }
+    }
+
+    rows
+}
+
+/// Places a goal in its team's column
+fn team_column<'a>(game: &Game, goal: &'a Goal) -> Option<(Option<&'a Goal>, Option<&'a Goal>)> {
+    if goal.team == game.home {
+        Some((Some(goal), None))
+    } else if goal.team == game.away {
+        Some((None, Some(goal)))
+    } else {
+        None
+    }
+}
+
+/// Scorer and minute of a goal, padded to the width of a column
+pub fn goal_cell(goal: &Goal, options: &RenderOptions) -> String {
+    let mut cell = String::with_capacity(goal_cell_width(&options.layout));
+    write_goal_cell(&mut cell, goal, options);
+    cell
+}
+
+/// Width of `goal_cell`, the name column, a space and the minute
+fn goal_cell_width(layout: &Layout) -> usize {
+    layout.name_width + 1 + layout.minute_width
+}
+
+/// Blank home column and the space after it in front of an away goal
+fn empty_cell(layout: &Layout) -> String {
+    " ".repeat(goal_cell_width(layout) + 1)
+}
+
+/// Appends the goal cell to `output` without building the name on its
+/// own first, goal rows are the bulk of a page
+fn write_goal_cell(output: &mut String, goal: &Goal, options: &RenderOptions) {
+    let start = output.len();
+    write_scorer_name(output, goal, options);
+    let name_width = output[start..].chars().count();
+    let layout = &options.layout;
+    output.extend(std::iter::repeat_n(
+        ' ',
+        layout.name_width.saturating_sub(name_width),
+    ));
+    // Writing to a String can't fail
+    let _ = write!(
+        output,
+        " {:>width$}",
+        goal.minute,
+        width = layout.minute_width
+    );
+}
+
+/// Last name of the scorer, with the first initial like "S. Crosby"
+/// when `RenderOptions::initials` is set
+pub fn scorer_name(goal: &Goal, options: &RenderOptions) -> String {
+    let mut name = String::new();
+    write_scorer_name(&mut name, goal, options);
+    name
+}
+
+/// Appends `scorer_name` to `output`
+fn write_scorer_name(output: &mut String, goal: &Goal, options: &RenderOptions) {
+    let scorer = &goal.scorer;
+    match scorer.first_name.chars().next() {
+        Some(initial) if options.initials && !scorer.last_name.is_empty() => {
+            output.push(initial);
+            output.push_str(". ");
+        }
+        _ => {}
+    }
+    output.push_str(&scorer.last_name);
+}
+
+pub fn standing_row(rank: usize, standing: &Standing, options: &RenderOptions) -> String {
+    format!(
+        "{:>2}. {:<15} {:>2} {:>2} {:>2} {:>2} {:>3}",
+        rank,
+        team_name(&standing.team, options),
+        standing.games_played,
+        standing.wins,
+        standing.losses,
+        standing.ot_losses,
+        standing.points
+    )
+}
+
+pub fn roster_row(player: &RosterPlayer) -> String {
+    let number = player
+        .number
+        .map(|number| number.to_string())
+        .unwrap_or_default();
+    let name = format!("{} {}", player.first_name, player.last_name);
+    let row = format!(
+        "{:>2} {:<24} {:<1} {}",
+        number,
+        name,
+        player.position,
+        player.nationality.as_deref().unwrap_or("")
+    );
+    row.trim_end().to_string()
+}
+
+/// Returns a short annotation for games played at special venues
+/// like the Winter Classic or the Global Series games in Europe
+fn venue_annotation(venue: &Venue, lang: Lang) -> Option<String> {
+    let neutral_site = lang.text(Text::NeutralSite);
+    match (&venue.special_event, venue.neutral_site) {
+        (Some(event), _) => Some(format!("{}, {}", event, venue.name)),
+        (None, true) => match &venue.city {
+            Some(city) => Some(format!("{}, {}, {}", neutral_site, venue.name, city)),
+            None => Some(format!("{}, {}", neutral_site, venue.name)),
+        },
+        (None, false) => None,
+    }
+}
+
+/// Running shootout score while the shootout is still going on
+pub fn shootout_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    let (home, away) = live_shootout_score(game)?;
+    Some(format!(
+        "{} {}-{}",
+        options.language.text(Text::ShootoutInProgress),
+        home,
+        away
+    ))
+}
+
+pub fn annotation_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    venue_annotation(game.venue.as_ref()?, options.language)
+}
+
+/// Arena and city for scheduled and live games. Special venue annotation
+/// already names the arena so it replaces the venue line.
+pub fn venue_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    let venue = game.venue.as_ref()?;
+    let show_venue = options.show_venue && (game.status == "LIVE" || game.status == "PREVIEW");
+    if !show_venue || venue_annotation(venue, options.language).is_some() {
+        return None;
+    }
+
+    match &venue.city {
+        Some(city) => Some(format!("{}, {}", venue.name, city)),
+        None => Some(venue.name.to_string()),
+    }
+}
+
+pub fn broadcasts_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    if options.show_broadcasts && game.status == "PREVIEW" && !game.broadcasts.is_empty() {
+        Some(format!("TV: {}", game.broadcasts.join(", ")))
+    } else {
+        None
+    }
+}
+
+pub fn duration_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    if !options.show_duration {
+        return None;
+    }
+    let length = game.length.as_ref()?;
+    let lang = options.language;
+    let mut message = format!(
+        "{} {}:{:02}",
+        lang.text(Text::Length),
+        length.seconds / 60,
+        length.seconds % 60
+    );
+    if let Some(end_time) = estimate_end_time(&game.start_time, length) {
+        let end_time = if options.deterministic {
+            format!("{} UTC", format_time(end_time.time(), lang))
+        } else {
+            format_time(end_time.with_timezone(&Local).time(), lang)
+        };
+        message = format!("{}, {} ~{}", message, lang.text(Text::Ended), end_time);
+    }
+
+    Some(message)
+}
+
+pub fn stats_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    if options.show_stats && !options.highlights.is_empty() {
+        craft_stats_message(&game.goals, &options.highlights, &options.stats_style)
+    } else {
+        None
+    }
+}
+
+/// Stats of the highlighted players over all sections
+pub fn total_stats_line(sections: &[Section], options: &RenderOptions) -> Option<String> {
+    if !options.show_stats || options.highlights.is_empty() {
+        return None;
+    }
+    let games: Vec<&Game> = sections
+        .iter()
+        .flat_map(|section| section.games.iter().flatten())
+        .collect();
+    let stats = if options.show_opponents {
+        craft_opponent_stats_message(&games, &options.highlights, &options.stats_style)?
+    } else {
+        let goals: Vec<Goal> = games
+            .iter()
+            .flat_map(|game| game.goals.iter().cloned())
+            .collect();
+        craft_stats_message(&goals, &options.highlights, &options.stats_style)?
+    };
+
+    Some(format!("{} {}", options.language.text(Text::Total), stats))
+}
+
+pub fn series_line(game: &Game, options: &RenderOptions) -> Option<String> {
+    let playoff_series = game.playoff_series.as_ref()?;
+    let series_wins = playoff_series.get("wins")?;
+    let home_wins = &series_wins[&game.home];
+    let away_wins = &series_wins[&game.away];
+
+    Some(format!(
+        "{} {}-{}",
+        options.language.text(Text::Series),
+        home_wins,
+        away_wins
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_annotates_special_venues() {
+        let regular = Venue {
+            name: String::from("PPG Paints Arena"),
+            city: Some(String::from("Pittsburgh")),
+            neutral_site: false,
+            special_event: None,
+        };
+        let global_series = Venue {
+            name: String::from("Nokia Arena"),
+            city: Some(String::from("Tampere")),
+            neutral_site: true,
+            special_event: None,
+        };
+        let winter_classic = Venue {
+            name: String::from("Wrigley Field"),
+            city: Some(String::from("Chicago")),
+            neutral_site: true,
+            special_event: Some(String::from("Winter Classic")),
+        };
+
+        assert_eq!(venue_annotation(&regular, Lang::En), None);
+        assert_eq!(
+            venue_annotation(&global_series, Lang::En),
+            Some(String::from("Neutral site, Nokia Arena, Tampere"))
+        );
+        assert_eq!(
+            venue_annotation(&winter_classic, Lang::En),
+            Some(String::from("Winter Classic, Wrigley Field"))
+        );
+    }
+
+    #[test]
+    fn it_names_teams_in_the_language_of_the_page() {
+        let english = RenderOptions::default();
+        let finnish = RenderOptions::default().language(Lang::Fi);
+        assert_eq!(team_name("TBL", &english), "Tampa Bay");
+        assert_eq!(team_name("TBL", &finnish), "Tampa");
+        assert_eq!(team_name("NYR", &finnish), "NY Rangers");
+
+        let mut names = std::collections::HashMap::new();
+        names.insert(String::from("TBL"), String::from("Lightning"));
+        let renamed = finnish.team_names(names);
+        assert_eq!(team_name("TBL", &renamed), "Lightning");
+        assert_eq!(team_name("PIT", &renamed), "Pittsburgh");
+    }
+
+    #[test]
+    fn it_shows_full_names_in_the_header() {
+        let json = include_str!("../tests/fixtures/live.json");
+        let scores: crate::api_types::APIResponse = serde_json::from_str(json).unwrap();
+        let games = crate::game::parse_games(scores);
+        let game = games[0].as_ref().unwrap();
+
+        let options = RenderOptions::default().team_names_style(TeamNames::Full);
+        assert_eq!(
+            header(game, &options),
+            format!("{:<21}  - {:<21}    ", game.home_name, game.away_name)
+        );
+        assert_eq!(game_team_names(game, &options).0, "Pittsburgh Penguins");
+
+        let options = RenderOptions::default().team_names_style(TeamNames::Abbreviation);
+        assert_eq!(header(game, &options), "PIT  - TOR    ");
+    }
+
+    #[test]
+    fn it_renders_a_game_as_plain_text() -> serde_json::Result<()> {
+        let test_game: crate::api_types::GameResponse = serde_json::from_str(
+            r#"{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[
+                    {"team":"TOR","period":"1","scorer":{"player":"Mitch Marner"},"min":4,"sec":10},
+                    {"team":"PIT","period":"3","scorer":{"player":"Sidney Crosby"},"min":4,"sec":27},
+                    {"team":"PIT","period":"OT","scorer":{"player":"Sidney Crosby"},"min":3,"sec":0}
+                ],
+                "scores":{"PIT":2,"TOR":1},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }"#,
+        )?;
+        let game = crate::game::parse_game(&test_game).unwrap();
+
+        let expected = [
+            "Toronto          - Pittsburgh         ot 1-2",
+            "Marner           4 Crosby          44",
+            "                   Crosby          63",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render_game(&game, &RenderOptions::default()), expected);
+
+        let tight = RenderOptions::default().spacing(0);
+        assert_eq!(
+            render_game(&game, &tight),
+            expected.trim_end().to_string() + "\n"
+        );
+
+        let favorite = |team: &str| {
+            RenderOptions::default()
+                .outcome_colors(true)
+                .favorite_teams(vec![String::from(team)])
+        };
+        assert_eq!(score_role(&game, &favorite("PIT")), Role::FinalScore);
+        assert_eq!(score_role(&game, &favorite("TOR")), Role::Loss);
+        assert_eq!(score_role(&game, &favorite("BOS")), Role::FinalScore);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shows_a_live_shootout_without_a_winner() -> serde_json::Result<()> {
+        let test_game: crate::api_types::GameResponse = serde_json::from_str(
+            r#"{
+                "status":{"state":"LIVE","progress":{"currentPeriod":5,"currentPeriodOrdinal":"SO","currentPeriodTimeRemaining":{"pretty":"00:00","min":0,"sec":0}}},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[
+                    {"team":"TOR","period":"1","scorer":{"player":"Mitch Marner"},"min":4,"sec":10},
+                    {"team":"PIT","period":"2","scorer":{"player":"Sidney Crosby"},"min":4,"sec":27},
+                    {"team":"PIT","period":"SO","scorer":{"player":"Sidney Crosby"}}
+                ],
+                "scores":{"PIT":1,"TOR":1},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }"#,
+        )?;
+        let game = crate::game::parse_game(&test_game).unwrap();
+
+        let expected = [
+            "Toronto          - Pittsburgh            1-1",
+            "SO in progress 0-1",
+            "Marner           4 Crosby          24",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render_game(&game, &RenderOptions::default()), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shows_a_power_play_next_to_the_live_score() -> serde_json::Result<()> {
+        let test_game: crate::api_types::GameResponse = serde_json::from_str(
+            r#"{
+                "status":{"state":"LIVE","progress":{"currentPeriod":2,"currentPeriodOrdinal":"2nd","currentPeriodTimeRemaining":{"pretty":"08:12","min":8,"sec":12},"powerPlay":{"team":"PIT","timeRemaining":{"pretty":"1:23","min":1,"sec":23}}}},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[
+                    {"team":"TOR","period":"1","scorer":{"player":"Mitch Marner"},"min":4,"sec":10}
+                ],
+                "scores":{"PIT":0,"TOR":1},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }"#,
+        )?;
+        let game = crate::game::parse_game(&test_game).unwrap();
+        assert_eq!(power_play(&game).map(|pp| pp.seconds_remaining), Some(83));
+
+        let expected = [
+            "Toronto          - Pittsburgh            1-0 PP PIT 1:23",
+            "Marner           4",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render_game(&game, &RenderOptions::default()), expected);
+
+        let finnish = RenderOptions::default().language(Lang::Fi);
+        assert!(render_game(&game, &finnish)
+            .starts_with("Toronto          - Pittsburgh            1-0 YV PIT 1:23\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_says_when_a_range_has_no_games() {
+        let options = RenderOptions::default();
+        let lines = sections_lines(&[], &options);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text(), "No games today.");
+    }
+
+    #[test]
+    fn it_renders_sections_with_a_total() -> serde_json::Result<()> {
+        let games = crate::game::parse_api_response(
+            r#"{"date":null,"errors":null,"games":[{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[{"team":"PIT","period":"1","scorer":{"player":"Sidney Crosby"},"assists":[{"player":"Evgeni Malkin","seasonTotal":1}],"min":4,"sec":10}],
+                "scores":{"PIT":1,"TOR":0},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }]}"#,
+        )?;
+        let sections = [
+            Section {
+                title: String::from("2021-01-23"),
+                games: games.into_iter().collect(),
+            },
+            Section {
+                title: String::from("2021-01-24"),
+                games: Vec::new(),
+            },
+        ];
+        let options = RenderOptions::default()
+            .highlights(vec![String::from("Crosby")])
+            .show_stats(true)
+            .spacing(0);
+
+        let expected = [
+            "Jan 23",
+            "Toronto          - Pittsburgh            0-1",
+            "                   Crosby           4",
+            "(Crosby 1+0)",
+            "Jan 24",
+            "No games today.",
+            "Total (Crosby 1+0)",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(PlainRenderer.render_sections(&sections, &options), expected);
+
+        let options = options.show_opponents(true);
+        assert!(PlainRenderer
+            .render_sections(&sections, &options)
+            .ends_with("Total (Crosby 1+0 vs TOR)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_goals_in_time_order() -> serde_json::Result<()> {
+        let test_game: crate::api_types::GameResponse = serde_json::from_str(
+            r#"{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[
+                    {"team":"TOR","period":"1","scorer":{"player":"Mitch Marner"},"min":4,"sec":10},
+                    {"team":"TOR","period":"3","scorer":{"player":"Auston Matthews"},"min":11,"sec":2},
+                    {"team":"PIT","period":"2","scorer":{"player":"Sidney Crosby"},"min":4,"sec":27},
+                    {"team":"PIT","period":"SO","scorer":{"player":"Sidney Crosby"}},
+                    {"team":"TOR","period":"SO","scorer":{"player":"Mitch Marner"}}
+                ],
+                "scores":{"PIT":1,"TOR":3},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }"#,
+        )?;
+        let game = crate::game::parse_game(&test_game).unwrap();
+
+        let expected = [
+            "Toronto          - Pittsburgh         so 3-1",
+            "Marner           4",
+            "                   Crosby          24",
+            "Matthews        51",
+            "Marner          65",
+            "",
+            "",
+        ]
+        .join("\n");
+        let options = RenderOptions::default().goal_order(GoalOrder::Time);
+        assert_eq!(render_game(&game, &options), expected);
+
+        let initials = options.initials(true);
+        assert!(render_game(&game, &initials).contains("                   S. Crosby       24\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_marks_notable_results_with_a_legend() -> serde_json::Result<()> {
+        let games = crate::game::parse_api_response(
+            r#"{"date":null,"errors":null,"games":[{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "scores":{"PIT":6,"TOR":0},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }]}"#,
+        )?;
+
+        let options = RenderOptions::default().spacing(0).show_notable(true);
+        assert_eq!(
+            render_games(&games, &options),
+            "Toronto          - Pittsburgh            0-6 *\n* blowout\n"
+        );
+        assert_eq!(
+            render_games(&games, &options.language(Lang::Fi)),
+            "Toronto          - Pittsburgh            0-6 *\n* murskavoitto\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_collapses_games_nobody_follows() -> serde_json::Result<()> {
+        let games = crate::game::parse_api_response(
+            r#"{"date":null,"errors":null,"games":[{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[{"team":"BOS","period":"1","scorer":{"player":"David Pastrnak"},"min":4,"sec":10}],
+                "scores":{"BOS":1,"MTL":0},
+                "teams":{
+                    "away":{"abbreviation":"MTL","id":8,"locationName":"Montréal","shortName":"Montréal","teamName":"Canadiens"},
+                    "home":{"abbreviation":"BOS","id":6,"locationName":"Boston","shortName":"Boston","teamName":"Bruins"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            },{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[{"team":"PIT","period":"1","scorer":{"player":"Sidney Crosby"},"min":4,"sec":10}],
+                "scores":{"PIT":1,"TOR":0},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }]}"#,
+        )?;
+
+        let options = RenderOptions::default()
+            .highlights(vec![String::from("Crosby")])
+            .collapse_others(true);
+        let expected = [
+            "Boston           - Montreal              1-0",
+            "",
+            "Toronto          - Pittsburgh            0-1",
+            "                   Crosby           4",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render_games(&games, &options), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shows_the_latest_goal_in_a_banner() {
+        let goal = Event::Goal {
+            game_id: String::new(),
+            home: String::from("PIT"),
+            away: String::from("TOR"),
+            team: String::from("PIT"),
+            scorer: String::from("Crosby"),
+            minute: 58,
+            score: String::from("3-2"),
+        };
+        let banner = goal_banner(&goal, &RenderOptions::default()).unwrap();
+        assert_eq!(banner.text(), "GOAL 58' Crosby — PIT 3-2 TOR");
+        assert_eq!(banner.spans[0].role, Role::Notice);
+
+        let options = RenderOptions::default().language(Lang::Fi);
+        assert!(goal_banner(&goal, &options)
+            .unwrap()
+            .text()
+            .starts_with("MAALI 58'"));
+    }
+
+    #[test]
+    fn it_lists_only_goals_by_highlighted_players() -> serde_json::Result<()> {
+        let games = crate::game::parse_api_response(
+            r#"{"date":null,"errors":null,"games":[{
+                "status":{"state":"FINAL"},
+                "startTime":"2021-01-23T19:00:00Z",
+                "goals":[
+                    {"team":"TOR","period":"1","scorer":{"player":"Mitch Marner"},"min":2,"sec":10},
+                    {"team":"PIT","period":"1","scorer":{"player":"Jake Guentzel"},"assists":[{"player":"Sidney Crosby","seasonTotal":1}],"min":4,"sec":10},
+                    {"team":"PIT","period":"2","scorer":{"player":"Evgeni Malkin"},"min":8,"sec":10}
+                ],
+                "scores":{"PIT":2,"TOR":1},
+                "teams":{
+                    "away":{"abbreviation":"PIT","id":5,"locationName":"Pittsburgh","shortName":"Pittsburgh","teamName":"Penguins"},
+                    "home":{"abbreviation":"TOR","id":10,"locationName":"Toronto","shortName":"Toronto","teamName":"Maple Leafs"}
+                },
+                "preGameStats":{"records":{}},
+                "currentStats":{"records":{},"standings":{}}
+            }]}"#,
+        )?;
+
+        let options = RenderOptions::default()
+            .highlights(vec![String::from("Crosby")])
+            .only_highlights(true);
+        let expected = [
+            "Toronto          - Pittsburgh            1-2",
+            "                   Guentzel         4",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render_games(&games, &options), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_lists_the_teams() {
+        let teams = crate::teams::team_listing();
+        let lines: Vec<String> = teams_lines(&teams).iter().map(StyledLine::text).collect();
+        assert_eq!(lines.len(), 32);
+        assert_eq!(lines[0], "ANA  Anaheim       Anaheim Ducks");
+        assert!(lines.contains(&String::from(
+            "PIT  Pittsburgh    Pittsburgh Penguins     pens"
+        )));
+    }
+
+    #[test]
+    fn it_renders_a_roster() {
+        let team = crate::teams::resolve_team("PIT").unwrap();
+        let players = [
+            RosterPlayer {
+                number: Some(87),
+                first_name: String::from("Sidney"),
+                last_name: String::from("Crosby"),
+                position: String::from("C"),
+                nationality: Some(String::from("CAN")),
+            },
+            RosterPlayer {
+                number: None,
+                first_name: String::from("Joel"),
+                last_name: String::from("Blomqvist"),
+                position: String::from("G"),
+                nationality: None,
+            },
+        ];
+
+        let expected = [
+            "Pittsburgh Penguins",
+            "87 Sidney Crosby            C CAN",
+            "   Joel Blomqvist           G",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(PlainRenderer.render_roster(team, &players), expected);
+    }
+
+    #[test]
+    fn it_renders_standings() {
+        let divisions = [Division {
+            name: String::from("Metropolitan"),
+            teams: vec![Standing {
+                team: String::from("PIT"),
+                games_played: 10,
+                wins: 5,
+                losses: 4,
+                ot_losses: 1,
+                points: 11,
+                playoff_spot: true,
+            }],
+        }];
+
+        let options = RenderOptions::default();
+        assert_eq!(
+            PlainRenderer.render_standings(&divisions, &options),
+            "Metropolitan\n 1. Pittsburgh      10  5  4  1  11\n"
+        );
+
+        // Divisions and conferences are named in the language of the page
+        let options = RenderOptions::default().language(Lang::Fi);
+        assert_eq!(
+            PlainRenderer.render_standings(&divisions, &options),
+            "Metropolitan-divisioona\n 1. Pittsburgh      10  5  4  1  11\n"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::api_types::{RosterPlayerResponse, RosterResponse};
+use serde::Serialize;
+
+/// A player on a team's current roster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RosterPlayer {
+    pub number: Option<u64>,
+    pub first_name: String,
+    pub last_name: String,
+    /// "C", "L", "R", "D" or "G"
+    pub position: String,
+    /// Three letter country code like "FIN", when known
+    pub nationality: Option<String>,
+}
+
+/// Lists the forwards, defensemen and goalies in that order,
+/// each group by jersey number
+pub fn parse_roster(roster: &RosterResponse) -> Vec<RosterPlayer> {
+    [&roster.forwards, &roster.defensemen, &roster.goalies]
+        .iter()
+        .flat_map(|group| {
+            let mut players: Vec<RosterPlayer> = group.iter().map(parse_player).collect();
+            // Players without a number yet go last
+            players.sort_by_key(|player| player.number.unwrap_or(u64::MAX));
+            players
+        })
+        .collect()
+}
+
+fn parse_player(player: &RosterPlayerResponse) -> RosterPlayer {
+    RosterPlayer {
+        number: player.sweater_number,
+        first_name: player.first_name.default.to_string(),
+        last_name: player.last_name.default.to_string(),
+        position: player.position_code.clone().unwrap_or_default(),
+        nationality: player.birth_country.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_lists_players_by_group_and_number() -> serde_json::Result<()> {
+        let roster: RosterResponse = serde_json::from_str(
+            r#"{
+                "forwards":[
+                    {"firstName":{"default":"Evgeni"},"lastName":{"default":"Malkin"},"sweaterNumber":71,"positionCode":"C","birthCountry":"RUS"},
+                    {"firstName":{"default":"Sidney"},"lastName":{"default":"Crosby"},"sweaterNumber":87,"positionCode":"C","birthCountry":"CAN"},
+                    {"firstName":{"default":"Bryan"},"lastName":{"default":"Rust"},"sweaterNumber":17,"positionCode":"R"}
+                ],
+                "defensemen":[
+                    {"firstName":{"default":"Kris"},"lastName":{"default":"Letang"},"sweaterNumber":58,"positionCode":"D","birthCountry":"CAN"}
+                ],
+                "goalies":[
+                    {"firstName":{"default":"Joel"},"lastName":{"default":"Blomqvist"},"positionCode":"G","birthCountry":"FIN"},
+                    {"firstName":{"default":"Tristan"},"lastName":{"default":"Jarry"},"sweaterNumber":35,"positionCode":"G","birthCountry":"CAN"}
+                ]
+            }"#,
+        )?;
+
+        let names: Vec<String> = parse_roster(&roster)
+            .into_iter()
+            .map(|player| player.last_name)
+            .collect();
+        assert_eq!(
+            names,
+            ["Rust", "Malkin", "Crosby", "Letang", "Jarry", "Blomqvist"]
+        );
+
+        Ok(())
+    }
+}
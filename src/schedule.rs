@@ -0,0 +1,151 @@
+//! `235 schedule`, the games that haven't started yet with the time the
+//! puck drops on the local clock. The API dates games by the day in North
+//! America, so the games are grouped by their local date instead.
+
+use crate::format::{format_date, format_time};
+use crate::game::Game;
+use crate::i18n::Text;
+use crate::options::RenderOptions;
+use crate::render::{game_team_names, Section};
+use crate::style::{Role, StyledLine};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use serde::{Serialize, Serializer};
+
+/// A game still to be played and its start time in the local time zone
+#[derive(Serialize)]
+pub struct ScheduledGame<'a> {
+    #[serde(serialize_with = "rfc3339")]
+    pub local_start: DateTime<FixedOffset>,
+    #[serde(flatten)]
+    pub game: &'a Game,
+}
+
+fn rfc3339<S: Serializer>(time: &DateTime<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&time.to_rfc3339())
+}
+
+/// Games of `sections` in `PREVIEW` state by their start time in `zone`.
+/// Games without a start time can't be placed and are left out.
+pub fn upcoming_games<'a, Tz: TimeZone>(
+    sections: &'a [Section],
+    zone: &Tz,
+) -> Vec<ScheduledGame<'a>> {
+    let mut games: Vec<ScheduledGame> = sections
+        .iter()
+        .flat_map(|section| section.games.iter().flatten())
+        .filter(|game| game.status == "PREVIEW")
+        .filter_map(|game| {
+            let start = DateTime::parse_from_rfc3339(&game.start_time).ok()?;
+            Some(ScheduledGame {
+                local_start: start.with_timezone(zone).fixed_offset(),
+                game,
+            })
+        })
+        .collect();
+    games.sort_by_key(|scheduled| scheduled.local_start);
+    games
+}
+
+/// The games under their local dates, a line per game with the start time
+/// and the teams
+pub fn schedule_lines(games: &[ScheduledGame], options: &RenderOptions) -> Vec<StyledLine> {
+    if games.is_empty() {
+        return vec![StyledLine::styled(
+            options.language.text(Text::NoScheduledGames),
+            Role::Text,
+        )];
+    }
+
+    let mut lines = Vec::new();
+    let mut previous_date = None;
+    for scheduled in games {
+        let date = scheduled.local_start.date_naive();
+        if previous_date != Some(date) {
+            if previous_date.is_some() {
+                lines.push(StyledLine::default());
+            }
+            lines.push(StyledLine::styled(
+                format_date(date, options.language),
+                Role::Title,
+            ));
+            previous_date = Some(date);
+        }
+
+        let (home, away) = game_team_names(scheduled.game, options);
+        let mut line = StyledLine::styled(
+            format!(
+                "{:<7}",
+                format_time(scheduled.local_start.time(), options.language)
+            ),
+            Role::Info,
+        );
+        line.push(format!("{} - {}", home, away), Role::Header);
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{test_game_json, test_games};
+
+    fn section(date: &str, games: &[(&str, &str, &str, &str)]) -> Section {
+        let games: Vec<String> = games
+            .iter()
+            .map(|(state, start, home, away)| {
+                let status = format!(r#"{{"state":"{}"}}"#, state);
+                test_game_json(&status, start, (home, 0), (away, 0), "")
+            })
+            .collect();
+        Section {
+            title: String::from(date),
+            games: test_games(&games),
+        }
+    }
+
+    #[test]
+    fn it_lists_games_by_their_local_start() {
+        let sections = [
+            section(
+                "2024-10-15",
+                &[
+                    ("FINAL", "2024-10-15T23:00:00Z", "BOS", "FLA"),
+                    ("PREVIEW", "2024-10-16T02:00:00Z", "EDM", "CGY"),
+                    ("PREVIEW", "2024-10-15T23:00:00Z", "PIT", "TOR"),
+                ],
+            ),
+            section("2024-10-16", &[("PREVIEW", "", "NYR", "NJD")]),
+        ];
+        let helsinki = FixedOffset::east_opt(3 * 3600).unwrap();
+        let games = upcoming_games(&sections, &helsinki);
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].game.home, "PIT");
+        assert_eq!(
+            games[1].local_start.to_rfc3339(),
+            "2024-10-16T05:00:00+03:00"
+        );
+
+        let options = RenderOptions::default().deterministic(true);
+        let lines: Vec<String> = schedule_lines(&games, &options)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Oct 16",
+                "02:00  Pittsburgh - Toronto",
+                "05:00  Edmonton - Calgary",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_says_when_nothing_is_scheduled() {
+        let options = RenderOptions::default().deterministic(true);
+        let lines = schedule_lines(&[], &options);
+        assert_eq!(lines[0].text(), "No games scheduled.");
+    }
+}
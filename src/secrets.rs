@@ -0,0 +1,59 @@
+//! Tokens and passwords kept in the OS keyring (Keychain, Credential Manager
+//! or Secret Service) instead of `$HOME/.235.toml`. The config refers to
+//! them by name, like `password_secret = "smtp-password"`.
+
+use keyring::Entry;
+use std::fmt;
+
+const SERVICE: &str = "nhl-235";
+
+#[derive(Debug)]
+pub struct SecretError {
+    name: String,
+    error: keyring::Error,
+}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error {
+            keyring::Error::NoEntry => write!(
+                f,
+                "No secret '{}' in the keyring, add it with `235 secret set {}`",
+                self.name, self.name
+            ),
+            _ => write!(f, "Can't access secret '{}': {}", self.name, self.error),
+        }
+    }
+}
+
+fn entry(name: &str) -> Result<Entry, SecretError> {
+    Entry::new(SERVICE, name).map_err(|error| SecretError {
+        name: name.to_string(),
+        error,
+    })
+}
+
+pub fn get_secret(name: &str) -> Result<String, SecretError> {
+    entry(name)?.get_password().map_err(|error| SecretError {
+        name: name.to_string(),
+        error,
+    })
+}
+
+pub fn set_secret(name: &str, value: &str) -> Result<(), SecretError> {
+    entry(name)?
+        .set_password(value)
+        .map_err(|error| SecretError {
+            name: name.to_string(),
+            error,
+        })
+}
+
+pub fn delete_secret(name: &str) -> Result<(), SecretError> {
+    entry(name)?
+        .delete_credential()
+        .map_err(|error| SecretError {
+            name: name.to_string(),
+            error,
+        })
+}
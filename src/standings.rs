@@ -0,0 +1,147 @@
+use crate::api_types::{StandingsResponse, TeamStandingResponse};
+use serde::Serialize;
+
+/// A team's row in the standings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Standing {
+    pub team: String,
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub ot_losses: u64,
+    pub points: u64,
+    /// Holds a playoff spot: one of the top three of the division or one
+    /// of the two wild cards of the conference
+    pub playoff_spot: bool,
+}
+
+/// Teams of a division or a conference in standings order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Division {
+    pub name: String,
+    pub teams: Vec<Standing>,
+}
+
+/// Groups the teams by division, divisions in alphabetical order and
+/// teams by their rank in the division
+pub fn parse_standings(standings: &StandingsResponse) -> Vec<Division> {
+    let mut teams: Vec<&TeamStandingResponse> = standings.standings.iter().collect();
+    teams.sort_by_key(|team| {
+        (
+            team.division_name.clone().unwrap_or_default(),
+            team.division_sequence.unwrap_or(u64::MAX),
+            std::cmp::Reverse(team.points),
+        )
+    });
+
+    let mut divisions: Vec<Division> = Vec::new();
+    for team in teams {
+        let name = team.division_name.clone().unwrap_or_default();
+        match divisions.last_mut() {
+            Some(division) if division.name == name => division.teams.push(parse_standing(team)),
+            _ => divisions.push(Division {
+                name,
+                teams: vec![parse_standing(team)],
+            }),
+        }
+    }
+    divisions
+}
+
+/// Groups the teams by conference, conferences in alphabetical order and
+/// teams by their rank in the conference
+pub fn parse_conferences(standings: &StandingsResponse) -> Vec<Division> {
+    let mut teams: Vec<&TeamStandingResponse> = standings.standings.iter().collect();
+    teams.sort_by_key(|team| {
+        (
+            team.conference_name.clone().unwrap_or_default(),
+            team.conference_sequence.unwrap_or(u64::MAX),
+            std::cmp::Reverse(team.points),
+        )
+    });
+
+    let mut conferences: Vec<Division> = Vec::new();
+    for team in teams {
+        let name = team.conference_name.clone().unwrap_or_default();
+        match conferences.last_mut() {
+            Some(conference) if conference.name == name => {
+                conference.teams.push(parse_standing(team))
+            }
+            _ => conferences.push(Division {
+                name,
+                teams: vec![parse_standing(team)],
+            }),
+        }
+    }
+    conferences
+}
+
+fn parse_standing(team: &TeamStandingResponse) -> Standing {
+    let division_leader = team.division_sequence.is_some_and(|rank| rank <= 3);
+    let wild_card = team
+        .wildcard_sequence
+        .is_some_and(|rank| (1..=2).contains(&rank));
+    Standing {
+        team: team.team_abbrev.default.to_string(),
+        games_played: team.games_played,
+        wins: team.wins,
+        losses: team.losses,
+        ot_losses: team.ot_losses,
+        points: team.points,
+        playoff_spot: division_leader || wild_card,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_groups_teams_by_division() -> serde_json::Result<()> {
+        let standings: StandingsResponse = serde_json::from_str(
+            r#"{"standings":[
+                {"teamAbbrev":{"default":"BOS"},"teamName":{"default":"Boston Bruins"},"divisionName":"Atlantic","gamesPlayed":10,"wins":6,"losses":3,"otLosses":1,"points":13,"divisionSequence":2},
+                {"teamAbbrev":{"default":"PIT"},"teamName":{"default":"Pittsburgh Penguins"},"divisionName":"Metropolitan","gamesPlayed":10,"wins":5,"losses":4,"otLosses":1,"points":11,"divisionSequence":1},
+                {"teamAbbrev":{"default":"FLA"},"teamName":{"default":"Florida Panthers"},"divisionName":"Atlantic","gamesPlayed":10,"wins":7,"losses":3,"otLosses":0,"points":14,"divisionSequence":1}
+            ]}"#,
+        )?;
+
+        let divisions = parse_standings(&standings);
+        assert_eq!(divisions.len(), 2);
+        assert_eq!(divisions[0].name, "Atlantic");
+        let teams: Vec<&str> = divisions[0]
+            .teams
+            .iter()
+            .map(|standing| standing.team.as_str())
+            .collect();
+        assert_eq!(teams, ["FLA", "BOS"]);
+        assert_eq!(divisions[1].teams[0].points, 11);
+        assert!(divisions[0].teams[0].playoff_spot);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_groups_teams_by_conference() -> serde_json::Result<()> {
+        let standings: StandingsResponse = serde_json::from_str(
+            r#"{"standings":[
+                {"teamAbbrev":{"default":"BOS"},"teamName":{"default":"Boston Bruins"},"conferenceName":"Eastern","divisionName":"Atlantic","gamesPlayed":10,"wins":6,"losses":3,"otLosses":1,"points":13,"divisionSequence":4,"conferenceSequence":2,"wildcardSequence":1},
+                {"teamAbbrev":{"default":"VAN"},"teamName":{"default":"Vancouver Canucks"},"conferenceName":"Western","divisionName":"Pacific","gamesPlayed":10,"wins":5,"losses":4,"otLosses":1,"points":11,"divisionSequence":1,"conferenceSequence":1,"wildcardSequence":0},
+                {"teamAbbrev":{"default":"PIT"},"teamName":{"default":"Pittsburgh Penguins"},"conferenceName":"Eastern","divisionName":"Metropolitan","gamesPlayed":10,"wins":3,"losses":6,"otLosses":1,"points":7,"divisionSequence":5,"conferenceSequence":9,"wildcardSequence":3},
+                {"teamAbbrev":{"default":"FLA"},"teamName":{"default":"Florida Panthers"},"conferenceName":"Eastern","divisionName":"Atlantic","gamesPlayed":10,"wins":7,"losses":3,"otLosses":0,"points":14,"divisionSequence":1,"conferenceSequence":1,"wildcardSequence":0}
+            ]}"#,
+        )?;
+
+        let conferences = parse_conferences(&standings);
+        assert_eq!(conferences.len(), 2);
+        assert_eq!(conferences[0].name, "Eastern");
+        let teams: Vec<(&str, bool)> = conferences[0]
+            .teams
+            .iter()
+            .map(|standing| (standing.team.as_str(), standing.playoff_spot))
+            .collect();
+        assert_eq!(teams, [("FLA", true), ("BOS", true), ("PIT", false)]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,769 @@
+use crate::game::{Game, Goal, Player};
+use itertools::Itertools;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// How a player's points are written in the stats footer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum StatsFormat {
+    /// Goals and assists, "1+1" like maalit+syötöt on the real page
+    #[default]
+    Plus,
+    /// Points only, "2 pts"
+    Points,
+    /// Goals and assists labeled, "G:1 A:1"
+    Labeled,
+}
+
+impl StatsFormat {
+    fn format(self, goals: u64, assists: u64) -> String {
+        match self {
+            StatsFormat::Plus => format!("{}+{}", goals, assists),
+            StatsFormat::Points => match goals + assists {
+                1 => String::from("1 pt"),
+                points => format!("{} pts", points),
+            },
+            StatsFormat::Labeled => format!("G:{} A:{}", goals, assists),
+        }
+    }
+}
+
+impl FromStr for StatsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plus" => Ok(StatsFormat::Plus),
+            "points" => Ok(StatsFormat::Points),
+            "labeled" => Ok(StatsFormat::Labeled),
+            _ => Err(format!(
+                "unsupported stats format '{}', use one of: plus, points, labeled",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for StatsFormat {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Look of the stats footer, "(Crosby 1+1)" by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsStyle {
+    pub format: StatsFormat,
+    /// Wraps the whole footer in parentheses
+    pub parentheses: bool,
+}
+
+impl Default for StatsStyle {
+    fn default() -> Self {
+        StatsStyle {
+            format: StatsFormat::default(),
+            parentheses: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Stat {
+    goals: u64,
+    assists: u64,
+}
+
+fn count_stats<'a>(
+    goals: &'a [Goal],
+    highlights: &[String],
+    stats: &mut HashMap<&'a Player, Stat>,
+) {
+    goals.iter().for_each(|goal| {
+        if goal.minute == 65 {
+            return;
+        }
+        if highlights.contains(&goal.scorer.last_name) {
+            stats
+                .entry(&goal.scorer)
+                .and_modify(|stat| stat.goals += 1)
+                .or_insert(Stat {
+                    goals: 1,
+                    assists: 0,
+                });
+        }
+        goal.assists.iter().for_each(|assist| {
+            if highlights.contains(&assist.last_name) {
+                stats
+                    .entry(assist)
+                    .and_modify(|stat| stat.assists += 1)
+                    .or_insert(Stat {
+                        goals: 0,
+                        assists: 1,
+                    });
+            }
+        })
+    });
+}
+
+fn has_last_name_namesake(player: &Player, stats: &HashMap<&Player, Stat>) -> bool {
+    for other in stats.keys() {
+        if other.last_name == player.last_name && other.team != player.team {
+            return true;
+        }
+        if other.last_name == player.last_name
+            && other.team == player.team
+            && other.first_name != player.first_name
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether a player of another team shares both the initial and the last name
+fn has_initial_namesake(player: &Player, stats: &HashMap<&Player, Stat>) -> bool {
+    stats.keys().any(|other| {
+        other.last_name == player.last_name
+            && other.team != player.team
+            && other.first_name.chars().next() == player.first_name.chars().next()
+    })
+}
+
+/// Last name, with the first initial if another highlighted player has the
+/// same last name and the team too if even the initials match
+fn player_name(player: &Player, stats: &HashMap<&Player, Stat>) -> String {
+    if !has_last_name_namesake(player, stats) {
+        return String::from(&player.last_name);
+    }
+    let name = match player.first_name.chars().next() {
+        Some(initial) => format!("{}. {}", initial, &player.last_name),
+        None => String::from(&player.last_name),
+    };
+    if has_initial_namesake(player, stats) {
+        format!("{} {}", name, &player.team)
+    } else {
+        name
+    }
+}
+
+pub fn craft_stats_message(
+    goals: &[Goal],
+    highlights: &[String],
+    style: &StatsStyle,
+) -> Option<String> {
+    craft_message(goals, highlights, style, |_| String::new())
+}
+
+/// Stats of the highlighted players over several games with the teams
+/// they got their points against, like "(Aho 1+2 vs TOR/BOS)"
+pub fn craft_opponent_stats_message(
+    games: &[&Game],
+    highlights: &[String],
+    style: &StatsStyle,
+) -> Option<String> {
+    let mut opponents: HashMap<&Player, Vec<&str>> = HashMap::new();
+    for game in games {
+        for goal in game.goals.iter().filter(|goal| goal.minute != 65) {
+            let opponent = if goal.team == game.home {
+                &game.away
+            } else {
+                &game.home
+            };
+            for player in std::iter::once(&goal.scorer).chain(goal.assists.iter()) {
+                let against = opponents.entry(player).or_default();
+                if !against.contains(&opponent.as_str()) {
+                    against.push(opponent);
+                }
+            }
+        }
+    }
+
+    let goals: Vec<Goal> = games
+        .iter()
+        .flat_map(|game| game.goals.iter().cloned())
+        .collect();
+    craft_message(&goals, highlights, style, |player| {
+        match opponents.get(player) {
+            Some(against) => format!(" vs {}", against.join("/")),
+            None => String::new(),
+        }
+    })
+}
+
+/// Formats the stats, `suffix` adds something after each player's points
+fn craft_message(
+    goals: &[Goal],
+    highlights: &[String],
+    style: &StatsStyle,
+    suffix: impl Fn(&Player) -> String,
+) -> Option<String> {
+    let mut stats: HashMap<&Player, Stat> = HashMap::new();
+    count_stats(goals, highlights, &mut stats);
+
+    if stats.is_empty() {
+        return None;
+    }
+
+    // Players are listed in the order they got their first point
+    // so that the footer is the same on every run
+    let players = goals
+        .iter()
+        .flat_map(|goal| std::iter::once(&goal.scorer).chain(goal.assists.iter()))
+        .filter(|player| stats.contains_key(player))
+        .unique();
+
+    let mut stats_messages: Vec<String> = Vec::new();
+    for player in players {
+        let player_stats = &stats[player];
+        let sub_message = format!(
+            "{} {}{}",
+            player_name(player, &stats),
+            style
+                .format
+                .format(player_stats.goals, player_stats.assists),
+            suffix(player)
+        );
+        stats_messages.push(sub_message);
+    }
+    let message = stats_messages.join(", ");
+    if style.parentheses {
+        Some(format!("({})", message))
+    } else {
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn it_crafts_no_message_if_no_highlighted_players_gain_stats() {
+        let highlights: Vec<String> = vec![String::from("Crosby")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Evgeni"),
+                last_name: String::from("Malkin"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Kris"),
+                    last_name: String::from("Letang"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Erik"),
+                    last_name: String::from("Karlsson"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: Option<String> = None;
+        let actual: Option<String> =
+            craft_stats_message(&[goal], &highlights, &StatsStyle::default());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_player_scored() {
+        let highlights: Vec<String> = vec![String::from("Crosby")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Sidney"),
+                last_name: String::from("Crosby"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Kris"),
+                    last_name: String::from("Letang"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Erik"),
+                    last_name: String::from("Karlsson"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: Option<String> = Some(String::from("(Crosby 1+0)"));
+        let actual: Option<String> =
+            craft_stats_message(&[goal], &highlights, &StatsStyle::default());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_player_gained_assist() {
+        let highlights: Vec<String> = vec![String::from("Crosby")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Evgeni"),
+                last_name: String::from("Malkin"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Sidney"),
+                    last_name: String::from("Crosby"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Erik"),
+                    last_name: String::from("Karlsson"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: Option<String> = Some(String::from("(Crosby 0+1)"));
+        let actual: Option<String> =
+            craft_stats_message(&[goal], &highlights, &StatsStyle::default());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_player_gained_both_goal_and_assist() {
+        let highlights: Vec<String> = vec![String::from("Crosby")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Evgeni"),
+                last_name: String::from("Malkin"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Sidney"),
+                    last_name: String::from("Crosby"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Erik"),
+                    last_name: String::from("Karlsson"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal2: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Sidney"),
+                last_name: String::from("Crosby"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![Player {
+                first_name: String::from("Brian"),
+                last_name: String::from("Rust"),
+                team: String::from("Pittsburgh"),
+            }],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: Option<String> = Some(String::from("(Crosby 1+1)"));
+        let actual: Option<String> =
+            craft_stats_message(&[goal, goal2], &highlights, &StatsStyle::default());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_player_gained_two_assists() {
+        let highlights: Vec<String> = vec![String::from("Crosby")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Evgeni"),
+                last_name: String::from("Malkin"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Sidney"),
+                    last_name: String::from("Crosby"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Erik"),
+                    last_name: String::from("Karlsson"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal2: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Evgeni"),
+                last_name: String::from("Malkin"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Brian"),
+                    last_name: String::from("Rust"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Sidney"),
+                    last_name: String::from("Crosby"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: Option<String> = Some(String::from("(Crosby 0+2)"));
+        let actual: Option<String> =
+            craft_stats_message(&[goal, goal2], &highlights, &StatsStyle::default());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_multiple_players_gain_points() {
+        let highlights: Vec<String> = vec![String::from("Crosby"), String::from("Malkin")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Evgeni"),
+                last_name: String::from("Malkin"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Sidney"),
+                    last_name: String::from("Crosby"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Erik"),
+                    last_name: String::from("Karlsson"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal2: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Sidney"),
+                last_name: String::from("Crosby"),
+                team: String::from("Pittsburgh"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Brian"),
+                    last_name: String::from("Rust"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Evgeni"),
+                    last_name: String::from("Malkin"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal3: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Brian"),
+                last_name: String::from("Rust"),
+                team: String::from("Pittsburg"),
+            },
+            assists: vec![
+                Player {
+                    first_name: String::from("Kris"),
+                    last_name: String::from("Letang"),
+                    team: String::from("Pittsburgh"),
+                },
+                Player {
+                    first_name: String::from("Evgeni"),
+                    last_name: String::from("Malkin"),
+                    team: String::from("Pittsburgh"),
+                },
+            ],
+            minute: 21,
+            special: false,
+            team: String::from("Pittsburg"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: String = String::from("Malkin 1+2");
+        let expected2: String = String::from("Crosby 1+1");
+        let actual: Option<String> =
+            craft_stats_message(&[goal, goal2, goal3], &highlights, &StatsStyle::default());
+
+        assert!(actual.as_ref().unwrap().contains(&expected));
+        assert!(actual.as_ref().unwrap().contains(&expected2));
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_different_players_from_different_teams_with_same_last_name() {
+        let highlights: Vec<String> = vec![String::from("Hughes")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Jack"),
+                last_name: String::from("Hughes"),
+                team: String::from("New Jersey"),
+            },
+            assists: vec![],
+            minute: 21,
+            special: false,
+            team: String::from("New Jersey"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal2: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Quinn"),
+                last_name: String::from("Hughes"),
+                team: String::from("Vancouver"),
+            },
+            assists: vec![],
+            minute: 23,
+            special: false,
+            team: String::from("Vancouver"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: String = String::from("Q. Hughes 1+0");
+        let expected2: String = String::from("J. Hughes 1+0");
+        let actual: Option<String> =
+            craft_stats_message(&[goal, goal2], &highlights, &StatsStyle::default());
+
+        assert!(actual.as_ref().unwrap().contains(&expected));
+        assert!(actual.as_ref().unwrap().contains(&expected2));
+    }
+
+    #[test]
+    fn it_crafts_good_message_if_different_players_from_same_team_with_same_last_name() {
+        let highlights: Vec<String> = vec![String::from("Hughes")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Jack"),
+                last_name: String::from("Hughes"),
+                team: String::from("New Jersey"),
+            },
+            assists: vec![],
+            minute: 21,
+            special: false,
+            team: String::from("New Jersey"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal2: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Quinn"),
+                last_name: String::from("Hughes"),
+                team: String::from("New Jersey"),
+            },
+            assists: vec![],
+            minute: 23,
+            special: false,
+            team: String::from("New Jersey"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: String = String::from("Q. Hughes 1+0");
+        let expected2: String = String::from("J. Hughes 1+0");
+        let actual: Option<String> =
+            craft_stats_message(&[goal, goal2], &highlights, &StatsStyle::default());
+
+        assert!(actual.as_ref().unwrap().contains(&expected));
+        assert!(actual.as_ref().unwrap().contains(&expected2));
+    }
+
+    #[test]
+    fn it_adds_the_team_if_players_share_the_initial_and_last_name() {
+        let highlights: Vec<String> = vec![String::from("Hughes")];
+        let hughes = |first_name: &str, team: &str, minute: u64| Goal {
+            scorer: Player {
+                first_name: String::from(first_name),
+                last_name: String::from("Hughes"),
+                team: String::from(team),
+            },
+            assists: vec![],
+            minute,
+            special: false,
+            team: String::from(team),
+            period: String::from("1"),
+            strength: None,
+        };
+        let goals = [
+            hughes("Jack", "NJD", 3),
+            hughes("Jim", "BOS", 8),
+            hughes("Quinn", "VAN", 12),
+        ];
+
+        let actual: Option<String> =
+            craft_stats_message(&goals, &highlights, &StatsStyle::default());
+
+        assert_eq!(
+            actual.as_deref(),
+            Some("(J. Hughes NJD 1+0, J. Hughes BOS 1+0, Q. Hughes 1+0)")
+        );
+    }
+
+    #[test]
+    fn it_doesnt_count_shootout_goals_to_stats() {
+        let highlights: Vec<String> = vec![String::from("Barkov")];
+        let goal: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Alexander"),
+                last_name: String::from("Barkov"),
+                team: String::from("Florida"),
+            },
+            assists: vec![],
+            minute: 21,
+            special: false,
+            team: String::from("Florida"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let goal2: Goal = Goal {
+            scorer: Player {
+                first_name: String::from("Alexander"),
+                last_name: String::from("Barkov"),
+                team: String::from("Florida"),
+            },
+            assists: vec![],
+            minute: 65,
+            special: false,
+            team: String::from("Florida"),
+            period: String::from("1"),
+            strength: None,
+        };
+
+        let expected: String = String::from("Barkov 1+0");
+        let actual: Option<String> =
+            craft_stats_message(&[goal, goal2], &highlights, &StatsStyle::default());
+
+        assert!(actual.as_ref().unwrap().contains(&expected));
+    }
+
+    #[test]
+    fn it_writes_stats_in_the_chosen_format() {
+        let highlights: Vec<String> = vec![String::from("Crosby")];
+        let crosby = Player {
+            first_name: String::from("Sidney"),
+            last_name: String::from("Crosby"),
+            team: String::from("PIT"),
+        };
+        let malkin = Player {
+            first_name: String::from("Evgeni"),
+            last_name: String::from("Malkin"),
+            team: String::from("PIT"),
+        };
+        let goals = [
+            Goal {
+                scorer: crosby.clone(),
+                assists: vec![],
+                minute: 5,
+                special: false,
+                team: String::from("PIT"),
+                period: String::from("1"),
+                strength: None,
+            },
+            Goal {
+                scorer: malkin,
+                assists: vec![crosby],
+                minute: 15,
+                special: false,
+                team: String::from("PIT"),
+                period: String::from("1"),
+                strength: None,
+            },
+        ];
+        let message = |format, parentheses| {
+            craft_stats_message(
+                &goals,
+                &highlights,
+                &StatsStyle {
+                    format,
+                    parentheses,
+                },
+            )
+        };
+
+        assert_eq!(
+            message(StatsFormat::Plus, true).as_deref(),
+            Some("(Crosby 1+1)")
+        );
+        assert_eq!(
+            message(StatsFormat::Points, false).as_deref(),
+            Some("Crosby 2 pts")
+        );
+        assert_eq!(
+            message(StatsFormat::Labeled, true).as_deref(),
+            Some("(Crosby G:1 A:1)")
+        );
+        assert_eq!(
+            craft_stats_message(
+                &goals[..1],
+                &highlights,
+                &StatsStyle {
+                    format: StatsFormat::Points,
+                    parentheses: true
+                }
+            )
+            .as_deref(),
+            Some("(Crosby 1 pt)")
+        );
+        assert_eq!("LABELED".parse(), Ok(StatsFormat::Labeled));
+        assert!("verbose".parse::<StatsFormat>().is_err());
+    }
+}
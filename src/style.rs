@@ -0,0 +1,97 @@
+//! The page as styled lines. The layout only says what each piece of text
+//! is, like a final score or a goal by a highlighted player, and every
+//! renderer decides how that role looks: colours on a terminal, nothing
+//! in plain text, and later classes in markup or a theme of its own.
+
+/// What a piece of text on the page is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Text without a style of its own, like "No games today."
+    Text,
+    /// Dates over sections, team names over rosters and division names
+    Title,
+    /// Team names at the start of a game
+    Header,
+    /// Score of a live game or a postponement
+    Score,
+    /// Score of a finished game, or a win by a favourite team
+    FinalScore,
+    /// Final score of a loss by a favourite team
+    Loss,
+    /// Live score of a game within one goal late in the game
+    CloseGame,
+    Goal,
+    /// Goal in overtime or in the shootout
+    SpecialGoal,
+    /// Goal or stats of a highlighted player
+    Highlight,
+    /// Power plays, special venues and upset markers that call for attention
+    Notice,
+    /// Details like venues, broadcasts, game length and table rows
+    Info,
+    /// Playoff series standing
+    SeriesInfo,
+    /// Row of a team holding a playoff spot in the standings
+    PlayoffSpot,
+}
+
+/// Text in a single role
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub role: Role,
+    /// Where the text leads, for renderers that can make it clickable
+    pub link: Option<String>,
+}
+
+/// One line of the page. A line without spans is a blank line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyledLine {
+    pub spans: Vec<Span>,
+}
+
+impl StyledLine {
+    /// A line of a single span
+    pub fn styled(text: impl Into<String>, role: Role) -> StyledLine {
+        let mut line = StyledLine::default();
+        line.push(text, role);
+        line
+    }
+
+    pub fn push(&mut self, text: impl Into<String>, role: Role) {
+        self.spans.push(Span {
+            text: text.into(),
+            role,
+            link: None,
+        });
+    }
+
+    /// Adds text that leads to `link`, or plain text without one
+    pub fn push_link(&mut self, text: impl Into<String>, role: Role, link: Option<String>) {
+        self.spans.push(Span {
+            text: text.into(),
+            role,
+            link,
+        });
+    }
+
+    /// The text of the line without any styles
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_joins_the_text_of_a_line() {
+        let mut line = StyledLine::styled("Pittsburgh - Toronto  ", Role::Header);
+        line.push("2-1", Role::FinalScore);
+
+        assert_eq!(line.text(), "Pittsburgh - Toronto  2-1");
+        assert_eq!(line.spans[1].role, Role::FinalScore);
+        assert_eq!(StyledLine::default().text(), "");
+    }
+}
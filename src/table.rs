@@ -0,0 +1,337 @@
+//! Rows for the structured formats of the subcommands. Standings, rosters,
+//! season series and the bracket are laid out once as a table here and
+//! written as CSV, TSV or Markdown the same way as the scores.
+
+use crate::bracket::Series;
+use crate::events::Event;
+use crate::export::GameRecord;
+use crate::player::{PlayerPoints, PointKind};
+use crate::roster::RosterPlayer;
+use crate::schedule::ScheduledGame;
+use crate::standings::Division;
+use crate::teams::TeamListing;
+use crate::versus::SeasonSeries;
+use crate::{csv, markdown, tsv};
+
+/// Column names and rows of plain text fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub header: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Comma-separated with a header row, fields quoted when needed
+    pub fn to_csv(&self) -> String {
+        self.write(",", csv::escape)
+    }
+
+    /// Tab-separated with a header row, without padding
+    pub fn to_tsv(&self) -> String {
+        self.write("\t", tsv::clean)
+    }
+
+    /// A Markdown table
+    pub fn to_markdown(&self) -> String {
+        let row = |fields: Vec<String>| format!("| {} |\n", fields.join(" | "));
+        let mut output = row(self
+            .header
+            .iter()
+            .map(|name| markdown::escape(name))
+            .collect());
+        output.push_str(&row(self
+            .header
+            .iter()
+            .map(|_| String::from("---"))
+            .collect()));
+        for fields in &self.rows {
+            output.push_str(&row(fields
+                .iter()
+                .map(|field| markdown::escape(field))
+                .collect()));
+        }
+        output
+    }
+
+    fn write(&self, separator: &str, escape: fn(&str) -> String) -> String {
+        let mut output = self.header.join(separator);
+        output.push('\n');
+        for fields in &self.rows {
+            let fields: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+            output.push_str(&fields.join(separator));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// A row per team in each division or conference table
+pub fn standings_table(tables: &[Division]) -> Table {
+    let rows = tables
+        .iter()
+        .flat_map(|table| {
+            table.teams.iter().enumerate().map(move |(rank, standing)| {
+                vec![
+                    table.name.to_string(),
+                    (rank + 1).to_string(),
+                    standing.team.to_string(),
+                    standing.games_played.to_string(),
+                    standing.wins.to_string(),
+                    standing.losses.to_string(),
+                    standing.ot_losses.to_string(),
+                    standing.points.to_string(),
+                    standing.playoff_spot.to_string(),
+                ]
+            })
+        })
+        .collect();
+    Table {
+        header: vec![
+            "table",
+            "rank",
+            "team",
+            "games_played",
+            "wins",
+            "losses",
+            "ot_losses",
+            "points",
+            "playoff_spot",
+        ],
+        rows,
+    }
+}
+
+/// A row per player
+pub fn roster_table(players: &[RosterPlayer]) -> Table {
+    let rows = players
+        .iter()
+        .map(|player| {
+            vec![
+                player
+                    .number
+                    .map(|number| number.to_string())
+                    .unwrap_or_default(),
+                player.first_name.to_string(),
+                player.last_name.to_string(),
+                player.position.to_string(),
+                player.nationality.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec![
+            "number",
+            "first_name",
+            "last_name",
+            "position",
+            "nationality",
+        ],
+        rows,
+    }
+}
+
+/// A row per series, teams still to be decided are left empty
+pub fn bracket_table(series: &[Series]) -> Table {
+    let rows = series
+        .iter()
+        .map(|series| {
+            vec![
+                series.round.to_string(),
+                series.top.clone().unwrap_or_default(),
+                series.bottom.clone().unwrap_or_default(),
+                series.top_wins.to_string(),
+                series.bottom_wins.to_string(),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec!["round", "top", "bottom", "top_wins", "bottom_wins"],
+        rows,
+    }
+}
+
+/// A row per game between the two teams
+pub fn versus_table(series: &SeasonSeries) -> Table {
+    let rows = series
+        .games
+        .iter()
+        .map(|meeting| {
+            vec![
+                meeting.date.to_string(),
+                meeting.game.home.to_string(),
+                meeting.game.away.to_string(),
+                meeting.game.home_score.to_string(),
+                meeting.game.away_score.to_string(),
+                meeting.game.status.to_string(),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec!["date", "home", "away", "home_score", "away_score", "status"],
+        rows,
+    }
+}
+
+/// A row per game still to be played, the date and time on the local
+/// clock
+pub fn schedule_table(games: &[ScheduledGame]) -> Table {
+    let rows = games
+        .iter()
+        .map(|scheduled| {
+            vec![
+                scheduled.local_start.format("%Y-%m-%d").to_string(),
+                scheduled.local_start.format("%H:%M").to_string(),
+                scheduled.game.home.to_string(),
+                scheduled.game.away.to_string(),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec!["date", "time", "home", "away"],
+        rows,
+    }
+}
+
+/// A row per goal or assist of the player
+pub fn player_table(player: &PlayerPoints) -> Table {
+    let rows = player
+        .points
+        .iter()
+        .map(|point| {
+            let assists: Vec<String> = point
+                .goal
+                .assists
+                .iter()
+                .map(|assist| format!("{} {}", assist.first_name, assist.last_name))
+                .collect();
+            let kind = match point.kind {
+                PointKind::Goal => "goal",
+                PointKind::Assist => "assist",
+            };
+            vec![
+                point.date.to_string(),
+                point.home.to_string(),
+                point.away.to_string(),
+                kind.to_string(),
+                point.goal.minute.to_string(),
+                point.goal.period.to_string(),
+                format!(
+                    "{} {}",
+                    point.goal.scorer.first_name, point.goal.scorer.last_name
+                ),
+                assists.join(";"),
+                point.goal.strength.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec![
+            "date", "home", "away", "kind", "minute", "period", "scorer", "assists", "strength",
+        ],
+        rows,
+    }
+}
+
+/// A row per team, the aliases separated by semicolons
+pub fn teams_table(teams: &[TeamListing]) -> Table {
+    let rows = teams
+        .iter()
+        .map(|team| {
+            vec![
+                team.abbreviation.to_string(),
+                team.name.to_string(),
+                team.full_name.to_string(),
+                team.aliases.join(";"),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec!["abbreviation", "name", "full_name", "aliases"],
+        rows,
+    }
+}
+
+/// A row per archived game
+pub fn history_table(games: &[&GameRecord]) -> Table {
+    let rows = games
+        .iter()
+        .map(|game| {
+            vec![
+                game.date.to_string(),
+                game.home.to_string(),
+                game.away.to_string(),
+                game.home_score.to_string(),
+                game.away_score.to_string(),
+                game.decision.to_string(),
+            ]
+        })
+        .collect();
+    Table {
+        header: vec![
+            "date",
+            "home",
+            "away",
+            "home_score",
+            "away_score",
+            "decision",
+        ],
+        rows,
+    }
+}
+
+/// A row per event with its type and description
+pub fn events_table(events: &[Event]) -> Table {
+    let rows = events
+        .iter()
+        .map(|event| vec![event.kind().to_string(), event.to_string()])
+        .collect();
+    Table {
+        header: vec!["type", "event"],
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::Standing;
+
+    #[test]
+    fn it_writes_the_standings_in_every_format() {
+        let tables = [Division {
+            name: String::from("Metropolitan"),
+            teams: vec![Standing {
+                team: String::from("PIT"),
+                games_played: 10,
+                wins: 5,
+                losses: 4,
+                ot_losses: 1,
+                points: 11,
+                playoff_spot: true,
+            }],
+        }];
+        let table = standings_table(&tables);
+
+        assert_eq!(
+            table.to_csv(),
+            "table,rank,team,games_played,wins,losses,ot_losses,points,playoff_spot\nMetropolitan,1,PIT,10,5,4,1,11,true\n"
+        );
+        assert_eq!(
+            table.to_tsv().lines().nth(1),
+            Some("Metropolitan\t1\tPIT\t10\t5\t4\t1\t11\ttrue")
+        );
+        let markdown = table.to_markdown();
+        assert!(markdown.starts_with("| table | rank | team |"));
+        assert_eq!(
+            markdown.lines().nth(2),
+            Some("| Metropolitan | 1 | PIT | 10 | 5 | 4 | 1 | 11 | true |")
+        );
+        assert_eq!(
+            markdown
+                .lines()
+                .nth(1)
+                .map(|row| row.matches("---").count()),
+            Some(9)
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use crate::i18n::Lang;
+use serde::Serialize;
+
+/// A team as shown on the page and the names people use for it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Team {
+    pub abbreviation: &'static str,
+    /// Name printed on the page, usually the city
+    pub name: &'static str,
+    pub nickname: &'static str,
+    /// Common short names, lowercase
+    pub aliases: &'static [&'static str],
+}
+
+impl Team {
+    /// Name and nickname like "Pittsburgh Penguins". The New York teams
+    /// are named after their nickname on the page, so they get the city
+    /// written out instead.
+    pub fn full_name(&self) -> String {
+        match self.name.strip_prefix("NY ") {
+            Some(_) => format!("New York {}", self.nickname),
+            None => format!("{} {}", self.name, self.nickname),
+        }
+    }
+}
+
+const fn team(
+    abbreviation: &'static str,
+    name: &'static str,
+    nickname: &'static str,
+    aliases: &'static [&'static str],
+) -> Team {
+    Team {
+        abbreviation,
+        name,
+        nickname,
+        aliases,
+    }
+}
+
+pub const TEAMS: [Team; 32] = [
+    team("BOS", "Boston", "Bruins", &["bs", "b's"]),
+    team("BUF", "Buffalo", "Sabres", &[]),
+    team("NJD", "New Jersey", "Devils", &["nj", "devs"]),
+    // Islanders is named like this to differentiate two New York teams
+    team("NYI", "NY Islanders", "Islanders", &["isles"]),
+    // Rangers is named like this to differentiate two New York teams
+    team("NYR", "NY Rangers", "Rangers", &["blueshirts"]),
+    team("PHI", "Philadelphia", "Flyers", &["philly"]),
+    team("PIT", "Pittsburgh", "Penguins", &["pens"]),
+    team("WSH", "Washington", "Capitals", &["caps"]),
+    team("CAR", "Carolina", "Hurricanes", &["canes"]),
+    team("CHI", "Chicago", "Blackhawks", &["hawks"]),
+    team("CBJ", "Columbus", "Blue Jackets", &["jackets"]),
+    team("DAL", "Dallas", "Stars", &[]),
+    team("DET", "Detroit", "Red Wings", &["wings"]),
+    team("FLA", "Florida", "Panthers", &["cats"]),
+    team("NSH", "Nashville", "Predators", &["preds"]),
+    team("TBL", "Tampa Bay", "Lightning", &["tampa", "bolts", "tb"]),
+    team("ANA", "Anaheim", "Ducks", &[]),
+    team("COL", "Colorado", "Avalanche", &["avs"]),
+    team("LAK", "Los Angeles", "Kings", &["la"]),
+    team("MIN", "Minnesota", "Wild", &[]),
+    team("SJS", "San Jose", "Sharks", &["sj"]),
+    team("STL", "St. Louis", "Blues", &["st louis"]),
+    team(
+        "VGK",
+        "Vegas",
+        "Golden Knights",
+        &["knights", "vegas golden knights"],
+    ),
+    team("CGY", "Calgary", "Flames", &[]),
+    team("EDM", "Edmonton", "Oilers", &["oil"]),
+    team("MTL", "Montreal", "Canadiens", &["habs", "montréal"]),
+    team("OTT", "Ottawa", "Senators", &["sens"]),
+    team("TOR", "Toronto", "Maple Leafs", &["leafs"]),
+    team("VAN", "Vancouver", "Canucks", &["nucks"]),
+    team("WPG", "Winnipeg", "Jets", &[]),
+    team("SEA", "Seattle", "Kraken", &[]),
+    team("UTA", "Utah", "Hockey Club", &["utah hc"]),
+];
+
+/// A team and every name it can be asked for with, for `235 teams`
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct TeamListing {
+    pub abbreviation: &'static str,
+    pub name: &'static str,
+    pub full_name: String,
+    pub aliases: &'static [&'static str],
+}
+
+/// Every team by abbreviation
+pub fn team_listing() -> Vec<TeamListing> {
+    let mut teams: Vec<TeamListing> = TEAMS
+        .iter()
+        .map(|team| TeamListing {
+            abbreviation: team.abbreviation,
+            name: team.name,
+            full_name: team.full_name(),
+            aliases: team.aliases,
+        })
+        .collect();
+    teams.sort_by_key(|team| team.abbreviation);
+    teams
+}
+
+/// Names Yle's Finnish page used where they differ from `Team::name`
+const FINNISH_NAMES: [(&str, &str); 1] = [("TBL", "Tampa")];
+
+pub fn translate_team_name(abbr: &str) -> String {
+    let city = match TEAMS.iter().find(|team| team.abbreviation == abbr) {
+        Some(team) => team.name,
+        None => "[unknown]",
+    };
+
+    String::from(city)
+}
+
+/// Name of a team on the page in `lang`, the Finnish page shortens a few
+/// names further like Yle did
+pub fn localized_team_name(abbr: &str, lang: Lang) -> String {
+    match lang {
+        Lang::Fi => FINNISH_NAMES
+            .iter()
+            .find(|(team, _)| *team == abbr)
+            .map(|(_, name)| String::from(*name))
+            .unwrap_or_else(|| translate_team_name(abbr)),
+        _ => translate_team_name(abbr),
+    }
+}
+
+/// Finds a team by its abbreviation, name, nickname or a common alias
+/// like "pens" or "leafs", ignoring case
+pub fn resolve_team(name: &str) -> Option<&'static Team> {
+    let name = name.trim().to_lowercase();
+    TEAMS.iter().find(|team| {
+        team.abbreviation.eq_ignore_ascii_case(&name)
+            || team.name.to_lowercase() == name
+            || team.nickname.to_lowercase() == name
+            || team.aliases.contains(&name.as_str())
+    })
+}
+
+/// Result of matching a name that may be misspelled
+#[derive(Debug, PartialEq)]
+pub enum NameMatch<T> {
+    Found(T),
+    /// Nothing matched but this one is close
+    Suggestion(T),
+    Unknown,
+}
+
+/// Result of matching a team name that may be misspelled
+pub type TeamMatch = NameMatch<&'static Team>;
+
+/// A division or a conference and the names people use for it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Group {
+    pub abbreviation: &'static str,
+    /// Name the standings use, like "Metropolitan"
+    pub name: &'static str,
+    /// Common short names, lowercase
+    pub aliases: &'static [&'static str],
+}
+
+pub const DIVISIONS: [Group; 4] = [
+    Group {
+        abbreviation: "ATL",
+        name: "Atlantic",
+        aliases: &[],
+    },
+    Group {
+        abbreviation: "MET",
+        name: "Metropolitan",
+        aliases: &["metro"],
+    },
+    Group {
+        abbreviation: "CEN",
+        name: "Central",
+        aliases: &[],
+    },
+    Group {
+        abbreviation: "PAC",
+        name: "Pacific",
+        aliases: &[],
+    },
+];
+
+pub const CONFERENCES: [Group; 2] = [
+    Group {
+        abbreviation: "EAST",
+        name: "Eastern",
+        aliases: &["e"],
+    },
+    Group {
+        abbreviation: "WEST",
+        name: "Western",
+        aliases: &["w"],
+    },
+];
+
+/// Matches a team like `resolve_team` but also accepts an unambiguous
+/// beginning of a name ("pitt", "Pittsburg") and suggests the closest team
+/// for typos
+pub fn match_team(name: &str) -> TeamMatch {
+    match_name(name, &TEAMS, names)
+}
+
+/// Matches a division by its abbreviation or name the same way as teams
+pub fn match_division(name: &str) -> NameMatch<&'static Group> {
+    match_name(name, &DIVISIONS, group_names)
+}
+
+/// Matches a conference by its abbreviation or name the same way as teams
+pub fn match_conference(name: &str) -> NameMatch<&'static Group> {
+    match_name(name, &CONFERENCES, group_names)
+}
+
+fn match_name<T>(
+    name: &str,
+    candidates: &'static [T],
+    names: fn(&T) -> Vec<String>,
+) -> NameMatch<&'static T> {
+    let name = name.trim().to_lowercase();
+    if let Some(found) = candidates
+        .iter()
+        .find(|candidate| names(candidate).contains(&name))
+    {
+        return NameMatch::Found(found);
+    }
+
+    if name.chars().count() >= 3 {
+        let mut prefixed = candidates.iter().filter(|candidate| {
+            names(candidate)
+                .iter()
+                .any(|candidate| candidate.starts_with(&name))
+        });
+        if let (Some(found), None) = (prefixed.next(), prefixed.next()) {
+            return NameMatch::Found(found);
+        }
+    }
+
+    // Allow roughly one typo per four letters
+    let max_distance = (name.chars().count() / 4).max(1);
+    let closest = candidates
+        .iter()
+        .map(|candidate| {
+            let distance = names(candidate)
+                .iter()
+                .map(|candidate| edit_distance(&name, candidate))
+                .min()
+                .unwrap_or(usize::MAX);
+            (distance, candidate)
+        })
+        .min_by_key(|(distance, _)| *distance);
+    match closest {
+        Some((distance, found)) if distance <= max_distance => NameMatch::Suggestion(found),
+        _ => NameMatch::Unknown,
+    }
+}
+
+fn names(team: &Team) -> Vec<String> {
+    [team.abbreviation, team.name, team.nickname]
+        .iter()
+        .chain(team.aliases.iter())
+        .map(|name| name.to_lowercase())
+        .collect()
+}
+
+fn group_names(group: &Group) -> Vec<String> {
+    [group.abbreviation, group.name]
+        .iter()
+        .chain(group.aliases.iter())
+        .map(|name| name.to_lowercase())
+        .collect()
+}
+
+/// Levenshtein distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_resolves_team_names() {
+        let abbreviation = |name| resolve_team(name).map(|team| team.abbreviation);
+        assert_eq!(abbreviation("pit"), Some("PIT"));
+        assert_eq!(abbreviation("Pens"), Some("PIT"));
+        assert_eq!(abbreviation("leafs"), Some("TOR"));
+        assert_eq!(abbreviation("Maple Leafs"), Some("TOR"));
+        assert_eq!(abbreviation("NY Rangers"), Some("NYR"));
+        assert_eq!(abbreviation("Pittsburg"), None);
+    }
+
+    #[test]
+    fn it_lists_every_team_by_abbreviation() {
+        let teams = team_listing();
+        assert_eq!(teams.len(), 32);
+        assert_eq!(teams[0].abbreviation, "ANA");
+        assert_eq!(teams[0].full_name, "Anaheim Ducks");
+        let islanders = teams.iter().find(|team| team.abbreviation == "NYI");
+        assert_eq!(
+            islanders.map(|team| team.full_name.as_str()),
+            Some("New York Islanders")
+        );
+    }
+
+    #[test]
+    fn it_matches_misspelled_team_names() {
+        let pittsburgh = resolve_team("PIT").unwrap();
+        assert_eq!(match_team("pens"), TeamMatch::Found(pittsburgh));
+        assert_eq!(match_team("PITT"), TeamMatch::Found(pittsburgh));
+        assert_eq!(match_team("Pittsburg"), TeamMatch::Found(pittsburgh));
+        assert_eq!(match_team("Pitsburgh"), TeamMatch::Suggestion(pittsburgh));
+        assert_eq!(match_team("PIY"), TeamMatch::Suggestion(pittsburgh));
+        // Blues, Blue Jackets and Rangers (blueshirts) all start with "blue"
+        assert!(!matches!(match_team("blue"), TeamMatch::Found(_)));
+        assert_eq!(match_team("xyzzy"), TeamMatch::Unknown);
+    }
+
+    #[test]
+    fn it_matches_divisions_and_conferences() {
+        let metropolitan = &DIVISIONS[1];
+        assert_eq!(match_division("MET"), NameMatch::Found(metropolitan));
+        assert_eq!(match_division("metro"), NameMatch::Found(metropolitan));
+        assert_eq!(
+            match_division("Metropolitan"),
+            NameMatch::Found(metropolitan)
+        );
+        assert_eq!(match_division("MEY"), NameMatch::Suggestion(metropolitan));
+        assert_eq!(match_conference("EAST"), NameMatch::Found(&CONFERENCES[0]));
+        assert_eq!(match_conference("west"), NameMatch::Found(&CONFERENCES[1]));
+        assert_eq!(match_conference("north"), NameMatch::Unknown);
+    }
+}
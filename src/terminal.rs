@@ -0,0 +1,147 @@
+//! What the page is written to. The `235` binary detects this once at
+//! startup and hands it to everything that prints, so the checks aren't
+//! repeated for every line and the choices can be tested without a real
+//! terminal.
+
+use crate::options::RenderOptions;
+use crate::render::{AnsiRenderer, HyperlinkRenderer, PlainRenderer, Renderer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TerminalInfo {
+    /// Standard output is an interactive terminal, not a pipe or a file
+    pub is_terminal: bool,
+    /// The terminal can show ANSI colours, which `TERM=dumb` ones can't
+    pub colors: bool,
+    /// The terminal makes OSC 8 hyperlinks clickable, see
+    /// `supports_hyperlinks`
+    pub hyperlinks: bool,
+}
+
+impl TerminalInfo {
+    /// Describes a terminal from whether standard output is one and the
+    /// value of `TERM`
+    pub fn new(is_terminal: bool, term: Option<&str>) -> TerminalInfo {
+        TerminalInfo {
+            is_terminal,
+            colors: is_terminal && term != Some("dumb"),
+            hyperlinks: false,
+        }
+    }
+
+    /// Turns on hyperlinks if the terminal shows colours and the
+    /// environment, read through `var`, tells it supports them
+    pub fn with_hyperlinks(self, var: impl Fn(&str) -> Option<String>) -> TerminalInfo {
+        TerminalInfo {
+            hyperlinks: self.colors && supports_hyperlinks(var),
+            ..self
+        }
+    }
+
+    /// Colours are only used on a terminal that can show them, and only
+    /// when `options.colors` allows. Links come with the colours on
+    /// terminals that support them.
+    pub fn renderer(&self, options: &RenderOptions) -> &'static dyn Renderer {
+        match (self.colors && options.colors, self.hyperlinks) {
+            (true, true) => &HyperlinkRenderer,
+            (true, false) => &AnsiRenderer,
+            (false, _) => &PlainRenderer,
+        }
+    }
+}
+
+/// Whether the terminal is known to support OSC 8 hyperlinks. There's no
+/// way to ask, so this goes by the variables terminals set like
+/// `TERM_PROGRAM` and `VTE_VERSION`. Unknown terminals get no links
+/// since some print the escape codes as text. `FORCE_HYPERLINK=1` turns
+/// them on and `FORCE_HYPERLINK=0` off either way.
+pub fn supports_hyperlinks(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return !force.is_empty() && force != "0";
+    }
+    if var("WT_SESSION").is_some() || var("KONSOLE_VERSION").is_some() || var("DOMTERM").is_some() {
+        return true;
+    }
+    let vte = var("VTE_VERSION").and_then(|version| version.parse::<u32>().ok());
+    if vte.is_some_and(|version| version >= 5000) {
+        return true;
+    }
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    ["iTerm.app", "WezTerm", "vscode", "ghostty"].contains(&program.as_str())
+        || ["xterm-kitty", "alacritty", "foot", "xterm-ghostty"].contains(&term.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{Role, StyledLine};
+
+    #[test]
+    fn it_detects_hyperlink_support() {
+        let supports = |vars: &[(&str, &str)]| {
+            supports_hyperlinks(|name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert!(supports(&[("TERM_PROGRAM", "iTerm.app")]));
+        assert!(supports(&[("VTE_VERSION", "7600")]));
+        assert!(!supports(&[("VTE_VERSION", "4600")]));
+        assert!(!supports(&[("TERM", "xterm-256color")]));
+        assert!(supports(&[("TERM", "screen"), ("FORCE_HYPERLINK", "1")]));
+        assert!(!supports(&[
+            ("TERM", "xterm-kitty"),
+            ("FORCE_HYPERLINK", "0")
+        ]));
+    }
+
+    #[test]
+    fn it_links_only_on_a_terminal_with_colours_and_links() {
+        let vars = |name: &str| match name {
+            "TERM_PROGRAM" => Some(String::from("WezTerm")),
+            _ => None,
+        };
+        let mut line = StyledLine::default();
+        line.push_link("Pittsburgh", Role::Header, Some(String::from("https://x")));
+        let options = RenderOptions::default();
+
+        let linked = TerminalInfo::new(true, Some("xterm")).with_hyperlinks(vars);
+        assert!(linked
+            .renderer(&options)
+            .render_lines(std::slice::from_ref(&line))
+            .starts_with("\x1b]8;;https://x\x1b\\"));
+        let piped = TerminalInfo::new(false, Some("xterm")).with_hyperlinks(vars);
+        assert_eq!(
+            piped
+                .renderer(&options)
+                .render_lines(std::slice::from_ref(&line)),
+            "Pittsburgh\n"
+        );
+        let plain = linked.renderer(&options.colors(false));
+        assert_eq!(
+            plain.render_lines(std::slice::from_ref(&line)),
+            "Pittsburgh\n"
+        );
+    }
+
+    #[test]
+    fn it_uses_colours_only_on_a_capable_terminal() {
+        let coloured = |terminal: TerminalInfo, options: &RenderOptions| {
+            terminal.renderer(options).paint("x", Role::FinalScore) != "x"
+        };
+        let options = RenderOptions::default();
+
+        assert!(coloured(
+            TerminalInfo::new(true, Some("xterm-256color")),
+            &options
+        ));
+        assert!(coloured(TerminalInfo::new(true, None), &options));
+        assert!(!coloured(TerminalInfo::new(true, Some("dumb")), &options));
+        assert!(!coloured(TerminalInfo::new(false, Some("xterm")), &options));
+        assert!(!coloured(
+            TerminalInfo::new(true, Some("xterm")),
+            &options.colors(false)
+        ));
+    }
+}
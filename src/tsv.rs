@@ -0,0 +1,99 @@
+//! The games as tab-separated values for shell pipelines. Every game is a
+//! `game` line followed by a `goal` line per goal, so `awk` and `cut` can
+//! pick either by the first column. Fields are never padded or coloured
+//! and a long name can't shift the columns after it. Shootout attempts
+//! aren't goals and are left out.
+
+use crate::game::{Game, Goal, Player};
+use crate::render::Section;
+
+/// The games of all `sections`, with the columns
+///
+/// - `game`, date, home, away, home score, away score, status
+/// - `goal`, team, scorer, assists, minute, period, strength
+///
+/// Assists are separated by semicolons.
+pub fn games_tsv(sections: &[Section]) -> String {
+    let mut output = String::new();
+    for section in sections {
+        for game in section.games.iter().flatten() {
+            push_row(&mut output, &game_row(&section.title, game));
+            for goal in game.goals.iter().filter(|goal| goal.period != "SO") {
+                push_row(&mut output, &goal_row(goal));
+            }
+        }
+    }
+    output
+}
+
+fn game_row(date: &str, game: &Game) -> Vec<String> {
+    vec![
+        String::from("game"),
+        date.to_string(),
+        game.home.to_string(),
+        game.away.to_string(),
+        game.home_score.to_string(),
+        game.away_score.to_string(),
+        game.status.to_string(),
+    ]
+}
+
+fn goal_row(goal: &Goal) -> Vec<String> {
+    let assists: Vec<String> = goal.assists.iter().map(full_name).collect();
+    vec![
+        String::from("goal"),
+        goal.team.to_string(),
+        full_name(&goal.scorer),
+        assists.join(";"),
+        goal.minute.to_string(),
+        goal.period.to_string(),
+        goal.strength.clone().unwrap_or_default(),
+    ]
+}
+
+fn push_row(output: &mut String, fields: &[String]) {
+    let fields: Vec<String> = fields.iter().map(|field| clean(field)).collect();
+    output.push_str(&fields.join("\t"));
+    output.push('\n');
+}
+
+fn full_name(player: &Player) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+}
+
+/// Turns tabs and line breaks into spaces so a field stays in its column
+pub(crate) fn clean(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::APIResponse;
+    use crate::game::parse_games;
+
+    #[test]
+    fn it_writes_a_line_per_game_and_goal() {
+        let json = include_str!("../tests/fixtures/live.json");
+        let scores: APIResponse = serde_json::from_str(json).unwrap();
+        let sections = [Section {
+            title: String::from("2024-10-15"),
+            games: parse_games(scores),
+        }];
+
+        let tsv = games_tsv(&sections);
+        let rows: Vec<&str> = tsv.lines().collect();
+        assert!(rows[0].starts_with("game\t2024-10-15\tPIT\tTOR\t"));
+        assert_eq!(
+            rows[2],
+            "goal\tPIT\tSidney Crosby\tEvgeni Malkin;Erik Karlsson\t32\t2\tPPG"
+        );
+        assert!(rows.iter().all(|row| row.split('\t').count() == 7));
+    }
+
+    #[test]
+    fn it_keeps_fields_in_their_columns() {
+        assert_eq!(clean("Crosby"), "Crosby");
+        assert_eq!(clean("Sidney\tCrosby\n"), "Sidney Crosby ");
+    }
+}
@@ -0,0 +1,1211 @@
+//! `235 tui`, the scoreboard as an interactive terminal UI. The games are
+//! listed in one pane and the selected game is expanded in another with
+//! every goal and its assists, which the teletext page has no room for.
+//! Standings and the schedule of the next games are on tabs of their own,
+//! fetched again with the scores at the pace of watch mode.
+//!
+//! The layout of the lines comes from the library so the TUI agrees with
+//! the printed page, only the panes and key handling live here. The keys
+//! can be changed under `[tui.keys]` in `$HOME/.235.toml`.
+//!
+//! Refreshes are compared like in watch mode with `events::diff`, and a
+//! game whose score changed blinks on the list for a moment with its new
+//! goals scrolled into view.
+//!
+//! `/` narrows the list down to games of a team or with goals by a player
+//! as the name is typed, which helps on nights with a dozen games or more.
+//!
+//! A click on a game selects it and a click on its recap link opens the
+//! link in the browser, unless `mouse = false` under `[tui]` leaves the
+//! mouse to the terminal for selecting text.
+
+use chrono::{Local, Utc};
+use nhl_235::events::{diff, refresh_delay, Event as GameEvent, NotifyState, WatchOptions};
+use nhl_235::i18n::Text;
+use nhl_235::links::game_url;
+use nhl_235::render::{
+    annotation_line, broadcasts_line, duration_line, header, score_text, series_line,
+    shootout_line, standings_lines, stats_line, venue_line, Colour, Section,
+};
+use nhl_235::schedule::{schedule_lines, upcoming_games};
+use nhl_235::standings::Division;
+use nhl_235::style::StyledLine;
+use nhl_235::teams::TEAMS;
+use nhl_235::{ClientError, Game, Goal, Player, RenderOptions};
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+    MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::layout::{Constraint, Flex, Layout, Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::HashMap;
+use std::io;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long a game whose score changed blinks on the list
+const FLASH: Duration = Duration::from_secs(4);
+const BLINK: Duration = Duration::from_millis(500);
+
+/// A score change being shown, until `until`
+struct Flash {
+    until: Instant,
+    /// Goals of the game before the change, the ones after it are new
+    goals_before: usize,
+}
+
+impl Flash {
+    /// Whether the blink is in its inverted half at `now`
+    fn is_on(&self, now: Instant) -> bool {
+        let elapsed = FLASH.saturating_sub(self.until.saturating_duration_since(now));
+        (elapsed.as_millis() / BLINK.as_millis()).is_multiple_of(2)
+    }
+}
+
+/// Where the tabs get their data. Only the tab on screen is fetched on a
+/// refresh, the others when they're switched to.
+pub trait Provider {
+    fn scores(&self) -> Result<Vec<Section>, ClientError>;
+    fn standings(&self) -> Result<Vec<Division>, ClientError>;
+    /// Games of today and tomorrow, of which the ones still to be played
+    /// are listed
+    fn schedule(&self) -> Result<Vec<Section>, ClientError>;
+    /// New options when the config files have changed since the previous
+    /// call, checked before every refresh
+    fn reload(&self) -> Option<Result<RenderOptions, String>>;
+}
+
+/// What a key does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Quit,
+    Down,
+    Up,
+    Refresh,
+    Help,
+    Highlights,
+    Stats,
+    Favourites,
+    Search,
+    Tab(Tab),
+}
+
+/// Actions by their name under `[tui.keys]`, their default key and what
+/// the help overlay says about them
+const ACTIONS: [(&str, Action, char, &str); 12] = [
+    ("quit", Action::Quit, 'q', "quit"),
+    ("down", Action::Down, 'j', "next game"),
+    ("up", Action::Up, 'k', "previous game"),
+    ("refresh", Action::Refresh, 'r', "refresh now"),
+    ("help", Action::Help, '?', "this help"),
+    ("highlights", Action::Highlights, 'h', "highlights on/off"),
+    ("stats", Action::Stats, 's', "stats on/off"),
+    (
+        "favourites",
+        Action::Favourites,
+        'f',
+        "only favourite teams",
+    ),
+    ("search", Action::Search, '/', "search teams and scorers"),
+    ("scores", Action::Tab(Tab::Scores), '1', "scores"),
+    ("standings", Action::Tab(Tab::Standings), '2', "standings"),
+    ("schedule", Action::Tab(Tab::Schedule), '3', "schedule"),
+];
+
+/// Keys and their actions. The arrow keys and Esc always work on top of
+/// the keys that can be changed.
+pub struct Keymap {
+    keys: Vec<(KeyCode, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap {
+            keys: ACTIONS
+                .iter()
+                .map(|(_, action, key, _)| (KeyCode::Char(*key), *action))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// The default keys with the ones from `[tui.keys]` in place of them,
+    /// like `refresh = "u"`
+    pub fn with_keys(keys: &HashMap<String, String>) -> Result<Keymap, String> {
+        let mut keymap = Keymap::default();
+        for (name, key) in keys {
+            let action = ACTIONS
+                .iter()
+                .find(|(known, ..)| known == name)
+                .map(|(_, action, ..)| *action)
+                .ok_or_else(|| format!("unknown action '{}' in [tui.keys]", name))?;
+            let code = parse_key(key).ok_or_else(|| {
+                format!(
+                    "invalid key '{}' for {} in [tui.keys], use a single character",
+                    key, name
+                )
+            })?;
+            for binding in keymap.keys.iter_mut() {
+                if binding.1 == action {
+                    binding.0 = code;
+                }
+            }
+        }
+        for (i, (code, action)) in keymap.keys.iter().enumerate() {
+            if let Some((_, other)) = keymap.keys[i + 1..].iter().find(|(key, _)| key == code) {
+                return Err(format!(
+                    "key '{}' is set for both {} and {} in [tui.keys]",
+                    key_name(*code),
+                    action_name(*action),
+                    action_name(*other)
+                ));
+            }
+        }
+        Ok(keymap)
+    }
+
+    fn action(&self, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Esc => Some(Action::Quit),
+            KeyCode::Down => Some(Action::Down),
+            KeyCode::Up => Some(Action::Up),
+            _ => self
+                .keys
+                .iter()
+                .find(|(key, _)| *key == code)
+                .map(|(_, action)| *action),
+        }
+    }
+
+    fn key(&self, action: Action) -> String {
+        self.keys
+            .iter()
+            .find(|(_, bound)| *bound == action)
+            .map(|(code, _)| key_name(*code))
+            .unwrap_or_default()
+    }
+}
+
+fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_whitespace() => Some(KeyCode::Char(c)),
+        _ => None,
+    }
+}
+
+fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+fn action_name(action: Action) -> &'static str {
+    ACTIONS
+        .iter()
+        .find(|(_, known, ..)| *known == action)
+        .map(|(name, ..)| *name)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Scores,
+    Standings,
+    Schedule,
+}
+
+impl Tab {
+    const ALL: [Tab; 3] = [Tab::Scores, Tab::Standings, Tab::Schedule];
+
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Scores => "Scores",
+            Tab::Standings => "Standings",
+            Tab::Schedule => "Schedule",
+        }
+    }
+}
+
+struct App {
+    options: RenderOptions,
+    keymap: Keymap,
+    tab: Tab,
+    /// Every fetched game, of which `shown` are listed
+    games: Vec<Game>,
+    /// What the previous refresh had, to tell which scores changed
+    seen: NotifyState,
+    /// Score changes by game id
+    flashes: HashMap<String, Flash>,
+    /// Indexes of the listed games in `games`
+    shown: Vec<usize>,
+    /// Position in `shown`
+    selected: ListState,
+    favourites_only: bool,
+    /// Text the games are searched by, empty for all games
+    query: String,
+    /// The query is being typed, keys go to it instead of the keymap
+    searching: bool,
+    standings: Vec<Division>,
+    schedule: Vec<Section>,
+    /// Lines scrolled down on the standings and schedule tabs
+    scroll: u16,
+    help: bool,
+    /// Shown at the bottom instead of the help, like a failed refresh
+    status: Option<String>,
+    /// Where the list of games was last drawn, for clicks on it
+    list_area: Rect,
+    /// Where the recap link of the selected game was last drawn
+    link: Option<(Rect, String)>,
+}
+
+impl App {
+    fn new(sections: Vec<Section>, options: RenderOptions, keymap: Keymap) -> App {
+        let mut app = App {
+            options,
+            keymap,
+            tab: Tab::Scores,
+            games: Vec::new(),
+            seen: NotifyState::default(),
+            flashes: HashMap::new(),
+            shown: Vec::new(),
+            selected: ListState::default(),
+            favourites_only: false,
+            query: String::new(),
+            searching: false,
+            standings: Vec::new(),
+            schedule: Vec::new(),
+            scroll: 0,
+            help: false,
+            status: None,
+            list_area: Rect::default(),
+            link: None,
+        };
+        app.replace_games(sections);
+        app
+    }
+
+    /// Fetches the data of the tab on screen again. A failure keeps the
+    /// old data up with a note at the bottom.
+    fn refresh(&mut self, provider: &impl Provider) {
+        let reloaded = provider.reload();
+        if let Some(Ok(options)) = &reloaded {
+            // The toggles stay as they were set with the keys
+            let mut options = options.clone();
+            options.show_highlights = self.options.show_highlights;
+            options.show_stats = self.options.show_stats;
+            self.options = options;
+        }
+        let result = match self.tab {
+            Tab::Scores => provider
+                .scores()
+                .map(|sections| self.replace_games(sections)),
+            Tab::Standings => provider
+                .standings()
+                .map(|standings| self.standings = standings),
+            Tab::Schedule => provider.schedule().map(|schedule| self.schedule = schedule),
+        };
+        self.status = match (result, reloaded) {
+            (Err(_), _) => Some(format!(
+                "Can't refresh the {}, try again later",
+                self.tab.title().to_lowercase()
+            )),
+            (Ok(()), Some(Err(e))) => Some(format!("Config not reloaded: {}", e)),
+            (Ok(()), _) => None,
+        };
+    }
+
+    fn switch_tab(&mut self, tab: Tab, provider: &impl Provider) {
+        if tab != self.tab {
+            self.tab = tab;
+            self.scroll = 0;
+            self.refresh(provider);
+        }
+    }
+
+    /// Takes in refreshed games, keeping the same game selected when it's
+    /// still on the list. Games with new goals start blinking, apart from
+    /// on the first load when every goal is new.
+    fn replace_games(&mut self, sections: Vec<Section>) {
+        let selected_id = self.selected_game().map(|game| game.id.clone());
+        let games: Vec<Option<Game>> = sections
+            .into_iter()
+            .flat_map(|section| section.games)
+            .collect();
+        let (events, seen) = diff(&self.seen, &games, &WatchOptions::default());
+        if !self.seen.games.is_empty() {
+            let until = Instant::now() + FLASH;
+            for event in events {
+                if let GameEvent::Goal { game_id, .. } = event {
+                    let goals_before = self
+                        .games
+                        .iter()
+                        .find(|game| game.id == game_id)
+                        .map_or(0, |game| game.goals.len());
+                    self.flashes
+                        .entry(game_id)
+                        .or_insert(Flash {
+                            until,
+                            goals_before,
+                        })
+                        .until = until;
+                }
+            }
+        }
+        self.seen = seen;
+        self.games = games.into_iter().flatten().collect();
+        self.filter(selected_id);
+    }
+
+    /// Time until the next refresh, `interval` or by the state of the
+    /// games
+    fn delay(&self, interval: Option<Duration>) -> Duration {
+        interval.unwrap_or_else(|| {
+            refresh_delay(&self.games, &self.options.favorite_teams, &Local::now())
+        })
+    }
+
+    /// Stops the blinks that are over
+    fn tick(&mut self, now: Instant) {
+        self.flashes.retain(|_, flash| flash.until > now);
+    }
+
+    /// The score change of `game` when it's in the inverted half of the
+    /// blink
+    fn flash_on(&self, game: &Game, now: Instant) -> Option<&Flash> {
+        self.flashes.get(&game.id).filter(|flash| flash.is_on(now))
+    }
+
+    /// Lists the games that pass the filters, selecting the game with
+    /// `selected_id` or the first one
+    fn filter(&mut self, selected_id: Option<String>) {
+        let favourites = &self.options.favorite_teams;
+        let query = self.query.to_lowercase();
+        self.shown = self
+            .games
+            .iter()
+            .enumerate()
+            .filter(|(_, game)| {
+                !self.favourites_only
+                    || favourites.contains(&game.home)
+                    || favourites.contains(&game.away)
+            })
+            .filter(|(_, game)| matches_search(game, &query, &self.options))
+            .map(|(index, _)| index)
+            .collect();
+        let games = &self.games;
+        let position = selected_id
+            .and_then(|id| self.shown.iter().position(|&index| games[index].id == id))
+            .or(if self.shown.is_empty() { None } else { Some(0) });
+        self.selected.select(position);
+    }
+
+    fn toggle_favourites(&mut self) {
+        if self.options.favorite_teams.is_empty() {
+            self.status = Some(String::from(
+                "No favourite teams, set favorites in $HOME/.235.toml",
+            ));
+            return;
+        }
+        self.favourites_only = !self.favourites_only;
+        let selected_id = self.selected_game().map(|game| game.id.clone());
+        self.filter(selected_id);
+    }
+
+    /// Takes a key typed into the search. The list follows every key,
+    /// Enter keeps the search and Esc drops it.
+    fn type_search(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.query.push(c),
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                self.query.clear();
+            }
+            _ => return,
+        }
+        let selected_id = self.selected_game().map(|game| game.id.clone());
+        self.filter(selected_id);
+    }
+
+    fn shown_games(&self) -> impl Iterator<Item = &Game> {
+        self.shown.iter().map(move |&index| &self.games[index])
+    }
+
+    fn selected_game(&self) -> Option<&Game> {
+        self.selected
+            .selected()
+            .and_then(|position| self.shown.get(position))
+            .map(|&index| &self.games[index])
+    }
+
+    /// Moves down the list of games, or scrolls the other tabs
+    fn select_next(&mut self) {
+        if self.tab != Tab::Scores {
+            self.scroll = self.scroll.saturating_add(1);
+        } else if let Some(position) = self.selected.selected() {
+            self.selected
+                .select(Some((position + 1).min(self.shown.len().saturating_sub(1))));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if self.tab != Tab::Scores {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else if let Some(position) = self.selected.selected() {
+            self.selected.select(Some(position.saturating_sub(1)));
+        }
+    }
+
+    /// Selects the game clicked on the list. Returns the link when the
+    /// recap link was clicked.
+    fn click(&mut self, column: u16, row: u16) -> Option<String> {
+        let position = Rect::new(column, row, 1, 1);
+        if self.help || self.tab != Tab::Scores {
+            return None;
+        }
+        if let Some((area, url)) = &self.link {
+            if area.intersects(position) {
+                return Some(url.clone());
+            }
+        }
+        // The rows start inside the border of the list
+        let inner = self.list_area.inner(Margin::new(1, 1));
+        if inner.intersects(position) {
+            let clicked = self.selected.offset() + (row - inner.y) as usize;
+            if clicked < self.shown.len() {
+                self.selected.select(Some(clicked));
+            }
+        }
+        None
+    }
+
+    /// Does what a key is for. Returns false when it's time to quit.
+    fn handle(&mut self, action: Action, provider: &impl Provider) -> bool {
+        // Any key closes the help
+        if self.help {
+            self.help = false;
+            return true;
+        }
+        match action {
+            // A search is dropped before quitting
+            Action::Quit if !self.query.is_empty() => self.type_search(KeyCode::Esc),
+            Action::Quit => return false,
+            Action::Down => self.select_next(),
+            Action::Up => self.select_previous(),
+            Action::Refresh => self.refresh(provider),
+            Action::Help => self.help = true,
+            Action::Highlights => self.options.show_highlights = !self.options.show_highlights,
+            Action::Stats => self.options.show_stats = !self.options.show_stats,
+            Action::Favourites => self.toggle_favourites(),
+            Action::Search => {
+                self.switch_tab(Tab::Scores, provider);
+                self.searching = true;
+            }
+            Action::Tab(tab) => self.switch_tab(tab, provider),
+        }
+        true
+    }
+}
+
+/// Runs the TUI until the user quits, fetching the tab on screen again
+/// from `provider` every `interval`, or at the pace of
+/// `events::refresh_delay` without one, and when the user asks for it.
+/// `mouse` captures the mouse for clicks on games and links.
+pub fn run(
+    sections: Vec<Section>,
+    provider: &impl Provider,
+    interval: Option<Duration>,
+    options: &RenderOptions,
+    keymap: Keymap,
+    mouse: bool,
+) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    if mouse {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
+    let app = App::new(sections, options.clone(), keymap);
+    let result = event_loop(&mut terminal, app, provider, interval);
+    if mouse {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+    }
+    ratatui::restore();
+    result
+}
+
+/// Opens `url` in the default browser without waiting for it
+fn open_url(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    mut app: App,
+    provider: &impl Provider,
+    interval: Option<Duration>,
+) -> io::Result<()> {
+    let mut next_refresh = Instant::now() + app.delay(interval);
+    loop {
+        app.tick(Instant::now());
+        terminal.draw(|frame| draw(frame, &mut app))?;
+        let mut timeout = next_refresh.saturating_duration_since(Instant::now());
+        if !app.flashes.is_empty() {
+            timeout = timeout.min(BLINK);
+        }
+        if !event::poll(timeout)? {
+            if Instant::now() < next_refresh {
+                // Only time for the next blink
+                continue;
+            }
+            app.refresh(provider);
+            next_refresh = Instant::now() + app.delay(interval);
+            continue;
+        }
+        let action = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press && app.searching => {
+                app.type_search(key.code);
+                continue;
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press => app.keymap.action(key.code),
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    if let Some(url) = app.click(mouse.column, mouse.row) {
+                        if open_url(&url).is_err() {
+                            app.status = Some(format!("Can't open a browser for {}", url));
+                        }
+                    }
+                    continue;
+                }
+                MouseEventKind::ScrollDown => Some(Action::Down),
+                MouseEventKind::ScrollUp => Some(Action::Up),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        if let Some(action) = action {
+            if !app.handle(action, provider) {
+                return Ok(());
+            }
+            if matches!(action, Action::Refresh | Action::Tab(_)) {
+                next_refresh = Instant::now() + app.delay(interval);
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [tabs_area, main, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let titles = Tab::ALL
+        .iter()
+        .map(|tab| format!("{} {}", app.keymap.key(Action::Tab(*tab)), tab.title()));
+    let selected = Tab::ALL.iter().position(|tab| *tab == app.tab);
+    frame.render_widget(
+        Tabs::new(titles)
+            .select(selected)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        tabs_area,
+    );
+
+    match app.tab {
+        Tab::Scores => draw_scores(frame, app, main),
+        Tab::Standings => draw_page(
+            frame,
+            standings_lines(&app.standings, &app.options),
+            app.scroll,
+            main,
+        ),
+        Tab::Schedule => {
+            let games = if app.options.deterministic {
+                upcoming_games(&app.schedule, &Utc)
+            } else {
+                upcoming_games(&app.schedule, &Local)
+            };
+            draw_page(
+                frame,
+                schedule_lines(&games, &app.options),
+                app.scroll,
+                main,
+            )
+        }
+    }
+
+    let footer_text = if app.searching {
+        format!("/{}", app.query)
+    } else if let Some(status) = &app.status {
+        status.clone()
+    } else if !app.query.is_empty() {
+        format!(
+            "Search: {}  {} new search  Esc all games",
+            app.query,
+            app.keymap.key(Action::Search)
+        )
+    } else {
+        format!(
+            "↑/↓ select  {} refresh  {} help  {} quit",
+            app.keymap.key(Action::Refresh),
+            app.keymap.key(Action::Help),
+            app.keymap.key(Action::Quit)
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(footer_text).style(Style::default().fg(Color::Yellow)),
+        footer,
+    );
+
+    if app.help {
+        draw_help(frame, &app.keymap, main);
+    }
+}
+
+/// The games on the left and the selected game on the right
+fn draw_scores(frame: &mut Frame, app: &mut App, area: Rect) {
+    let [list_area, detail_area] =
+        Layout::horizontal([Constraint::Length(46), Constraint::Min(30)]).areas(area);
+
+    let now = Instant::now();
+    let items: Vec<ListItem> = app
+        .shown_games()
+        .map(|game| {
+            let item = ListItem::new(summary_line(game, &app.options));
+            match app.flash_on(game, now) {
+                Some(_) => item.style(FLASH_STYLE),
+                None => item,
+            }
+        })
+        .collect();
+    let title = if app.favourites_only {
+        " 235 favourites "
+    } else {
+        " 235 "
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, list_area, &mut app.selected);
+    app.list_area = list_area;
+
+    let mut scroll = 0;
+    let mut link = None;
+    let detail = match app.selected_game() {
+        Some(game) => {
+            let new_goals = app.flashes.get(&game.id).map(|flash| flash.goals_before);
+            let blink = app.flash_on(game, now).is_some();
+            let lines = detail_lines(game, &app.options, new_goals.filter(|_| blink));
+            // New goals at the end of a long game are scrolled into view
+            if new_goals.is_some() {
+                let height = detail_area.height.saturating_sub(2) as usize;
+                scroll = lines.len().saturating_sub(height) as u16;
+            }
+            // The link is on the line under the score, when the box has room
+            // for it inside its borders
+            let width = detail_area.width.saturating_sub(2);
+            let fits = width > 0 && detail_area.height > 3;
+            if let Some(url) = game_url(game).filter(|_| scroll == 0 && fits) {
+                let row = Rect::new(detail_area.x + 1, detail_area.y + 2, width, 1);
+                link = Some((row, url));
+            }
+            lines
+        }
+        None => vec![Line::from(app.options.language.text(Text::NoGames))],
+    };
+    app.link = link;
+    frame.render_widget(
+        Paragraph::new(detail)
+            .block(Block::default().borders(Borders::ALL))
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0)),
+        detail_area,
+    );
+}
+
+/// Every key and what it does, in a box over the middle of `area`
+fn draw_help(frame: &mut Frame, keymap: &Keymap, area: Rect) {
+    let mut lines = vec![
+        Line::from("↑/↓   next and previous game"),
+        Line::from("Esc   quit"),
+    ];
+    lines.extend(ACTIONS.iter().map(|(_, action, _, description)| {
+        Line::from(format!("{:<5} {}", keymap.key(*action), description))
+    }));
+    let [row] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [help_area] = Layout::horizontal([Constraint::Length(36)])
+        .flex(Flex::Center)
+        .areas(row);
+    frame.render_widget(Clear, help_area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Keys ")),
+        help_area,
+    );
+}
+
+/// Lines laid out by the library for the printed page, like the standings
+fn draw_page(frame: &mut Frame, lines: Vec<StyledLine>, scroll: u16, area: Rect) {
+    let lines: Vec<Line> = lines.iter().map(styled_line).collect();
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL))
+            .scroll((scroll, 0)),
+        area,
+    );
+}
+
+/// A line of the page in the colours of the teletext palette
+fn styled_line(line: &StyledLine) -> Line<'static> {
+    line.spans
+        .iter()
+        .map(|span| match Colour::of(span.role) {
+            Some(colour) => Span::styled(span.text.clone(), Style::default().fg(color(colour))),
+            None => Span::raw(span.text.clone()),
+        })
+        .collect::<Vec<Span>>()
+        .into()
+}
+
+fn color(colour: Colour) -> Color {
+    match colour {
+        Colour::White => Color::White,
+        Colour::Green => Color::Green,
+        Colour::Red => Color::Red,
+        Colour::Yellow => Color::Yellow,
+        Colour::Cyan => Color::Cyan,
+        Colour::Magenta => Color::Magenta,
+    }
+}
+
+/// Whether a team of the game or a scorer has `query` in their name.
+/// `query` is in lowercase.
+fn matches_search(game: &Game, query: &str, options: &RenderOptions) -> bool {
+    let mut names = vec![summary_line(game, options)];
+    for abbr in &[&game.home, &game.away] {
+        names.push(abbr.to_string());
+        if let Some(team) = TEAMS.iter().find(|team| team.abbreviation == abbr.as_str()) {
+            names.push(team.full_name());
+        }
+    }
+    names.extend(game.goals.iter().map(|goal| full_name(&goal.scorer)));
+    query.is_empty() || names.iter().any(|name| name.to_lowercase().contains(query))
+}
+
+/// Teams and score of a game on one line, like on the page
+fn summary_line(game: &Game, options: &RenderOptions) -> String {
+    let score = score_text(game, options).unwrap_or_default();
+    format!("{}{}", header(game, options), score)
+        .trim_end()
+        .to_string()
+}
+
+/// Inverted colours of a score change
+const FLASH_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+/// The selected game: the score, everything the page can add about the
+/// game, and every goal with its assists. Goals from `new_goals` on are
+/// shown inverted.
+fn detail_lines(
+    game: &Game,
+    options: &RenderOptions,
+    new_goals: Option<usize>,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(
+        summary_line(game, options),
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if let Some(url) = game_url(game) {
+        lines.push(Line::styled(
+            format!("Recap: {}", url),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::UNDERLINED),
+        ));
+    }
+    let info_lines = vec![
+        annotation_line(game, options),
+        shootout_line(game, options),
+        venue_line(game, options),
+        broadcasts_line(game, options),
+        duration_line(game, options),
+        series_line(game, options),
+        stats_line(game, options),
+    ];
+    lines.extend(
+        info_lines
+            .into_iter()
+            .flatten()
+            .map(|line| Line::styled(line, Style::default().fg(Color::Yellow))),
+    );
+    lines.push(Line::from(""));
+    lines.extend(game.goals.iter().enumerate().map(|(index, goal)| {
+        if new_goals.is_some_and(|first| index >= first) {
+            return Line::styled(goal_line(goal), FLASH_STYLE);
+        }
+        let colour =
+            if options.show_highlights && options.highlights.contains(&goal.scorer.last_name) {
+                Color::Yellow
+            } else if goal.special {
+                Color::Magenta
+            } else {
+                Color::Cyan
+            };
+        Line::styled(goal_line(goal), Style::default().fg(colour))
+    }));
+    lines
+}
+
+/// A goal with the full names of the scorer and the assisting players,
+/// like "44 PIT Sidney Crosby (Evgeni Malkin, Kris Letang)"
+fn goal_line(goal: &Goal) -> String {
+    let mut line = format!(
+        "{:>2} {:<3} {}",
+        goal.minute,
+        goal.team,
+        full_name(&goal.scorer)
+    );
+    if !goal.assists.is_empty() {
+        let assists: Vec<String> = goal.assists.iter().map(full_name).collect();
+        line.push_str(&format!(" ({})", assists.join(", ")));
+    }
+    line
+}
+
+fn full_name(player: &Player) -> String {
+    format!("{} {}", player.first_name, player.last_name)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nhl_235::api_types::APIResponse;
+    use nhl_235::parse_games;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn sections() -> Vec<Section> {
+        let json = include_str!("../tests/fixtures/sixteen_games.json");
+        let scores: APIResponse = serde_json::from_str(json).unwrap();
+        vec![Section {
+            title: String::new(),
+            games: parse_games(scores),
+        }]
+    }
+
+    fn app() -> App {
+        let options = RenderOptions::default().deterministic(true);
+        App::new(sections(), options, Keymap::default())
+    }
+
+    /// An error like the client's for a request that never got out
+    fn offline() -> ClientError {
+        let url_error = reqwest::Client::new().get("::").build().unwrap_err();
+        ClientError::from(url_error)
+    }
+
+    /// Serves the fixture and a standings table, or fails every request.
+    /// `options` are handed out as if the config had changed.
+    struct FakeProvider {
+        fail: bool,
+        options: Option<RenderOptions>,
+    }
+
+    impl Provider for FakeProvider {
+        fn scores(&self) -> Result<Vec<Section>, ClientError> {
+            if self.fail {
+                return Err(offline());
+            }
+            Ok(sections())
+        }
+
+        fn standings(&self) -> Result<Vec<Division>, ClientError> {
+            if self.fail {
+                return Err(offline());
+            }
+            Ok(vec![Division {
+                name: String::from("Metropolitan"),
+                teams: Vec::new(),
+            }])
+        }
+
+        fn schedule(&self) -> Result<Vec<Section>, ClientError> {
+            self.scores()
+        }
+
+        fn reload(&self) -> Option<Result<RenderOptions, String>> {
+            self.options.clone().map(Ok)
+        }
+    }
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn it_moves_the_selection_within_the_games() {
+        let mut app = app();
+        assert_eq!(app.selected.selected(), Some(0));
+        app.select_previous();
+        assert_eq!(app.selected.selected(), Some(0));
+        for _ in 0..app.games.len() + 3 {
+            app.select_next();
+        }
+        assert_eq!(app.selected.selected(), Some(app.games.len() - 1));
+
+        // A refresh keeps the same game selected
+        app.select_previous();
+        let id = app.selected_game().unwrap().id.clone();
+        app.replace_games(sections());
+        assert_eq!(app.selected_game().unwrap().id, id);
+    }
+
+    #[test]
+    fn it_shows_the_goals_of_the_selected_game() {
+        let mut app = app();
+        let first_goal = goal_line(&app.selected_game().unwrap().goals[0]);
+        let screen = screen(&mut app);
+        assert!(screen.contains(&first_goal));
+        assert!(screen.contains("q quit"));
+    }
+
+    #[test]
+    fn it_flashes_games_with_new_goals() {
+        let mut before = sections();
+        let game = before[0].games[2].as_mut().unwrap();
+        let goal = game.goals.pop().unwrap();
+        let goals_before = game.goals.len();
+        let mut app = App::new(before, RenderOptions::default(), Keymap::default());
+        assert!(app.flashes.is_empty());
+
+        app.replace_games(sections());
+        let id = app.games[2].id.clone();
+        assert_eq!(app.flashes.keys().collect::<Vec<_>>(), [&id]);
+        assert_eq!(app.flashes[&id].goals_before, goals_before);
+
+        // The new goal is shown inverted while the game is selected
+        app.select_next();
+        app.select_next();
+        let now = Instant::now();
+        let game = app.selected_game().unwrap();
+        let lines = detail_lines(game, &app.options, Some(goals_before));
+        let last = lines.last().unwrap();
+        assert_eq!(last.to_string(), goal_line(&goal));
+        assert_eq!(last.style, FLASH_STYLE);
+        assert!(app.flash_on(game, now).is_some());
+
+        app.tick(now + FLASH);
+        assert!(app.flashes.is_empty());
+    }
+
+    #[test]
+    fn it_selects_clicked_games_and_links() {
+        let mut app = app();
+        screen(&mut app);
+        // The list starts under the tabs and its top border
+        assert_eq!(app.click(5, 5), None);
+        assert_eq!(app.selected.selected(), Some(3));
+
+        screen(&mut app);
+        let url = game_url(app.selected_game().unwrap());
+        let (area, _) = app.link.clone().unwrap();
+        assert_eq!(app.click(area.x + 3, area.y), url);
+
+        // Clicks past the last game change nothing
+        assert_eq!(app.click(5, 30), None);
+        assert_eq!(app.selected.selected(), Some(3));
+    }
+
+    #[test]
+    fn it_draws_on_a_narrow_terminal() {
+        let mut app = app();
+        for width in 0..12 {
+            let mut terminal = Terminal::new(TestBackend::new(width, 40)).unwrap();
+            terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+            assert!(app.link.iter().all(|(area, _)| area.width > 0));
+        }
+    }
+
+    #[test]
+    fn it_searches_by_team_and_scorer() {
+        let mut app = app();
+        let provider = FakeProvider {
+            fail: false,
+            options: None,
+        };
+        let scorer = app.games[5].goals[0].scorer.last_name.clone();
+        app.handle(Action::Search, &provider);
+        for c in scorer.chars() {
+            app.type_search(KeyCode::Char(c.to_ascii_lowercase()));
+        }
+        assert!(app.searching);
+        assert!(app.shown_games().any(|game| game.id == app.games[5].id));
+        assert!(app.shown.len() < app.games.len());
+
+        app.type_search(KeyCode::Enter);
+        assert!(!app.searching);
+        assert!(screen(&mut app).contains(&format!("Search: {}", scorer.to_lowercase())));
+
+        // The first quit drops the search
+        assert!(app.handle(Action::Quit, &provider));
+        assert_eq!(app.shown.len(), app.games.len());
+
+        let team = app.games[7].home.clone();
+        app.query = team.to_lowercase();
+        app.filter(None);
+        assert_eq!(app.selected_game().unwrap().id, app.games[7].id);
+    }
+
+    #[test]
+    fn it_picks_up_a_changed_config() {
+        let mut app = app();
+        app.options.show_stats = true;
+        let options = RenderOptions::default().highlights(vec![String::from("Crosby")]);
+        app.refresh(&FakeProvider {
+            fail: false,
+            options: Some(options),
+        });
+        assert_eq!(app.options.highlights, ["Crosby"]);
+        assert!(app.options.show_stats);
+    }
+
+    #[test]
+    fn it_switches_between_the_tabs() {
+        let mut app = app();
+        app.switch_tab(
+            Tab::Standings,
+            &FakeProvider {
+                fail: false,
+                options: None,
+            },
+        );
+        assert!(screen(&mut app).contains("Metropolitan"));
+
+        // A failed refresh keeps the tab up with a note
+        app.switch_tab(
+            Tab::Scores,
+            &FakeProvider {
+                fail: true,
+                options: None,
+            },
+        );
+        let screen = screen(&mut app);
+        assert_eq!(app.games.len(), 16);
+        assert!(screen.contains("Can't refresh the scores"));
+    }
+
+    #[test]
+    fn it_filters_the_favourite_teams() {
+        let mut app = app();
+        let provider = FakeProvider {
+            fail: false,
+            options: None,
+        };
+        app.handle(Action::Favourites, &provider);
+        assert_eq!(app.shown.len(), 16);
+        assert!(app.status.is_some());
+
+        app.options.favorite_teams = vec![app.games[3].home.clone()];
+        app.handle(Action::Favourites, &provider);
+        assert!(app
+            .shown_games()
+            .all(|game| game.home == app.games[3].home || game.away == app.games[3].home));
+        assert_eq!(app.selected_game().unwrap().id, app.games[3].id);
+    }
+
+    #[test]
+    fn it_shows_the_keys_in_the_help() {
+        let mut app = app();
+        let provider = FakeProvider {
+            fail: false,
+            options: None,
+        };
+        app.handle(Action::Help, &provider);
+        assert!(screen(&mut app).contains("highlights on/off"));
+
+        // Any key closes the help without doing anything else
+        assert!(app.handle(Action::Quit, &provider));
+        assert!(!screen(&mut app).contains("highlights on/off"));
+    }
+
+    #[test]
+    fn it_reads_keys_from_the_config() {
+        let keys = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(action, key)| (action.to_string(), key.to_string()))
+                .collect::<HashMap<String, String>>()
+        };
+        let keymap = Keymap::with_keys(&keys(&[("refresh", "u")])).unwrap();
+        assert_eq!(keymap.action(KeyCode::Char('u')), Some(Action::Refresh));
+        assert_eq!(keymap.action(KeyCode::Char('r')), None);
+        assert_eq!(keymap.action(KeyCode::Down), Some(Action::Down));
+
+        assert!(Keymap::with_keys(&keys(&[("reload", "u")])).is_err());
+        assert!(Keymap::with_keys(&keys(&[("refresh", "ctrl-r")])).is_err());
+        assert_eq!(
+            Keymap::with_keys(&keys(&[("refresh", "q")])).err().unwrap(),
+            "key 'q' is set for both quit and refresh in [tui.keys]"
+        );
+    }
+
+    #[test]
+    fn it_lists_assists_by_full_name() {
+        let player = |first: &str, last: &str| Player {
+            first_name: String::from(first),
+            last_name: String::from(last),
+            team: String::from("PIT"),
+        };
+        let goal = Goal {
+            scorer: player("Sidney", "Crosby"),
+            assists: vec![player("Evgeni", "Malkin"), player("Kris", "Letang")],
+            minute: 44,
+            special: false,
+            team: String::from("PIT"),
+            period: String::from("1"),
+            strength: None,
+        };
+        assert_eq!(
+            goal_line(&goal),
+            "44 PIT Sidney Crosby (Evgeni Malkin, Kris Letang)"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! `235 versus` (or `235 h2h`), the games two teams have played against
+//! each other during a season, how the season series stands and the
+//! record and goals of the first team in them.
+
+use crate::format::format_iso_date;
+use crate::game::{Game, Record, Special};
+use crate::i18n::Text;
+use crate::options::RenderOptions;
+use crate::render::{summary_line, team_name, Section};
+use crate::style::{Role, StyledLine};
+use serde::Serialize;
+
+/// A game between the two teams and the date it was played on
+#[derive(Serialize)]
+pub struct Meeting<'a> {
+    pub date: &'a str,
+    #[serde(flatten)]
+    pub game: &'a Game,
+}
+
+/// The games of `team` against `opponent` during a season and the wins
+/// of each in the finished ones
+#[derive(Serialize)]
+pub struct SeasonSeries<'a> {
+    pub team: String,
+    pub opponent: String,
+    pub wins: usize,
+    pub losses: usize,
+    /// Record of `team` in the finished games, losses in overtime or a
+    /// shootout counted apart
+    pub record: Record,
+    pub goals_for: u64,
+    pub goals_against: u64,
+    pub games: Vec<Meeting<'a>>,
+}
+
+/// Collects the season series of `team` and `opponent` from the days of
+/// the season
+pub fn season_series<'a>(sections: &'a [Section], team: &str, opponent: &str) -> SeasonSeries<'a> {
+    let games = head_to_head(sections, team, opponent);
+    let (wins, losses) = series_tally(&games, team);
+    let (record, goals_for, goals_against) = aggregate_record(&games, team);
+    SeasonSeries {
+        team: team.to_string(),
+        opponent: opponent.to_string(),
+        wins,
+        losses,
+        record,
+        goals_for,
+        goals_against,
+        games,
+    }
+}
+
+/// Games between `team` and `opponent` in either order
+pub fn head_to_head<'a>(sections: &'a [Section], team: &str, opponent: &str) -> Vec<Meeting<'a>> {
+    sections
+        .iter()
+        .flat_map(|section| {
+            section.games.iter().flatten().map(move |game| Meeting {
+                date: section.title.as_str(),
+                game,
+            })
+        })
+        .filter(|meeting| {
+            let game = meeting.game;
+            (game.home == team && game.away == opponent)
+                || (game.home == opponent && game.away == team)
+        })
+        .collect()
+}
+
+/// Wins of `team` and of the other team in the finished games
+pub fn series_tally(games: &[Meeting], team: &str) -> (usize, usize) {
+    games
+        .iter()
+        .map(|meeting| meeting.game)
+        .filter(|game| game.status == "FINAL")
+        .fold((0, 0), |(wins, losses), game| {
+            let (own, other) = if game.home == team {
+                (game.home_score, game.away_score)
+            } else {
+                (game.away_score, game.home_score)
+            };
+            if own > other {
+                (wins + 1, losses)
+            } else {
+                (wins, losses + 1)
+            }
+        })
+}
+
+/// Record of `team` and the goals for and against it in the finished
+/// games
+pub fn aggregate_record(games: &[Meeting], team: &str) -> (Record, u64, u64) {
+    let mut record = Record {
+        wins: 0,
+        losses: 0,
+        ot: 0,
+    };
+    let (mut goals_for, mut goals_against) = (0, 0);
+    for game in games
+        .iter()
+        .map(|meeting| meeting.game)
+        .filter(|game| game.status == "FINAL")
+    {
+        let (own, other) = if game.home == team {
+            (game.home_score, game.away_score)
+        } else {
+            (game.away_score, game.home_score)
+        };
+        goals_for += own;
+        goals_against += other;
+        if own > other {
+            record.wins += 1;
+        } else if game.special == Special::Regulation {
+            record.losses += 1;
+        } else {
+            record.ot += 1;
+        }
+    }
+    (record, goals_for, goals_against)
+}
+
+/// The games of the two teams one per line under their dates, followed
+/// by the season series
+pub fn versus_lines(series: &SeasonSeries, options: &RenderOptions) -> Vec<StyledLine> {
+    let own_name = team_name(&series.team, options);
+    let opponent_name = team_name(&series.opponent, options);
+    let mut lines = vec![StyledLine::styled(
+        format!("{} - {}", own_name, opponent_name),
+        Role::Title,
+    )];
+
+    if series.games.is_empty() {
+        lines.push(StyledLine::styled(
+            options.language.text(Text::NoMeetings),
+            Role::Text,
+        ));
+        return lines;
+    }
+
+    for meeting in &series.games {
+        let mut line = StyledLine::styled(
+            format!("{:<11}", format_iso_date(meeting.date, options.language)),
+            Role::Info,
+        );
+        line.spans.extend(summary_line(meeting.game, options).spans);
+        lines.push(line);
+    }
+
+    lines.push(StyledLine::default());
+    lines.push(StyledLine::styled(
+        format!(
+            "{}: {} {}, {} {}",
+            options.language.text(Text::SeasonSeries),
+            own_name,
+            series.wins,
+            opponent_name,
+            series.losses
+        ),
+        Role::SeriesInfo,
+    ));
+    lines.push(StyledLine::styled(
+        format!(
+            "{} {}, {} {}-{}",
+            own_name,
+            series.record,
+            options.language.text(Text::Goals),
+            series.goals_for,
+            series.goals_against
+        ),
+        Role::SeriesInfo,
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{test_game_json, test_games};
+
+    fn sections() -> Vec<Section> {
+        let day = |date: &str, home: &str, away: &str, home_score: u64, away_score: u64| {
+            let start = format!("{}T23:00:00Z", date);
+            Section {
+                title: String::from(date),
+                games: test_games(&[test_game_json(
+                    r#"{"state":"FINAL"}"#,
+                    &start,
+                    (home, home_score),
+                    (away, away_score),
+                    "",
+                )]),
+            }
+        };
+        vec![
+            day("2024-10-16", "PIT", "TOR", 3, 2),
+            day("2024-11-02", "CAR", "PIT", 4, 1),
+            day("2024-12-05", "TOR", "PIT", 5, 1),
+            day("2025-01-10", "TOR", "PIT", 2, 3),
+        ]
+    }
+
+    #[test]
+    fn it_tallies_the_season_series() {
+        let sections = sections();
+        let games = head_to_head(&sections, "PIT", "TOR");
+        assert_eq!(games.len(), 3);
+        assert_eq!(series_tally(&games, "PIT"), (2, 1));
+        assert_eq!(series_tally(&games, "TOR"), (1, 2));
+
+        let (record, goals_for, goals_against) = aggregate_record(&games, "PIT");
+        assert_eq!(record.to_string(), "2-1-0");
+        assert_eq!((goals_for, goals_against), (7, 9));
+    }
+
+    #[test]
+    fn it_lists_the_games_and_the_series() {
+        let options = RenderOptions::default().deterministic(true);
+        let sections = sections();
+        let lines: Vec<String> = versus_lines(&season_series(&sections, "PIT", "TOR"), &options)
+            .iter()
+            .map(StyledLine::text)
+            .collect();
+
+        assert_eq!(lines[0], "Pittsburgh - Toronto");
+        assert_eq!(lines.len(), 1 + 3 + 3);
+        assert!(lines[1].contains("Pittsburgh       - Toronto"));
+        assert_eq!(lines[5], "Season series: Pittsburgh 2, Toronto 1");
+        assert_eq!(lines[6], "Pittsburgh 2-1-0, goals 7-9");
+
+        let none = versus_lines(&season_series(&sections, "PIT", "BOS"), &options);
+        assert_eq!(none[1].text(), "No games between the teams this season.");
+    }
+}
@@ -0,0 +1,786 @@
+//! Network paths tested against a local mock of nhl-score-api.
+//!
+//! The mock serves the recorded responses in `tests/fixtures`, so these
+//! tests run offline. Error statuses and slow responses are simulated to
+//! check how `ScoreClient` and the `235` binary behave when the API
+//! misbehaves.
+
+use nhl_235::{fetch_games, ClientConfig, ClientError, ScoreClient};
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{}.json", name))
+}
+
+fn fixture_json(name: &str) -> serde_json::Value {
+    let json = fs::read_to_string(fixture(name)).expect("fixture is readable");
+    serde_json::from_str(&json).expect("fixture is valid JSON")
+}
+
+fn client(server: &MockServer) -> ScoreClient {
+    ScoreClient::new(ClientConfig {
+        score_api_url: server.uri(),
+        timeout: Some(Duration::from_millis(500)),
+        max_rps: None,
+        ..ClientConfig::default()
+    })
+}
+
+/// Runs the `235` binary without config files in the home directory
+fn output_235(args: &[&str]) -> Output {
+    let home = env::temp_dir().join(format!("nhl-235-mock-{}", std::process::id()));
+    fs::create_dir_all(&home).expect("home directory is writable");
+    Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+        .args(args)
+        .env("HOME", &home)
+        .output()
+        .expect("binary runs")
+}
+
+fn run_235(args: &[&str]) -> String {
+    String::from_utf8(output_235(args).stdout).expect("output is UTF-8")
+}
+
+#[tokio::test]
+async fn it_fetches_the_latest_scores() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture_json("live")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let scores = client(&server).latest().await.unwrap();
+    assert_eq!(scores.date.unwrap().raw, "2024-10-16");
+    assert!(!scores.games.is_empty());
+}
+
+#[tokio::test]
+async fn it_fetches_a_date() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores"))
+        .and(query_param("startDate", "2024-10-16"))
+        .and(query_param("endDate", "2024-10-16"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![fixture_json("live")]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 10, 16).unwrap();
+    let scores = client(&server).for_date(date).await.unwrap();
+    assert!(!scores.games.is_empty());
+}
+
+#[tokio::test]
+async fn it_fetches_a_roster() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/roster/PIT/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "forwards": [{
+                "firstName": {"default": "Sidney"},
+                "lastName": {"default": "Crosby"},
+                "sweaterNumber": 87,
+                "positionCode": "C",
+                "birthCountry": "CAN"
+            }],
+            "defensemen": [],
+            "goalies": []
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ScoreClient::new(ClientConfig {
+        nhl_api_url: server.uri(),
+        max_rps: None,
+        ..ClientConfig::default()
+    });
+    let roster = client.roster("PIT").await.unwrap();
+    assert_eq!(roster.forwards[0].sweater_number, Some(87));
+}
+
+#[tokio::test]
+async fn it_reports_too_many_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+
+    match client(&server).latest().await {
+        Err(ClientError::Status(e)) => assert_eq!(e.status().map(|s| s.as_u16()), Some(429)),
+        other => panic!("expected a status error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn it_times_out_on_a_slow_api() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(fixture_json("live"))
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    assert!(matches!(
+        client(&server).latest().await,
+        Err(ClientError::Timeout(_))
+    ));
+}
+
+#[tokio::test]
+async fn it_reports_malformed_data() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>maintenance</html>"))
+        .mount(&server)
+        .await;
+
+    assert!(matches!(
+        client(&server).latest().await,
+        Err(ClientError::Decode(_))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_prints_the_page_from_the_api() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture_json("live")))
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let fetched = tokio::task::spawn_blocking(move || {
+        run_235(&["--deterministic", "--max-rps", "100", "--api-url", &uri])
+    })
+    .await
+    .unwrap();
+    assert!(fetched.starts_with("Pittsburgh       - Toronto"));
+
+    let mocked = run_235(&[
+        "--deterministic",
+        "--mock",
+        fixture("live").to_str().unwrap(),
+    ]);
+    assert_eq!(fetched, mocked);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_explains_api_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let output = tokio::task::spawn_blocking(move || run_235(&["--api-url", &uri]))
+        .await
+        .unwrap();
+    assert!(output.starts_with("ERROR: API is getting too many requests."));
+}
+
+#[test]
+fn binary_warns_about_missing_highlights() {
+    let live = fixture("live");
+    let mock = live.to_str().unwrap();
+
+    let output = output_235(&["--stats", "--mock", mock]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("WARNING: No highlighted players found in"));
+
+    let output = output_235(&["--stats", "--require-config", "--mock", mock]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("ERROR: No highlighted players found in"));
+}
+
+#[test]
+fn binary_takes_score_flags_with_or_without_the_subcommand() {
+    let live = fixture("live");
+    let mock = live.to_str().unwrap();
+
+    let legacy = run_235(&["--deterministic", "--duration", "--mock", mock]);
+    let subcommand = run_235(&["--deterministic", "--mock", mock, "scores", "--duration"]);
+    assert_eq!(legacy, subcommand);
+}
+
+#[test]
+fn binary_delivers_events_once_and_replays_them() {
+    let dir = env::temp_dir().join(format!("nhl-235-events-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let state = dir.join("state.json");
+    let log = dir.join("events.jsonl");
+    let _ = fs::remove_file(&log);
+    let live = fixture("live");
+    let notify = [
+        "--mock",
+        live.to_str().unwrap(),
+        "notify-once",
+        "--state",
+        state.to_str().unwrap(),
+        "--log",
+        log.to_str().unwrap(),
+    ];
+
+    let first = run_235(&notify);
+    assert!(!first.is_empty());
+
+    // Losing the state doesn't send the same events again
+    fs::remove_file(&state).unwrap();
+    assert_eq!(run_235(&notify), "");
+
+    let replayed = run_235(&[
+        "events",
+        "--since",
+        "2000-01-01",
+        "--log",
+        log.to_str().unwrap(),
+    ]);
+    assert_eq!(replayed.lines().count(), first.lines().count());
+    assert_eq!(
+        run_235(&[
+            "events",
+            "--since",
+            "2999-01-01",
+            "--log",
+            log.to_str().unwrap()
+        ]),
+        ""
+    );
+}
+
+#[test]
+fn binary_delivers_events_once_from_concurrent_runs() {
+    let dir = env::temp_dir().join(format!("nhl-235-concurrent-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let state = dir.join("state.json");
+    let log = dir.join("events.jsonl");
+    let _ = fs::remove_file(&state);
+    let _ = fs::remove_file(&log);
+    let live = fixture("live");
+
+    let runs: Vec<_> = (0..6)
+        .map(|_| {
+            Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+                .args([
+                    "--mock",
+                    live.to_str().unwrap(),
+                    "notify-once",
+                    "--state",
+                    state.to_str().unwrap(),
+                    "--log",
+                    log.to_str().unwrap(),
+                ])
+                .env("HOME", &dir)
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("binary runs")
+        })
+        .collect();
+    let mut delivered: Vec<String> = Vec::new();
+    for run in runs {
+        let output = run.wait_with_output().unwrap();
+        assert!(output.status.success());
+        delivered.extend(
+            String::from_utf8(output.stdout)
+                .unwrap()
+                .lines()
+                .map(String::from),
+        );
+    }
+
+    let logged = fs::read_to_string(&log).unwrap();
+    assert!(!delivered.is_empty());
+    assert_eq!(logged.lines().count(), delivered.len());
+    let mut unique = delivered.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), delivered.len());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_pings_the_healthcheck() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture_json("live")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/ping/check"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = env::temp_dir().join(format!("nhl-235-healthcheck-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".235.toml"),
+        format!("[notify]\nhealthcheck = \"{}/ping/check\"", server.uri()),
+    )
+    .unwrap();
+
+    let uri = server.uri();
+    let state = dir.join("state.json");
+    let log = dir.join("events.jsonl");
+    tokio::task::spawn_blocking(move || {
+        Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+            .args([
+                "--max-rps",
+                "100",
+                "--api-url",
+                &uri,
+                "notify-once",
+                "--state",
+                state.to_str().unwrap(),
+                "--log",
+                log.to_str().unwrap(),
+            ])
+            .env("HOME", &dir)
+            .output()
+            .expect("binary runs")
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_keeps_finished_games_for_history() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture_json("overtime")))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let home = env::temp_dir().join(format!("nhl-235-history-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    let _ = fs::remove_file(home.join(".235.toml"));
+    let _ = fs::remove_file(home.join(".235.results.jsonl"));
+    let archive = home.join(".235.results.jsonl");
+    let config = home.join(".235.toml");
+    let run = move |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+            .args(args)
+            .env("HOME", &home)
+            .output()
+            .expect("binary runs");
+        String::from_utf8(output.stdout).expect("output is UTF-8")
+    };
+
+    let uri = server.uri();
+    let history = tokio::task::spawn_blocking(move || {
+        // Nothing is archived until the config turns it on
+        run(&["--deterministic", "--api-url", &uri]);
+        assert!(!archive.exists());
+        fs::write(&config, "[history]\narchive = true\n").unwrap();
+        // The second fetch finds the games already archived
+        run(&["--deterministic", "--api-url", &uri]);
+        run(&["--deterministic", "--api-url", &uri]);
+        run(&["--deterministic", "history", "--team", "PIT"])
+    })
+    .await
+    .unwrap();
+    let lines: Vec<&str> = history.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", history);
+    assert!(lines[0].ends_with("Toronto - Pittsburgh ot 1-2"));
+    assert_eq!(lines[2], "Pittsburgh 1-0-0");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_handles_days_without_games() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(Vec::<serde_json::Value>::new()))
+        .mount(&server)
+        .await;
+
+    let dir = env::temp_dir().join(format!("nhl-235-empty-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let state = dir.join("state.json");
+    let log = dir.join("events.jsonl");
+    let uri = server.uri();
+    let (page, notified) = tokio::task::spawn_blocking(move || {
+        let page = output_235(&["--deterministic", "--api-url", &uri, "--days", "3"]);
+        let notified = output_235(&[
+            "--api-url",
+            &uri,
+            "--days",
+            "3",
+            "notify-once",
+            "--state",
+            state.to_str().unwrap(),
+            "--log",
+            log.to_str().unwrap(),
+        ]);
+        (page, notified)
+    })
+    .await
+    .unwrap();
+
+    assert!(page.status.success());
+    assert_eq!(String::from_utf8_lossy(&page.stdout), "No games today.\n");
+    assert!(notified.status.success(), "{:?}", notified);
+    assert_eq!(String::from_utf8_lossy(&notified.stdout), "");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_prints_the_last_days_under_their_dates() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(vec![fixture_json("overtime"), fixture_json("live")]),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let output = tokio::task::spawn_blocking(move || {
+        run_235(&["--deterministic", "--api-url", &uri, "--days", "2"])
+    })
+    .await
+    .unwrap();
+    let headings: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("Oct "))
+        .collect();
+    assert_eq!(headings, ["Oct 15", "Oct 16"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_prints_the_schedule_in_utc_when_deterministic() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![fixture_json("live")]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let output = tokio::task::spawn_blocking(move || {
+        let home = env::temp_dir().join(format!("nhl-235-mock-{}", std::process::id()));
+        fs::create_dir_all(&home).unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+            .args(["--deterministic", "--api-url", &uri, "schedule"])
+            .env("HOME", &home)
+            .env("TZ", "Asia/Tokyo")
+            .output()
+            .expect("binary runs");
+        String::from_utf8(output.stdout).expect("output is UTF-8")
+    })
+    .await
+    .unwrap();
+    assert_eq!(output, "Oct 17\n01:00  Edmonton - Calgary\n");
+}
+
+#[test]
+fn binary_keeps_running_with_watch() {
+    let live = fixture("live");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+        .args(["--deterministic", "--mock", live.to_str().unwrap()])
+        .args(["--watch", "--interval", "5"])
+        .env("HOME", env::temp_dir())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("binary runs");
+
+    let mut first_line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first_line)
+        .unwrap();
+    let still_running = child.try_wait().unwrap().is_none();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(!first_line.is_empty());
+    assert!(still_running);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_keeps_watching_after_a_failed_fetch() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture_json("live")))
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let (first_line, errors, still_running) = tokio::task::spawn_blocking(move || {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+            .args(["--deterministic", "--api-url", &uri])
+            .args(["--watch", "--interval", "5"])
+            .env("HOME", env::temp_dir())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("binary runs");
+
+        // The first round fails, the page comes from the second one
+        let mut first_line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut first_line)
+            .unwrap();
+        let still_running = child.try_wait().unwrap().is_none();
+        child.kill().unwrap();
+        let output = child.wait_with_output().unwrap();
+        (
+            first_line,
+            String::from_utf8_lossy(&output.stderr).to_string(),
+            still_running,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(first_line.starts_with("Pittsburgh"), "{}", first_line);
+    assert!(
+        errors.contains("ERROR: API returned an error"),
+        "{}",
+        errors
+    );
+    assert!(still_running);
+}
+
+#[test]
+fn binary_transliterates_names_with_ascii_names() {
+    let home = env::temp_dir().join(format!("nhl-235-ascii-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    fs::write(home.join(".235.config"), "Stützle\n").unwrap();
+    let games = fixture("sixteen_games");
+    let stats = |ascii: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_nhl-235"));
+        command
+            .args([
+                "--deterministic",
+                "--stats",
+                "--mock",
+                games.to_str().unwrap(),
+            ])
+            .env("HOME", &home);
+        if ascii {
+            command.arg("--ascii-names");
+        }
+        String::from_utf8(command.output().unwrap().stdout).unwrap()
+    };
+
+    let plain = stats(false);
+    let ascii = stats(true);
+    assert!(plain.contains("Stützle"));
+    assert!(ascii.contains("Stutzle"));
+    assert!(ascii.is_ascii());
+}
+
+#[test]
+fn binary_adds_highlighted_players_from_the_command_line() {
+    let home = env::temp_dir().join(format!("nhl-235-highlight-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    fs::write(home.join(".235.config"), "Crosby\n").unwrap();
+    let games = fixture("sixteen_games");
+    let output = Command::new(env!("CARGO_BIN_EXE_nhl-235"))
+        .args([
+            "--stats",
+            "--highlight-player",
+            "Stützle",
+            "--mock",
+            games.to_str().unwrap(),
+        ])
+        .env("HOME", &home)
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Stützle"));
+    assert!(output.stderr.is_empty());
+}
+
+#[tokio::test]
+async fn it_fetches_parsed_games_of_several_dates() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![fixture_json("live")]))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let day = |d| chrono::NaiveDate::from_ymd_opt(2024, 10, d).unwrap();
+    let one_day = fetch_games(&client(&server), &[day(16)]).await.unwrap();
+    let two_days = fetch_games(&client(&server), &[day(16), day(17)])
+        .await
+        .unwrap();
+    assert!(!one_day.is_empty());
+    assert_eq!(two_days.len(), one_day.len() * 2);
+}
+
+#[test]
+fn binary_prints_the_games_as_json() {
+    let live = fixture("live");
+    let json = run_235(&["--json", "--mock", live.to_str().unwrap()]);
+    let games: serde_json::Value = serde_json::from_str(&json).expect("output is JSON");
+
+    let game = &games[0];
+    assert_eq!(game["home"], "PIT");
+    assert_eq!(game["away"], "TOR");
+    assert_eq!(game["score"], "2-1");
+    assert_eq!(game["goals"][1]["scorer"]["last_name"], "Crosby");
+    assert_eq!(game["goals"][1]["assists"][0]["last_name"], "Malkin");
+    assert_eq!(game["goals"][1]["minute"], 32);
+}
+
+#[test]
+fn binary_prints_the_raw_response() {
+    let live = fixture("live");
+    let raw = run_235(&["--raw", "--mock", live.to_str().unwrap()]);
+    let response: serde_json::Value = serde_json::from_str(&raw).expect("output is JSON");
+
+    assert_eq!(response, fixture_json("live"));
+    assert!(raw.lines().count() > 1);
+}
+
+#[tokio::test]
+async fn it_fetches_the_unparsed_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture_json("live")))
+        .mount(&server)
+        .await;
+
+    let response = client(&server).latest_json().await.unwrap();
+    assert_eq!(response, fixture_json("live"));
+}
+
+#[tokio::test]
+async fn it_fetches_the_playoff_bracket() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/playoff-bracket/2024"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "series": [{
+                "seriesLetter": "A",
+                "playoffRound": 1,
+                "topSeedTeam": {"abbrev": "FLA"},
+                "bottomSeedTeam": {"abbrev": "TBL"},
+                "topSeedWins": 4,
+                "bottomSeedWins": 1
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = ScoreClient::new(ClientConfig {
+        nhl_api_url: server.uri(),
+        ..ClientConfig::default()
+    });
+    let bracket = client.playoff_bracket(2024).await.unwrap();
+    assert_eq!(bracket.series[0].series_letter, "A");
+    assert_eq!(bracket.series[0].top_seed_wins, 4);
+}
+
+#[test]
+fn binary_shows_what_changed_between_snapshots() {
+    let mut before = fixture_json("live");
+    before["games"][0]["goals"]
+        .as_array_mut()
+        .expect("goals are a list")
+        .pop();
+    let path = env::temp_dir().join(format!("nhl-235-before-{}.json", std::process::id()));
+    fs::write(&path, before.to_string()).expect("temp dir is writable");
+
+    let live = fixture("live");
+    let changes = run_235(&["diff", path.to_str().unwrap(), live.to_str().unwrap()]);
+    fs::remove_file(&path).ok();
+    assert_eq!(
+        changes.lines().collect::<Vec<_>>(),
+        ["Pittsburgh - Toronto 2-1 Malkin 42"]
+    );
+
+    let same = run_235(&["diff", live.to_str().unwrap(), live.to_str().unwrap()]);
+    assert_eq!(same, "No changes.\n");
+}
+
+#[test]
+fn binary_prints_the_box_score_of_a_game() {
+    let live = fixture("live");
+    let boxscore = run_235(&["--mock", live.to_str().unwrap(), "game", "TOR-PIT"]);
+    assert!(boxscore.starts_with("Pittsburgh - Toronto 2-1\nPIT 5-2-1, TOR 4-3-0\n"));
+    assert!(boxscore.contains(" 32 PIT Sidney Crosby (Evgeni Malkin, Erik Karlsson) PPG\n"));
+
+    let json = run_235(&[
+        "--json",
+        "--mock",
+        live.to_str().unwrap(),
+        "game",
+        "PIT-TOR",
+    ]);
+    let games: serde_json::Value = serde_json::from_str(&json).expect("output is JSON");
+    assert_eq!(games.as_array().map(Vec::len), Some(1));
+    assert_eq!(games[0]["records"][0]["wins"], 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_lists_the_points_of_a_player() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/scores"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(vec![fixture_json("overtime"), fixture_json("live")]),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let uri = server.uri();
+    let output = tokio::task::spawn_blocking(move || {
+        run_235(&[
+            "--deterministic",
+            "--api-url",
+            &uri,
+            "player",
+            "Crosby",
+            "--days",
+            "2",
+        ])
+    })
+    .await
+    .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "Sidney Crosby (PIT)");
+    assert_eq!(lines.last(), Some(&"2 GP  3+1=4"));
+}
@@ -0,0 +1,151 @@
+//! Property tests feeding malformed, truncated and randomized API responses
+//! through the parser and the renderer. Neither may panic on any input.
+
+use nhl_235::parse_api_response;
+use nhl_235::render::{AnsiRenderer, Renderer};
+use nhl_235::RenderOptions;
+use proptest::prelude::*;
+use serde_json::{json, Value};
+
+const FIXTURE: &str = include_str!("fixtures/playoffs.json");
+
+fn options() -> RenderOptions {
+    RenderOptions::default()
+        .highlights(vec![String::from("Crosby"), String::from("")])
+        .show_highlights(true)
+        .show_stats(true)
+        .show_duration(true)
+        .show_venue(true)
+        .show_broadcasts(true)
+        .show_close_games(true)
+        .deterministic(true)
+}
+
+fn render(json: &str) {
+    if let Ok(games) = parse_api_response(json) {
+        AnsiRenderer.render_games(&games, &options());
+    }
+}
+
+fn team() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::from("PIT")),
+        Just(String::from("TOR")),
+        "[A-Z]{0,4}",
+    ]
+}
+
+fn period() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::from("1")),
+        Just(String::from("3")),
+        Just(String::from("OT")),
+        Just(String::from("SO")),
+        Just(String::from("0")),
+        any::<u64>().prop_map(|period| period.to_string()),
+        ".{0,3}",
+    ]
+}
+
+fn player() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::from("Sidney Crosby")),
+        Just(String::from("Crosby")),
+        Just(String::from("")),
+        Just(String::from(" ")),
+        ".{0,12}",
+    ]
+}
+
+fn goal() -> impl Strategy<Value = Value> {
+    (
+        period(),
+        team(),
+        player(),
+        prop::collection::vec(player(), 0..3),
+        prop::option::of(any::<u64>()),
+        prop::option::of(any::<u64>()),
+        prop::option::of(any::<bool>()),
+    )
+        .prop_map(|(period, team, scorer, assists, min, sec, empty_net)| {
+            json!({
+                "period": period,
+                "team": team,
+                "scorer": {"player": scorer},
+                "assists": assists
+                    .into_iter()
+                    .map(|player| json!({"player": player, "seasonTotal": 1}))
+                    .collect::<Vec<Value>>(),
+                "min": min,
+                "sec": sec,
+                "emptyNet": empty_net,
+            })
+        })
+}
+
+fn game() -> impl Strategy<Value = Value> {
+    (
+        prop_oneof![
+            Just("LIVE"),
+            Just("FINAL"),
+            Just("PREVIEW"),
+            Just("POSTPONED"),
+        ],
+        prop::option::of(prop::collection::vec(goal(), 0..8)),
+        prop::option::of(any::<u64>()),
+        prop::option::of((any::<u64>(), any::<u64>())),
+        prop::option::of(any::<bool>()),
+        ".{0,25}",
+    )
+        .prop_map(|(state, goals, period, scores, series, start_time)| {
+            let mut game = json!({
+                "status": {"state": state},
+                "startTime": start_time,
+                "goals": goals,
+                "scores": {},
+                "teams": {
+                    "away": {"abbreviation": "PIT", "id": 5, "locationName": "Pittsburgh", "shortName": "Pittsburgh", "teamName": "Penguins"},
+                    "home": {"abbreviation": "TOR", "id": 10, "locationName": "Toronto", "shortName": "Toronto", "teamName": "Maple Leafs"}
+                },
+                "preGameStats": {"records": {}},
+                "currentStats": {"records": {}, "standings": {}}
+            });
+            if let Some(period) = period {
+                game["status"]["progress"] = json!({
+                    "currentPeriod": period,
+                    "currentPeriodOrdinal": "",
+                    "currentPeriodTimeRemaining": {"pretty": "", "min": 0, "sec": 0}
+                });
+            }
+            if let Some((home, away)) = scores {
+                game["scores"] = json!({"TOR": home, "PIT": away});
+            }
+            match series {
+                Some(true) => game["currentStats"]["playoffSeries"] = json!({"wins": {"TOR": 1}}),
+                Some(false) => game["currentStats"]["playoffSeries"] = json!({}),
+                None => (),
+            }
+            game
+        })
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_input_never_panics(input in ".*") {
+        render(&input);
+    }
+
+    #[test]
+    // Dropping just the trailing newline still leaves valid JSON
+    fn truncated_responses_never_panic(len in 0..FIXTURE.trim_end().len()) {
+        if let Some(prefix) = FIXTURE.get(..len) {
+            prop_assert!(parse_api_response(prefix).is_err());
+        }
+    }
+
+    #[test]
+    fn randomized_games_never_panic(games in prop::collection::vec(game(), 0..4)) {
+        let response = json!({"date": null, "games": games, "errors": null});
+        render(&response.to_string());
+    }
+}
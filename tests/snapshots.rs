@@ -0,0 +1,109 @@
+//! Golden-file tests for the 235 page.
+//!
+//! Every recorded API response in `tests/fixtures` is rendered with colours
+//! off and on and compared to `tests/snapshots/<fixture>.<plain|ansi>.txt`.
+//! Goals in time order are compared to `<fixture>.time.txt` and the wide
+//! layout to `<fixture>.wide.txt`.
+//!
+//! After an intentional change to the layout, update the snapshots with
+//!
+//! ```text
+//! BLESS=1 cargo test --test snapshots
+//! ```
+//!
+//! and review the diff before committing.
+
+use nhl_235::api_types::APIResponse;
+use nhl_235::parse_games;
+use nhl_235::render::{AnsiRenderer, PlainRenderer, Renderer};
+use nhl_235::{GoalOrder, Layout, RenderOptions};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const FIXTURES: [&str; 6] = [
+    "live",
+    "overtime",
+    "shootout",
+    "playoffs",
+    "postponed",
+    "sixteen_games",
+];
+
+fn options() -> RenderOptions {
+    RenderOptions::default()
+        .highlights(vec![
+            String::from("Crosby"),
+            String::from("Barkov"),
+            String::from("Hughes"),
+        ])
+        .show_highlights(true)
+        .show_stats(true)
+        .show_venue(true)
+        .show_broadcasts(true)
+        .show_close_games(true)
+        .show_duration(true)
+        .deterministic(true)
+}
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn check(name: &str, variant: &str, renderer: &dyn Renderer, options: &RenderOptions) {
+    let fixture = root().join("fixtures").join(format!("{}.json", name));
+    let json = fs::read_to_string(&fixture).expect("fixture is readable");
+    let scores: APIResponse = serde_json::from_str(&json).expect("fixture is valid API JSON");
+    let actual = renderer.render_games(&parse_games(scores), options);
+
+    let snapshot = root()
+        .join("snapshots")
+        .join(format!("{}.{}.txt", name, variant));
+    if env::var_os("BLESS").is_some() {
+        fs::write(&snapshot, &actual).expect("snapshot is writable");
+        return;
+    }
+
+    let expected = fs::read_to_string(&snapshot).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}, run with BLESS=1 to create it",
+            snapshot.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "{} differs from the rendered output, run with BLESS=1 to update it\n\n{}",
+        snapshot.display(),
+        actual
+    );
+}
+
+#[test]
+fn plain_snapshots_match() {
+    for name in FIXTURES.iter() {
+        check(name, "plain", &PlainRenderer, &options());
+    }
+}
+
+#[test]
+fn ansi_snapshots_match() {
+    for name in FIXTURES.iter() {
+        check(name, "ansi", &AnsiRenderer, &options());
+    }
+}
+
+#[test]
+fn time_order_snapshots_match() {
+    let options = options().goal_order(GoalOrder::Time);
+    for name in FIXTURES.iter() {
+        check(name, "time", &PlainRenderer, &options);
+    }
+}
+
+#[test]
+fn wide_snapshots_match() {
+    let options = options().layout(Layout::WIDE);
+    for name in FIXTURES.iter() {
+        check(name, "wide", &PlainRenderer, &options);
+    }
+}